@@ -0,0 +1,67 @@
+"""Nightly low-stock report."""
+
+import csv
+import dataclasses
+from pathlib import Path
+from typing import Iterable
+
+LOW_STOCK_THRESHOLD = 5
+
+
+@dataclasses.dataclass
+class Config:
+    """Report settings."""
+
+    output: Path
+    threshold: int = LOW_STOCK_THRESHOLD
+
+
+@dataclasses.dataclass
+class StockLevel:
+    item: int
+    available: int
+    reserved: int
+
+    @property
+    def is_low(self) -> bool:
+        return self.available <= LOW_STOCK_THRESHOLD
+
+
+class Report:
+    """Collects stock levels and writes them as CSV."""
+
+    def __init__(self, config: Config):
+        self.config = config
+        self.rows: list[StockLevel] = []
+
+    def add(self, level: StockLevel) -> None:
+        if level.available <= self.config.threshold:
+            self.rows.append(level)
+
+    def extend(self, levels: Iterable[StockLevel]) -> None:
+        for level in levels:
+            self.add(level)
+
+    def write(self) -> int:
+        with self.config.output.open("w", newline="") as f:
+            writer = csv.writer(f)
+            writer.writerow(["item", "available", "reserved"])
+            for row in sorted(self.rows, key=lambda r: r.item):
+                writer.writerow([row.item, row.available, row.reserved])
+        return len(self.rows)
+
+
+def load_levels(path: Path) -> list[StockLevel]:
+    with path.open() as f:
+        return [StockLevel(int(r["item"]), int(r["available"]), int(r["reserved"])) for r in csv.DictReader(f)]
+
+
+def main() -> None:
+    config = Config(output=Path("low_stock.csv"))
+    report = Report(config)
+    report.extend(load_levels(Path("stock.csv")))
+    print(f"{report.write()} low-stock items")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,50 @@
+package inventory;
+
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+import java.util.stream.Collectors;
+
+/** Tracks reservations per order. */
+public class Reservations {
+    /** Settings for reservation expiry. */
+    public record Config(int ttlSeconds, boolean allowBackorder) {}
+
+    public interface Listener {
+        void onReserved(long orderId, int item, int count);
+    }
+
+    private final Config config;
+    private final Map<Long, Map<Integer, Integer>> byOrder = new HashMap<>();
+    private Listener listener;
+
+    public Reservations(Config config) {
+        this.config = config;
+    }
+
+    public void setListener(Listener listener) {
+        this.listener = listener;
+    }
+
+    /** Record a reservation for an order. */
+    public void reserve(long orderId, int item, int count) {
+        byOrder.computeIfAbsent(orderId, k -> new HashMap<>()).merge(item, count, Integer::sum);
+        if (listener != null) {
+            listener.onReserved(orderId, item, count);
+        }
+    }
+
+    /** Release every reservation held by an order. */
+    public Map<Integer, Integer> release(long orderId) {
+        Map<Integer, Integer> held = byOrder.remove(orderId);
+        return held == null ? Map.of() : held;
+    }
+
+    public List<Long> ordersHolding(int item) {
+        return byOrder.entrySet().stream()
+            .filter(e -> e.getValue().containsKey(item))
+            .map(Map.Entry::getKey)
+            .sorted()
+            .collect(Collectors.toList());
+    }
+}
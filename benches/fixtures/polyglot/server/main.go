@@ -0,0 +1,71 @@
+// Package main serves the inventory HTTP API.
+package main
+
+import (
+	"encoding/json"
+	"log"
+	"net/http"
+	"strconv"
+	"sync"
+)
+
+// Config holds server settings.
+type Config struct {
+	Addr           string
+	AllowBackorder bool
+}
+
+// StockLevel is the wire format for one item.
+type StockLevel struct {
+	Item      int `json:"item"`
+	Available int `json:"available"`
+	Reserved  int `json:"reserved"`
+}
+
+// Store is an in-memory stock table.
+type Store struct {
+	mu       sync.Mutex
+	stock    map[int]int
+	reserved map[int]int
+}
+
+// NewStore returns an empty store.
+func NewStore() *Store {
+	return &Store{stock: map[int]int{}, reserved: map[int]int{}}
+}
+
+// Level reports the stock level of an item.
+func (s *Store) Level(item int) StockLevel {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	return StockLevel{Item: item, Available: s.stock[item] - s.reserved[item], Reserved: s.reserved[item]}
+}
+
+// Reserve holds units of an item.
+func (s *Store) Reserve(item, count int, backorder bool) bool {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if s.stock[item]-s.reserved[item] < count && !backorder {
+		return false
+	}
+	s.reserved[item] += count
+	return true
+}
+
+func stockHandler(store *Store) http.HandlerFunc {
+	return func(w http.ResponseWriter, r *http.Request) {
+		item, err := strconv.Atoi(r.URL.Query().Get("item"))
+		if err != nil {
+			http.Error(w, "bad item", http.StatusBadRequest)
+			return
+		}
+		_ = json.NewEncoder(w).Encode(store.Level(item))
+	}
+}
+
+func main() {
+	config := Config{Addr: ":8080"}
+	store := NewStore()
+	http.HandleFunc("/stock", stockHandler(store))
+	log.Fatal(http.ListenAndServe(config.Addr, nil))
+}
@@ -0,0 +1,109 @@
+//! Inventory core: items, stock levels and reservations.
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// Identifier for a stocked item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ItemId(pub u64);
+
+/// Errors raised by [`Inventory`] operations.
+#[derive(Debug)]
+pub enum InventoryError {
+    UnknownItem(ItemId),
+    Insufficient { item: ItemId, wanted: u32, available: u32 },
+}
+
+impl fmt::Display for InventoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownItem(id) => write!(f, "unknown item {}", id.0),
+            Self::Insufficient { item, wanted, available } => {
+                write!(f, "item {}: wanted {wanted}, have {available}", item.0)
+            }
+        }
+    }
+}
+
+/// Runtime configuration.
+pub struct Config {
+    pub low_stock_threshold: u32,
+    pub allow_backorder: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            low_stock_threshold: 5,
+            allow_backorder: false,
+        }
+    }
+}
+
+/// Stock levels keyed by item.
+pub struct Inventory {
+    config: Config,
+    stock: HashMap<ItemId, u32>,
+    reserved: HashMap<ItemId, u32>,
+}
+
+impl Inventory {
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            stock: HashMap::new(),
+            reserved: HashMap::new(),
+        }
+    }
+
+    /// Add `count` units of `item`.
+    pub fn restock(&mut self, item: ItemId, count: u32) {
+        *self.stock.entry(item).or_default() += count;
+    }
+
+    /// Units on hand that are not reserved.
+    pub fn available(&self, item: ItemId) -> u32 {
+        let on_hand = self.stock.get(&item).copied().unwrap_or(0);
+        let held = self.reserved.get(&item).copied().unwrap_or(0);
+        on_hand.saturating_sub(held)
+    }
+
+    /// Hold `count` units for a pending order.
+    pub fn reserve(&mut self, item: ItemId, count: u32) -> Result<(), InventoryError> {
+        if !self.stock.contains_key(&item) {
+            return Err(InventoryError::UnknownItem(item));
+        }
+        let available = self.available(item);
+        if available < count && !self.config.allow_backorder {
+            return Err(InventoryError::Insufficient {
+                item,
+                wanted: count,
+                available,
+            });
+        }
+        *self.reserved.entry(item).or_default() += count;
+        Ok(())
+    }
+
+    /// Items at or below the configured threshold.
+    pub fn low_stock(&self) -> Vec<ItemId> {
+        let mut low: Vec<ItemId> = self
+            .stock
+            .keys()
+            .copied()
+            .filter(|&id| self.available(id) <= self.config.low_stock_threshold)
+            .collect();
+        low.sort_by_key(|id| id.0);
+        low
+    }
+}
+
+pub trait Notifier {
+    fn notify(&self, message: &str);
+}
+
+pub fn report_low_stock(inventory: &Inventory, notifier: &dyn Notifier) {
+    for id in inventory.low_stock() {
+        notifier.notify(&format!("item {} is low", id.0));
+    }
+}
@@ -0,0 +1,67 @@
+import { EventEmitter } from "events";
+
+/** Options accepted by {@link InventoryClient}. */
+export interface Config {
+  baseUrl: string;
+  timeoutMs?: number;
+  retries?: number;
+}
+
+export type ItemId = number;
+
+export interface StockLevel {
+  item: ItemId;
+  available: number;
+  reserved: number;
+}
+
+export enum ClientState {
+  Idle,
+  Loading,
+  Failed,
+}
+
+/** Thin HTTP wrapper around the inventory service. */
+export class InventoryClient extends EventEmitter {
+  private state: ClientState = ClientState.Idle;
+
+  constructor(private readonly config: Config) {
+    super();
+  }
+
+  /** Fetch the stock level for one item. */
+  async stock(item: ItemId): Promise<StockLevel> {
+    return this.request<StockLevel>(`/items/${item}/stock`);
+  }
+
+  /** Reserve units of an item; rejects when stock is insufficient. */
+  async reserve(item: ItemId, count: number): Promise<void> {
+    await this.request(`/items/${item}/reserve`, { method: "POST", body: JSON.stringify({ count }) });
+    this.emit("reserved", item, count);
+  }
+
+  private async request<T>(path: string, init: RequestInit = {}): Promise<T> {
+    this.state = ClientState.Loading;
+    const retries = this.config.retries ?? 2;
+    for (let attempt = 0; ; attempt++) {
+      try {
+        const res = await fetch(this.config.baseUrl + path, init);
+        if (!res.ok) throw new Error(`HTTP ${res.status}`);
+        this.state = ClientState.Idle;
+        return (await res.json()) as T;
+      } catch (err) {
+        if (attempt >= retries) {
+          this.state = ClientState.Failed;
+          throw err;
+        }
+      }
+    }
+  }
+}
+
+export function lowStock(levels: StockLevel[], threshold = 5): StockLevel[] {
+  return levels.filter((l) => l.available <= threshold);
+}
+
+export const formatLevel = (level: StockLevel): string =>
+  `#${level.item}: ${level.available} available, ${level.reserved} reserved`;
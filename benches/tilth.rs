@@ -0,0 +1,55 @@
+//! Baselines for the hot paths: per-language outlines, `map` over a small
+//! polyglot tree, and symbol/content search. Run with `cargo bench`.
+
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use tilth::cache::OutlineCache;
+
+fn fixture() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("benches/fixtures/polyglot")
+}
+
+const OUTLINE_FILES: &[&str] = &[
+    "src/lib.rs",
+    "web/client.ts",
+    "scripts/report.py",
+    "server/main.go",
+    "server/Reservations.java",
+];
+
+fn outline(c: &mut Criterion) {
+    let mut group = c.benchmark_group("outline");
+    for rel in OUTLINE_FILES {
+        let path = fixture().join(rel);
+        let content = std::fs::read_to_string(&path).unwrap();
+        group.bench_function(*rel, |b| {
+            b.iter(|| tilth::outline_named(black_box(&content), &path));
+        });
+    }
+    group.finish();
+}
+
+fn map(c: &mut Criterion) {
+    let scope = fixture();
+    c.bench_function("map/polyglot", |b| {
+        // A fresh cache each time: measure parsing, not cache hits.
+        b.iter(|| tilth::map::generate(&scope, 3, None, &OutlineCache::new()).unwrap());
+    });
+}
+
+fn search(c: &mut Criterion) {
+    let scope = fixture();
+    let cache = OutlineCache::new();
+    let mut group = c.benchmark_group("search");
+    for query in ["Config", "reserve", "low stock"] {
+        group.bench_function(query, |b| {
+            b.iter(|| tilth::run(black_box(query), &scope, None, None, None, &cache).unwrap());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, outline, map, search);
+criterion_main!(benches);
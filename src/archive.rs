@@ -0,0 +1,122 @@
+//! Source archives as a scope: `.tar.gz` / `.tgz` / `.tar` / `.zip` are read
+//! entry by entry into memory, never extracted to disk.
+
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Component, Path, PathBuf};
+
+use crate::error::TilthError;
+
+/// Entries larger than this are listed with their size but not loaded.
+const MAX_ENTRY_SIZE: u64 = 500_000;
+
+/// A regular file inside an archive.
+pub struct ArchiveEntry {
+    /// Path within the archive, relative and normalized.
+    pub path: PathBuf,
+    /// Uncompressed size in bytes.
+    pub size: u64,
+    /// Contents; empty when the entry exceeds the size cap.
+    pub data: Vec<u8>,
+}
+
+enum Kind {
+    TarGz,
+    Tar,
+    Zip,
+}
+
+fn kind(path: &Path) -> Option<Kind> {
+    let ext = |p: &Path| p.extension()?.to_str().map(str::to_ascii_lowercase);
+    match ext(path)?.as_str() {
+        "tgz" => Some(Kind::TarGz),
+        "gz" if ext(Path::new(path.file_stem()?)).as_deref() == Some("tar") => Some(Kind::TarGz),
+        "tar" => Some(Kind::Tar),
+        "zip" => Some(Kind::Zip),
+        _ => None,
+    }
+}
+
+/// Is `path` a supported archive file (by name)?
+#[must_use]
+pub fn is_archive(path: &Path) -> bool {
+    kind(path).is_some() && path.is_file()
+}
+
+/// Read every regular file in the archive at `path`, in archive order.
+pub fn read_entries(path: &Path) -> Result<Vec<ArchiveEntry>, TilthError> {
+    let file = File::open(path).map_err(|e| TilthError::from_io(path, e))?;
+    let reader = BufReader::new(file);
+    let io_err = |e: std::io::Error| TilthError::IoError {
+        path: path.to_path_buf(),
+        source: e,
+    };
+    match kind(path) {
+        Some(Kind::TarGz) => {
+            read_tar(tar::Archive::new(flate2::read::GzDecoder::new(reader))).map_err(io_err)
+        }
+        Some(Kind::Tar) => read_tar(tar::Archive::new(reader)).map_err(io_err),
+        Some(Kind::Zip) => read_zip(reader).map_err(|e| TilthError::ParseError {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        }),
+        None => Err(TilthError::InvalidQuery {
+            query: path.display().to_string(),
+            reason: "not a .tar.gz, .tgz, .tar or .zip archive".into(),
+        }),
+    }
+}
+
+fn read_tar<R: Read>(mut archive: tar::Archive<R>) -> std::io::Result<Vec<ArchiveEntry>> {
+    let mut entries = Vec::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let Some(path) = normalize(&entry.path()?) else {
+            continue;
+        };
+        let size = entry.size();
+        let mut data = Vec::new();
+        if size <= MAX_ENTRY_SIZE {
+            entry.read_to_end(&mut data)?;
+        }
+        entries.push(ArchiveEntry { path, size, data });
+    }
+    Ok(entries)
+}
+
+fn read_zip<R: Read + std::io::Seek>(reader: R) -> zip::result::ZipResult<Vec<ArchiveEntry>> {
+    let mut archive = zip::ZipArchive::new(reader)?;
+    let mut entries = Vec::new();
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        if !file.is_file() {
+            continue;
+        }
+        let Some(path) = file.enclosed_name().and_then(|p| normalize(&p)) else {
+            continue;
+        };
+        let size = file.size();
+        let mut data = Vec::new();
+        if size <= MAX_ENTRY_SIZE {
+            file.read_to_end(&mut data)?;
+        }
+        entries.push(ArchiveEntry { path, size, data });
+    }
+    Ok(entries)
+}
+
+/// Drop `./` prefixes; reject absolute paths and `..` so entries stay inside the scope.
+fn normalize(path: &Path) -> Option<PathBuf> {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(c) => out.push(c),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!out.as_os_str().is_empty()).then_some(out)
+}
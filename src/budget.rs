@@ -41,3 +41,42 @@ pub fn apply(output: &str, budget: u64) -> String {
         "{header}{clean_body}\n\n... truncated ({remaining_tokens} tokens omitted, budget: {budget})"
     )
 }
+
+/// Cap output at `max_bytes`, cutting at the last line boundary that leaves
+/// room for the truncation notice. Unlike [`apply`], the limit is exact: the
+/// result, notice included, never exceeds `max_bytes` unless the notice alone does.
+pub fn cap_bytes(output: &str, max_bytes: usize) -> String {
+    if output.len() <= max_bytes {
+        return output.to_string();
+    }
+
+    let notice =
+        |omitted: usize| format!("\n... truncated ({omitted} bytes omitted, max: {max_bytes})");
+    // Sized for the largest possible omission, so the final notice always fits.
+    let limit = max_bytes.saturating_sub(notice(output.len()).len());
+    let safe_max = output.floor_char_boundary(limit);
+    let cut_point = output[..=safe_max.min(output.len() - 1)]
+        .rfind('\n')
+        .unwrap_or(0);
+
+    let kept = &output[..cut_point];
+    format!("{kept}{}", notice(output.len() - cut_point))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cap_bytes_cuts_at_line_boundary() {
+        let output = "# Map: src (depth 3)\nalpha.rs: one, two, three\nbeta.rs: four, five, six\n";
+        assert_eq!(cap_bytes(output, 1000), output);
+
+        let capped = cap_bytes(output, 70);
+        assert!(capped.len() <= 70, "got {} bytes:\n{capped}", capped.len());
+        assert_eq!(
+            capped,
+            "# Map: src (depth 3)\n... truncated (52 bytes omitted, max: 70)"
+        );
+    }
+}
@@ -1,29 +1,103 @@
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::SystemTime;
 
 use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::error::TilthError;
+
+/// Bumped when the saved layout or the outline text format changes; a cache
+/// file from another version loads empty.
+const SAVE_VERSION: u32 = 1;
 
 /// Cached outline entry.
 struct CacheEntry {
     outline: Arc<str>,
+    /// Tick of the last lookup that returned it, for LRU eviction.
+    last_used: AtomicU64,
+}
+
+impl CacheEntry {
+    fn new(outline: Arc<str>, now: u64) -> Self {
+        Self {
+            outline,
+            last_used: AtomicU64::new(now),
+        }
+    }
+}
+
+/// What a cached outline is filed under; see [`HashMode`].
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Key {
+    Mtime(PathBuf, SystemTime),
+    /// Content hash, file extension — which picks the language — and whether
+    /// the path marks a test file and a build script, which outline differently.
+    Content(u64, Option<OsString>, bool, bool),
+}
+
+/// How [`OutlineCache`] tells whether a file changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HashMode {
+    /// By path and modified time. No extra reads, but a `touch` or checkout
+    /// misses and an edit that keeps the mtime is not seen.
+    #[default]
+    Mtime,
+    /// By a hash of the file's bytes, read on every lookup. Edits always miss,
+    /// and identical files with the same extension share one outline unless
+    /// only one is a test file or build script, whose views depend on the path.
+    Content,
 }
 
 /// Outline cache keyed by (canonical path, mtime). If the file changes,
-/// mtime changes and the old entry is never hit again.
+/// mtime changes and the old entry is never hit again. [`HashMode::Content`]
+/// (see [`with_hashing`](Self::with_hashing)) keys on file content instead.
+///
+/// [`save`](Self::save) and [`load`](Self::load) carry it across runs. On disk
+/// an outline is keyed by content hash instead, since a checkout or copy
+/// changes mtimes without changing content.
 ///
 /// Value is `Arc<str>` — inline string data in the Arc allocation,
 /// one less indirection than `Arc<String>`.
+///
+/// `Send + Sync`: every method takes `&self`, so one cache can be shared by
+/// reference across threads (rayon walks, MCP requests). The `DashMap` is
+/// sharded, so lookups of different files rarely contend, and counters and
+/// recency are atomics.
 pub struct OutlineCache {
-    entries: DashMap<(PathBuf, SystemTime), CacheEntry>,
+    entries: DashMap<Key, CacheEntry>,
+    mode: HashMode,
+    /// Most entries kept; the least recently used go first.
+    capacity: Option<usize>,
+    clock: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
 }
 
+/// Counters from [`OutlineCache::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Outlines held now.
+    pub entries: usize,
+    pub hits: u64,
+    /// Lookups that had to compute, evicted entries asked for again included.
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+// Sharing across threads is part of the API; keep it from regressing.
+const _: fn() = || {
+    fn shared<T: Send + Sync>() {}
+    shared::<OutlineCache>();
+};
+
 impl Default for OutlineCache {
     fn default() -> Self {
-        Self {
-            entries: DashMap::new(),
-        }
+        Self::with_hashing(HashMode::default())
     }
 }
 
@@ -33,23 +107,404 @@ impl OutlineCache {
         Self::default()
     }
 
+    /// An empty cache that tells changed files apart by `mode`.
+    #[must_use]
+    pub fn with_hashing(mode: HashMode) -> Self {
+        Self {
+            entries: DashMap::new(),
+            mode,
+            capacity: None,
+            clock: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    /// An empty cache holding at most `capacity` outlines, for long-running
+    /// servers. Past that, the least recently used outline is evicted; asking
+    /// for it again recomputes it. Each eviction scans the cache, so keep
+    /// `capacity` to what a scan per insert can afford (tens of thousands).
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity: Some(capacity),
+            ..Self::default()
+        }
+    }
+
+    /// Outlines held now.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entry count and lookup counters since the cache was created.
+    #[must_use]
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.entries.len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+        }
+    }
+
     /// Get cached outline or compute and cache it. Accepts `&Path` (not `&PathBuf`).
     /// Uses `entry()` API to avoid TOCTOU race between get and insert.
+    /// With [`HashMode::Content`] the file is read to hash it; one that can't
+    /// be read falls back to its mtime.
     pub fn get_or_compute(
         &self,
         path: &Path,
         mtime: SystemTime,
         compute: impl FnOnce() -> String,
     ) -> Arc<str> {
-        match self.entries.entry((path.to_path_buf(), mtime)) {
-            Entry::Occupied(e) => Arc::clone(&e.get().outline),
+        let key = match self.mode {
+            HashMode::Content => std::fs::read(path).map_or_else(
+                |_| Key::Mtime(path.to_path_buf(), mtime),
+                |bytes| content_key(path, &bytes),
+            ),
+            HashMode::Mtime => Key::Mtime(path.to_path_buf(), mtime),
+        };
+        self.lookup(key, compute)
+    }
+
+    fn lookup(&self, key: Key, compute: impl FnOnce() -> String) -> Arc<str> {
+        let now = self.clock.fetch_add(1, Ordering::Relaxed);
+        let outline = match self.entries.entry(key) {
+            Entry::Occupied(e) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                e.get().last_used.store(now, Ordering::Relaxed);
+                return Arc::clone(&e.get().outline);
+            }
             Entry::Vacant(e) => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
                 let outline: Arc<str> = compute().into();
-                e.insert(CacheEntry {
-                    outline: Arc::clone(&outline),
-                });
+                e.insert(CacheEntry::new(Arc::clone(&outline), now));
                 outline
             }
+        };
+        self.evict();
+        outline
+    }
+
+    /// Drop least recently used entries until the cache fits its capacity.
+    fn evict(&self) {
+        let Some(capacity) = self.capacity else {
+            return;
+        };
+        while self.entries.len() > capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|e| e.value().last_used.load(Ordering::Relaxed))
+                .map(|e| e.key().clone());
+            let Some(oldest) = oldest else {
+                return;
+            };
+            if self.entries.remove(&oldest).is_some() {
+                self.evictions.fetch_add(1, Ordering::Relaxed);
+            }
         }
     }
+
+    /// [`get_or_compute`](Self::get_or_compute) keyed on the file's current
+    /// mtime (or content), so an edited file misses and is outlined again.
+    /// `compute` gets the file's content and only runs on a miss. Fails when
+    /// the file can't be stat'ed or read.
+    pub fn get_or_read(
+        &self,
+        path: &Path,
+        compute: impl FnOnce(&str) -> String,
+    ) -> Result<Arc<str>, TilthError> {
+        if self.mode == HashMode::Content {
+            let content =
+                std::fs::read_to_string(path).map_err(|e| TilthError::from_io(path, e))?;
+            let key = content_key(path, content.as_bytes());
+            return Ok(self.lookup(key, || compute(&content)));
+        }
+        let mtime = std::fs::metadata(path)
+            .and_then(|m| m.modified())
+            .map_err(|e| TilthError::from_io(path, e))?;
+        let mut failed = None;
+        let outline = self.get_or_compute(path, mtime, || match std::fs::read_to_string(path) {
+            Ok(content) => compute(&content),
+            Err(e) => {
+                failed = Some(e);
+                String::new()
+            }
+        });
+        match failed {
+            Some(e) => {
+                // Don't keep the empty placeholder for an unreadable file.
+                self.entries.remove(&Key::Mtime(path.to_path_buf(), mtime));
+                Err(TilthError::from_io(path, e))
+            }
+            None => Ok(outline),
+        }
+    }
+
+    /// Read a cache written by [`save`](Self::save). An outline is kept only
+    /// while its file's content still hashes the same; the rest are left to be
+    /// recomputed. A missing cache file, or one from another tilth version,
+    /// gives an empty cache.
+    pub fn load(path: &Path) -> Result<Self, TilthError> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::new()),
+            Err(e) => return Err(TilthError::from_io(path, e)),
+        };
+        let saved: SavedCache =
+            serde_json::from_str(&text).map_err(|e| TilthError::ParseError {
+                path: path.to_path_buf(),
+                reason: e.to_string(),
+            })?;
+        let cache = Self::new();
+        if saved.version != SAVE_VERSION {
+            return Ok(cache);
+        }
+        for entry in saved.entries {
+            let Ok(mtime) = std::fs::metadata(&entry.path).and_then(|m| m.modified()) else {
+                continue;
+            };
+            let Ok(content) = std::fs::read(&entry.path) else {
+                continue;
+            };
+            if content_hash(&content) == entry.hash {
+                cache.entries.insert(
+                    Key::Mtime(entry.path, mtime),
+                    CacheEntry::new(entry.outline.into(), 0),
+                );
+            }
+        }
+        Ok(cache)
+    }
+
+    /// Write the outlines of files unchanged since they were cached to `path`,
+    /// for a later [`load`](Self::load). Outlines cached by
+    /// [`HashMode::Content`] aren't tied to one path and are left out.
+    pub fn save(&self, path: &Path) -> Result<(), TilthError> {
+        let mut entries = Vec::new();
+        for item in &self.entries {
+            let Key::Mtime(file, mtime) = item.key() else {
+                continue;
+            };
+            let current = std::fs::metadata(file).and_then(|m| m.modified());
+            if current.ok() != Some(*mtime) {
+                continue;
+            }
+            let Ok(content) = std::fs::read(file) else {
+                continue;
+            };
+            entries.push(SavedEntry {
+                path: file.clone(),
+                hash: content_hash(&content),
+                outline: item.value().outline.to_string(),
+            });
+        }
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        let saved = SavedCache {
+            version: SAVE_VERSION,
+            entries,
+        };
+        let json = serde_json::to_string(&saved).expect("cache entries serialize");
+        std::fs::write(path, json).map_err(|e| TilthError::from_io(path, e))
+    }
+}
+
+/// On-disk form of an [`OutlineCache`].
+#[derive(Serialize, Deserialize)]
+struct SavedCache {
+    version: u32,
+    entries: Vec<SavedEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedEntry {
+    path: PathBuf,
+    hash: u64,
+    outline: String,
+}
+
+fn content_key(path: &Path, bytes: &[u8]) -> Key {
+    Key::Content(
+        content_hash(bytes),
+        path.extension().map(OsString::from),
+        crate::types::is_test_file(path),
+        crate::read::outline::build_script::is_build_script(path),
+    )
+}
+
+/// 64-bit FNV-1a of a file's bytes. Unlike `DefaultHasher`, stable across
+/// builds, so a saved cache stays valid after an upgrade.
+fn content_hash(bytes: &[u8]) -> u64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for &b in bytes {
+        h ^= u64::from(b);
+        h = h.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edited_file_is_outlined_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        std::fs::write(&file, "pub fn api() {}\n").unwrap();
+        let cache = OutlineCache::new();
+        let outline = |content: &str| content.trim().to_string();
+
+        assert_eq!(
+            &*cache.get_or_read(&file, outline).unwrap(),
+            "pub fn api() {}"
+        );
+        let hit = cache.get_or_read(&file, |_| panic!("re-parsed")).unwrap();
+        assert_eq!(&*hit, "pub fn api() {}");
+
+        // A later mtime, even within the filesystem's timestamp resolution.
+        std::fs::write(&file, "pub fn renamed() {}\n").unwrap();
+        let later = std::time::SystemTime::now() + std::time::Duration::from_secs(5);
+        std::fs::File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert_eq!(
+            &*cache.get_or_read(&file, outline).unwrap(),
+            "pub fn renamed() {}"
+        );
+
+        let missing = dir.path().join("gone.rs");
+        assert!(matches!(
+            cache.get_or_read(&missing, outline),
+            Err(TilthError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn content_hashing_shares_identical_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let (a, b) = (dir.path().join("a.rs"), dir.path().join("b.rs"));
+        std::fs::write(&a, "pub fn api() {}\n").unwrap();
+        std::fs::write(&b, "pub fn api() {}\n").unwrap();
+        let cache = OutlineCache::with_hashing(HashMode::Content);
+
+        let first = cache.get_or_read(&a, |c| c.trim().to_string()).unwrap();
+        let shared = cache.get_or_read(&b, |_| panic!("re-parsed")).unwrap();
+        assert!(Arc::ptr_eq(&first, &shared));
+
+        // Same mtime or not, new bytes miss.
+        let mtime = std::fs::metadata(&b).unwrap().modified().unwrap();
+        std::fs::write(&b, "pub fn edited() {}\n").unwrap();
+        let edited = cache.get_or_compute(&b, mtime, || "edited".into());
+        assert_eq!(&*edited, "edited");
+
+        // Test files and build scripts are outlined differently: no sharing.
+        std::fs::create_dir(dir.path().join("__tests__")).unwrap();
+        for other in ["__tests__/a.rs", "build.rs"] {
+            let other = dir.path().join(other);
+            std::fs::write(&other, "pub fn api() {}\n").unwrap();
+            let own = cache.get_or_read(&other, |_| other.display().to_string());
+            assert_eq!(&*own.unwrap(), other.display().to_string());
+        }
+    }
+
+    #[test]
+    fn capacity_evicts_least_recently_used() {
+        let cache = OutlineCache::with_capacity(2);
+        let mtime = SystemTime::UNIX_EPOCH;
+        let get = |name: &str| cache.get_or_compute(Path::new(name), mtime, || name.to_string());
+
+        get("a.rs");
+        get("b.rs");
+        get("a.rs"); // b.rs is now the least recently used
+        get("c.rs");
+        assert_eq!(cache.len(), 2);
+
+        let recomputed = std::cell::Cell::new(false);
+        cache.get_or_compute(Path::new("a.rs"), mtime, || panic!("a.rs was evicted"));
+        cache.get_or_compute(Path::new("b.rs"), mtime, || {
+            recomputed.set(true);
+            "b.rs".into()
+        });
+        assert!(recomputed.get());
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                entries: 2,
+                hits: 2,
+                misses: 4,
+                evictions: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn concurrent_lookups_agree() {
+        for cache in [OutlineCache::new(), OutlineCache::with_capacity(16)] {
+            let computed = AtomicU64::new(0);
+            std::thread::scope(|scope| {
+                for thread in 0..8 {
+                    let (cache, computed) = (&cache, &computed);
+                    scope.spawn(move || {
+                        for i in 0..2_000 {
+                            let name = format!("f{}.rs", (i * 7 + thread) % 64);
+                            let outline = cache.get_or_compute(
+                                Path::new(&name),
+                                SystemTime::UNIX_EPOCH,
+                                || {
+                                    computed.fetch_add(1, Ordering::Relaxed);
+                                    name.clone()
+                                },
+                            );
+                            assert_eq!(&*outline, name);
+                        }
+                    });
+                }
+            });
+            let stats = cache.stats();
+            assert_eq!(stats.hits + stats.misses, 16_000);
+            assert_eq!(stats.misses, computed.load(Ordering::Relaxed));
+            assert!(cache.len() <= cache.capacity.unwrap_or(64));
+        }
+    }
+
+    #[test]
+    fn saved_outlines_survive_a_reload_until_content_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        let saved = dir.path().join("outlines.json");
+        std::fs::write(&file, "pub fn api() {}\n").unwrap();
+        let mtime = || std::fs::metadata(&file).unwrap().modified().unwrap();
+
+        let cache = OutlineCache::new();
+        cache.get_or_compute(&file, mtime(), || "[1] fn api".into());
+        cache.save(&saved).unwrap();
+
+        // Same content: a hit, never computed.
+        let warm = OutlineCache::load(&saved).unwrap();
+        let outline = warm.get_or_compute(&file, mtime(), || panic!("re-parsed"));
+        assert_eq!(&*outline, "[1] fn api");
+
+        // Changed content: the stale outline is dropped.
+        std::fs::write(&file, "pub fn other() {}\n").unwrap();
+        let reloaded = OutlineCache::load(&saved).unwrap();
+        let outline = reloaded.get_or_compute(&file, mtime(), || "[1] fn other".into());
+        assert_eq!(&*outline, "[1] fn other");
+
+        // No cache file yet: a cold start, not an error.
+        let cold = OutlineCache::load(&dir.path().join("missing.json")).unwrap();
+        assert!(cold.entries.is_empty());
+    }
 }
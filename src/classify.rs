@@ -31,7 +31,7 @@ pub fn classify(query: &str, scope: &Path) -> QueryType {
     if (query.starts_with("./") || query.starts_with("../"))
         || (query.contains('/') && !query.contains(' '))
     {
-        let resolved = scope.join(query);
+        let resolved = scope.join(crate::lang::expand_home(Path::new(query)));
         return match resolved.try_exists() {
             Ok(true) => QueryType::FilePath(resolved),
             _ => QueryType::Fallthrough(query.into()),
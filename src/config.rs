@@ -0,0 +1,212 @@
+//! Project configuration: an optional `.tilth.toml` at the scope root.
+//!
+//! ```toml
+//! budget = 4000                 # default --budget
+//!
+//! [search]
+//! exclude = ["generated/**", "*.pb.go"]   # globs relative to the scope, also unmapped
+//! exclude_tests = true
+//! kinds = "!var"                # as --kinds
+//! limit = 20
+//! ignore_case_style = false     # as --ignore-case-style
+//! ignore_case = false           # as --ignore-case
+//!
+//! [outline]
+//! style = "names"               # standard, names, stub, grouped
+//! body_lines = 2
+//! max_name_len = 40             # as --max-name-len
+//! byte_ranges = false           # as --byte-ranges
+//! collapse_accessors = false    # as --collapse-accessors
+//! unused_imports = false        # as --unused-imports
+//! format_version = 1            # as --format-version
+//! ```
+//!
+//! The file only supplies defaults. Callers start from [`ProjectConfig::options`]
+//! and overwrite whatever was passed explicitly, so a flag or tool argument
+//! always wins over the file. Unknown keys are an error, not silently ignored.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::error::TilthError;
+use crate::RunOptions;
+
+/// File name looked up at the scope root. Parent directories are not searched.
+pub const FILE_NAME: &str = ".tilth.toml";
+
+/// Defaults loaded from `.tilth.toml`.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectConfig {
+    /// Token budget used when the caller passes none.
+    pub budget: Option<u64>,
+    /// Run options with the file's settings applied over `RunOptions::default()`.
+    pub options: RunOptions,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct RawConfig {
+    budget: Option<u64>,
+    search: RawSearch,
+    outline: RawOutline,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct RawSearch {
+    exclude: Vec<String>,
+    exclude_tests: bool,
+    kinds: Option<String>,
+    limit: Option<usize>,
+    ignore_case_style: bool,
+    ignore_case: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct RawOutline {
+    style: Option<String>,
+    body_lines: usize,
+    max_name_len: Option<usize>,
+    byte_ranges: bool,
+    collapse_accessors: bool,
+    unused_imports: bool,
+    format_version: Option<u32>,
+}
+
+impl ProjectConfig {
+    /// Load `scope/.tilth.toml`. A missing file, or a scope that is not a
+    /// directory (such as an archive), gives the defaults.
+    pub fn load(scope: &Path) -> Result<Self, TilthError> {
+        if !scope.is_dir() {
+            return Ok(Self::default());
+        }
+        let path = scope.join(FILE_NAME);
+        let raw = match std::fs::read_to_string(&path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(TilthError::from_io(&path, e)),
+        };
+        Self::parse(&raw).map_err(|reason| TilthError::InvalidConfig { path, reason })
+    }
+
+    fn parse(raw: &str) -> Result<Self, String> {
+        let raw: RawConfig = toml::from_str(raw).map_err(|e| e.message().to_string())?;
+        let mut options = RunOptions::default();
+
+        let search = &mut options.search;
+        search.exclude = raw.search.exclude;
+        search.exclude_tests = raw.search.exclude_tests;
+        search.ignore_case_style = raw.search.ignore_case_style;
+        search.ignore_case = raw.search.ignore_case;
+        if let Some(kinds) = raw.search.kinds {
+            search.kinds = kinds.parse()?;
+        }
+        if let Some(limit) = raw.search.limit {
+            search.limit = limit.max(1);
+        }
+
+        if let Some(style) = raw.outline.style {
+            options.outline.style = style.parse()?;
+        }
+        options.outline.body_lines = raw.outline.body_lines;
+        options.outline.max_name_len = raw.outline.max_name_len;
+        options.outline.byte_ranges = raw.outline.byte_ranges;
+        options.outline.collapse_accessors = raw.outline.collapse_accessors;
+        options.outline.unused_imports = raw.outline.unused_imports;
+        if let Some(version) = raw.outline.format_version {
+            options.outline.format_version = version.to_string().parse()?;
+        }
+
+        Ok(Self {
+            budget: raw.budget,
+            options,
+        })
+    }
+
+    /// Where [`load`](Self::load) looks for the file.
+    #[must_use]
+    pub fn path(scope: &Path) -> PathBuf {
+        scope.join(FILE_NAME)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FormatVersion, OutlineStyle};
+
+    #[test]
+    fn exclusion_from_file_is_honored() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(tmp.path().join("generated")).unwrap();
+        std::fs::write(tmp.path().join("api.rs"), "pub fn handle_order() {}\n").unwrap();
+        std::fs::write(
+            tmp.path().join("generated/api.rs"),
+            "pub fn handle_order() {}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            ProjectConfig::path(tmp.path()),
+            "[search]\nexclude = [\"generated/**\"]\n",
+        )
+        .unwrap();
+
+        let cache = crate::cache::OutlineCache::new();
+        let search = |options: &RunOptions| {
+            crate::run_with_options(
+                "handle_order",
+                tmp.path(),
+                None,
+                None,
+                false,
+                0,
+                None,
+                options,
+                &cache,
+            )
+            .unwrap()
+        };
+        let config = ProjectConfig::load(tmp.path()).unwrap();
+        let out = search(&config.options);
+        assert!(out.contains("api.rs"), "{out}");
+        assert!(!out.contains("generated"), "{out}");
+        assert!(search(&RunOptions::default()).contains("generated/api.rs"));
+    }
+
+    #[test]
+    fn parses_every_section() {
+        let config = ProjectConfig::parse(
+            "budget = 900\n[search]\nexclude_tests = true\nkinds = \"type\"\nlimit = 0\n\
+             [outline]\nstyle = \"names\"\nbody_lines = 3\nformat_version = 1\n",
+        )
+        .unwrap();
+        assert_eq!(config.budget, Some(900));
+        assert!(config.options.search.exclude_tests);
+        assert_eq!(config.options.search.kinds, "type".parse().unwrap());
+        assert_eq!(config.options.search.limit, 1);
+        assert_eq!(config.options.outline.style, OutlineStyle::Names);
+        assert_eq!(config.options.outline.body_lines, 3);
+        assert_eq!(config.options.outline.format_version, FormatVersion::V1);
+        assert!(ProjectConfig::parse("[outline]\nformat_version = 9\n").is_err());
+    }
+
+    #[test]
+    fn missing_file_is_default_and_bad_file_is_an_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = ProjectConfig::load(tmp.path()).unwrap();
+        assert_eq!(config.budget, None);
+        assert_eq!(config.options.search, crate::SearchOptions::default());
+
+        // A file scope (an archive) has no config of its own.
+        let archive = tmp.path().join("pkg.tar.gz");
+        std::fs::write(&archive, "").unwrap();
+        assert_eq!(ProjectConfig::load(&archive).unwrap().budget, None);
+
+        std::fs::write(ProjectConfig::path(tmp.path()), "[search]\nexclud = []\n").unwrap();
+        let err = ProjectConfig::load(tmp.path()).unwrap_err();
+        assert!(matches!(err, TilthError::InvalidConfig { .. }), "{err}");
+        assert_eq!(err.exit_code(), 3);
+    }
+}
@@ -362,9 +362,15 @@ fn clone_entry_shallow(entry: &OutlineEntry) -> OutlineEntry {
         name: entry.name.clone(),
         start_line: entry.start_line,
         end_line: entry.end_line,
+        byte_range: entry.byte_range.clone(),
         signature: entry.signature.clone(),
         children: Vec::new(),
         doc: entry.doc.clone(),
+        deprecated: entry.deprecated,
+        visibility: entry.visibility.clone(),
+        modifiers: entry.modifiers.clone(),
+        type_params: entry.type_params.clone(),
+        decorators: entry.decorators.clone(),
     }
 }
 
@@ -510,24 +516,6 @@ fn jaccard_similarity(a: &str, b: &str) -> f32 {
 mod tests {
     use super::*;
 
-    fn make_entry(
-        kind: OutlineKind,
-        name: &str,
-        start: u32,
-        end: u32,
-        sig: Option<&str>,
-    ) -> OutlineEntry {
-        OutlineEntry {
-            kind,
-            name: name.to_string(),
-            start_line: start,
-            end_line: end,
-            signature: sig.map(|s| s.to_string()),
-            children: Vec::new(),
-            doc: None,
-        }
-    }
-
     fn make_sym(
         kind: OutlineKind,
         name: &str,
@@ -539,13 +527,8 @@ mod tests {
         let structural_hash = compute_structural_hash(source, name, Lang::Rust);
         DiffSymbol {
             entry: OutlineEntry {
-                kind,
-                name: name.to_string(),
-                start_line: 1,
-                end_line: 1,
-                signature: sig.map(|s| s.to_string()),
-                children: Vec::new(),
-                doc: None,
+                signature: sig.map(ToString::to_string),
+                ..OutlineEntry::new(kind, name, 1, 1)
             },
             identity: SymbolIdentity {
                 kind,
@@ -328,6 +328,38 @@ pub fn diff(
 // Helper functions
 // ---------------------------------------------------------------------------
 
+/// New-side lines a unified diff changes in `path`, for
+/// [`OutlineOptions::changed_lines`](crate::OutlineOptions::changed_lines).
+/// Added lines count as changed; a pure deletion marks the lines on either side
+/// of the gap. `path` matches a diff path when either ends with the other, so
+/// repo-relative diff paths match a file read by absolute or scoped path.
+/// `None` when the diff doesn't touch `path`.
+#[must_use]
+pub fn changed_lines(patch: &str, path: &Path) -> Option<crate::LineRanges> {
+    let file = parse::parse_unified_diff(patch)
+        .into_iter()
+        .find(|f| path.ends_with(&f.path) || f.path.ends_with(path))?;
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+    let mut push = |lo: u32, hi: u32| match ranges.last_mut() {
+        Some(last) if lo <= last.1 + 1 => last.1 = last.1.max(hi),
+        _ => ranges.push((lo, hi)),
+    };
+    for hunk in &file.hunks {
+        let mut line = hunk.new_start;
+        for diff_line in &hunk.lines {
+            match diff_line.kind {
+                DiffLineKind::Context => line += 1,
+                DiffLineKind::Added => {
+                    push(line, line);
+                    line += 1;
+                }
+                DiffLineKind::Removed => push(line.saturating_sub(1).max(1), line),
+            }
+        }
+    }
+    (!ranges.is_empty()).then_some(crate::LineRanges(ranges))
+}
+
 /// Human-readable label for a diff source.
 fn source_label(source: &DiffSource) -> String {
     match source {
@@ -566,7 +598,7 @@ mod tests {
         String::from_utf8_lossy(&output.stdout).into_owned()
     }
 
-    /// Run diff() from within the test repo directory, serialized via CWD_LOCK.
+    /// Run `diff()` from within the test repo directory, serialized via `CWD_LOCK`.
     fn run_diff_in(
         dir: &Path,
         source: &DiffSource,
@@ -1095,5 +1127,27 @@ diff --git a/src/main.rs b/src/main.rs
             DiffSource::Patch(_)
         ));
     }
+
+    #[test]
+    fn changed_lines_come_from_new_side_of_hunks() {
+        let patch = "\
+diff --git a/src/main.rs b/src/main.rs
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1,3 +1,4 @@
+ fn hello() {
+-    println!(\"hello\");
++    println!(\"hi\");
++    println!(\"there\");
+ }
+@@ -9,4 +10,3 @@ fn main() {
+     hello();
+-    goodbye();
+ }
+";
+        let ranges = changed_lines(patch, Path::new("/repo/src/main.rs")).unwrap();
+        assert_eq!(ranges.0, [(1, 3), (10, 11)]);
+        assert!(changed_lines(patch, Path::new("src/lib.rs")).is_none());
+    }
 }
 // test
@@ -71,7 +71,10 @@ pub fn apply_edits(path: &Path, edits: &[Edit]) -> Result<EditResult, TilthError
         },
     })?;
 
-    let lines: Vec<&str> = content.lines().collect();
+    // Hashes are computed on BOM-less text (as `read` shows it); the BOM is
+    // written back unchanged.
+    let bom = content.len() - crate::lang::strip_bom(&content).len();
+    let lines: Vec<&str> = content[bom..].lines().collect();
     let total = lines.len();
 
     // Phase 1: Verify all hashes
@@ -194,7 +197,8 @@ pub fn apply_edits(path: &Path, edits: &[Edit]) -> Result<EditResult, TilthError
         "\n"
     };
     let has_trailing_newline = content.ends_with('\n');
-    let mut output = owned.join(line_sep);
+    let mut output = content[..bom].to_string();
+    output.push_str(&owned.join(line_sep));
     if has_trailing_newline {
         output.push_str(line_sep);
     }
@@ -484,6 +488,30 @@ mod tests {
         let _ = std::fs::remove_file(&path);
     }
 
+    #[test]
+    fn bom_is_hidden_from_hashes_and_preserved() {
+        let content = "\u{feff}aaa\nbbb\n";
+        let path = write_temp("bom", content);
+        let h = format::line_hash(b"aaa");
+
+        let edits = vec![Edit {
+            start_line: 1,
+            start_hash: h,
+            end_line: 1,
+            end_hash: h,
+            content: "AAA".into(),
+        }];
+
+        let result = apply_edits(&path, &edits).unwrap();
+        assert!(matches!(result, EditResult::Applied { .. }));
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "\u{feff}AAA\nbbb\n"
+        );
+
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[test]
     fn empty_edits_no_diff() {
         let content = "aaa\nbbb\n";
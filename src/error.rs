@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Every error tilth can produce. Displayed as user-facing messages with suggestions.
 #[derive(Debug)]
@@ -10,6 +10,14 @@ pub enum TilthError {
     PermissionDenied {
         path: PathBuf,
     },
+    /// A directory was required (e.g. a search scope) but the path is a file.
+    NotADirectory {
+        path: PathBuf,
+    },
+    /// A file was required (e.g. a line range or deps target) but the path is a directory.
+    IsADirectory {
+        path: PathBuf,
+    },
     InvalidQuery {
         query: String,
         reason: String,
@@ -22,6 +30,29 @@ pub enum TilthError {
         path: PathBuf,
         reason: String,
     },
+    /// `tilth install` was given a host it has no config location for.
+    UnknownHost {
+        host: String,
+    },
+    /// Reading, creating or writing an MCP host config failed.
+    ConfigIo {
+        action: &'static str,
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// A config file (MCP host config, `.tilth.toml`) can't be parsed or used.
+    InvalidConfig {
+        path: PathBuf,
+        reason: String,
+    },
+    /// A required environment variable (e.g. `HOME`) is unset.
+    MissingEnv {
+        var: &'static str,
+    },
+    /// The requested location has no known path on this OS.
+    UnsupportedPlatform {
+        what: &'static str,
+    },
 }
 
 impl std::fmt::Display for TilthError {
@@ -37,6 +68,12 @@ impl std::fmt::Display for TilthError {
             Self::PermissionDenied { path } => {
                 write!(f, "{} [permission denied]", path.display())
             }
+            Self::NotADirectory { path } => {
+                write!(f, "{} [not a directory]", path.display())
+            }
+            Self::IsADirectory { path } => {
+                write!(f, "{} [is a directory, expected a file]", path.display())
+            }
             Self::InvalidQuery { query, reason } => {
                 write!(f, "invalid query \"{query}\": {reason}")
             }
@@ -46,20 +83,144 @@ impl std::fmt::Display for TilthError {
             Self::ParseError { path, reason } => {
                 write!(f, "parse error in {}: {reason}", path.display())
             }
+            Self::UnknownHost { host } => write!(
+                f,
+                "unknown host: {host}. Supported: {}",
+                crate::install::SUPPORTED_HOSTS.join(", ")
+            ),
+            Self::ConfigIo {
+                action,
+                path,
+                source,
+            } => write!(f, "failed to {action} {}: {source}", path.display()),
+            Self::InvalidConfig { path, reason } => {
+                write!(f, "invalid config {}: {reason}", path.display())
+            }
+            Self::MissingEnv { var } => write!(f, "{var} not set"),
+            Self::UnsupportedPlatform { what } => write!(f, "{what} unknown on this OS"),
         }
     }
 }
 
-impl std::error::Error for TilthError {}
+impl std::error::Error for TilthError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::IoError { source, .. } | Self::ConfigIo { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
 
 impl TilthError {
     /// Exit code matching the spec.
     #[must_use]
     pub fn exit_code(&self) -> i32 {
         match self {
-            Self::NotFound { .. } | Self::IoError { .. } => 2,
-            Self::InvalidQuery { .. } | Self::ParseError { .. } => 3,
+            Self::NotFound { .. }
+            | Self::NotADirectory { .. }
+            | Self::IsADirectory { .. }
+            | Self::IoError { .. }
+            | Self::ConfigIo { .. }
+            | Self::MissingEnv { .. }
+            | Self::UnsupportedPlatform { .. } => 2,
+            Self::InvalidQuery { .. }
+            | Self::ParseError { .. }
+            | Self::UnknownHost { .. }
+            | Self::InvalidConfig { .. } => 3,
             Self::PermissionDenied { .. } => 4,
         }
     }
+
+    /// Map an I/O failure on `path` to the specific variant, so callers report
+    /// "not found" / "permission denied" instead of a raw OS message.
+    pub(crate) fn from_io(path: &Path, source: std::io::Error) -> Self {
+        match source.kind() {
+            std::io::ErrorKind::NotFound => Self::NotFound {
+                path: path.to_path_buf(),
+                suggestion: None,
+            },
+            std::io::ErrorKind::PermissionDenied => Self::PermissionDenied {
+                path: path.to_path_buf(),
+            },
+            _ => Self::IoError {
+                path: path.to_path_buf(),
+                source,
+            },
+        }
+    }
+}
+
+/// Fail unless `path` exists and is a directory.
+pub(crate) fn require_dir(path: &Path) -> Result<(), TilthError> {
+    let meta = std::fs::metadata(path).map_err(|e| TilthError::from_io(path, e))?;
+    if meta.is_dir() {
+        Ok(())
+    } else {
+        Err(TilthError::NotADirectory {
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+/// Fail unless `path` exists and is not a directory.
+pub(crate) fn require_file(path: &Path) -> Result<(), TilthError> {
+    let meta = std::fs::metadata(path).map_err(|e| TilthError::from_io(path, e))?;
+    if meta.is_dir() {
+        Err(TilthError::IsADirectory {
+            path: path.to_path_buf(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn require_dir_distinguishes_failures() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("a.rs");
+        std::fs::write(&file, "").unwrap();
+        let missing = tmp.path().join("missing");
+
+        assert!(require_dir(tmp.path()).is_ok());
+        assert_eq!(
+            require_dir(&missing).unwrap_err().to_string(),
+            format!("not found: {}", missing.display())
+        );
+        assert_eq!(
+            require_dir(&file).unwrap_err().to_string(),
+            format!("{} [not a directory]", file.display())
+        );
+    }
+
+    #[test]
+    fn require_file_rejects_directories() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert_eq!(
+            require_file(tmp.path()).unwrap_err().to_string(),
+            format!("{} [is a directory, expected a file]", tmp.path().display())
+        );
+    }
+
+    #[test]
+    fn run_reports_missing_scope_as_not_found() {
+        let tmp = tempfile::tempdir().unwrap();
+        let missing = tmp.path().join("gone");
+        let cache = crate::cache::OutlineCache::new();
+        let err = crate::run("main", &missing, None, None, None, &cache).unwrap_err();
+        assert!(matches!(&err, TilthError::NotFound { path, .. } if *path == missing));
+    }
+
+    #[test]
+    fn from_io_maps_permission_denied() {
+        let err = TilthError::from_io(
+            Path::new("secret"),
+            std::io::Error::from(std::io::ErrorKind::PermissionDenied),
+        );
+        assert_eq!(err.to_string(), "secret [permission denied]");
+        assert_eq!(err.exit_code(), 4);
+    }
 }
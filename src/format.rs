@@ -1,7 +1,11 @@
 use std::fmt::Write;
 use std::path::Path;
 
-use crate::types::{estimate_tokens, ViewMode};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::search::glob::GlobResult;
+use crate::types::{estimate_tokens, Lang, OutlineEntry, SearchResult, ViewMode};
 
 /// Build the standard header line:
 /// `# path/to/file.ts (N lines, ~X.Xk tokens) [mode]`
@@ -43,7 +47,7 @@ pub fn search_header(
 }
 
 /// Human-readable file size. Integer math only — no floats.
-fn format_size(bytes: u64) -> String {
+pub(crate) fn format_size(bytes: u64) -> String {
     match bytes {
         b if b < 1024 => format!("{b}B"),
         b if b < 1024 * 1024 => format!("{}KB", b / 1024),
@@ -115,3 +119,96 @@ pub(crate) fn rel(path: &Path, scope: &Path) -> String {
         .display()
         .to_string()
 }
+
+/// A file's outline as JSON: its path, language (`null` when it has no code
+/// outline) and entry tree.
+pub(crate) fn outline_json(
+    path: &Path,
+    scope: &Path,
+    lang: Option<Lang>,
+    entries: &[OutlineEntry],
+) -> Value {
+    json!({
+        "path": rel(path, scope),
+        "lang": lang.map(Lang::json_name),
+        "entries": entries.iter().map(EntryJson::from).collect::<Vec<_>>(),
+    })
+}
+
+/// An [`OutlineEntry`] as the JSON outputs write it: field for field, kinds
+/// in `snake_case`, children nested — the same shape the optional `serde`
+/// derives produce, without needing them.
+#[derive(Serialize)]
+pub(crate) struct EntryJson<'a> {
+    kind: &'static str,
+    name: &'a str,
+    start_line: u32,
+    end_line: u32,
+    byte_range: &'a std::ops::Range<usize>,
+    signature: Option<&'a str>,
+    children: Vec<EntryJson<'a>>,
+    doc: Option<&'a str>,
+    deprecated: bool,
+    visibility: Option<&'a str>,
+    modifiers: &'a [String],
+    type_params: Option<&'a str>,
+    decorators: &'a [String],
+}
+
+impl<'a> From<&'a OutlineEntry> for EntryJson<'a> {
+    fn from(e: &'a OutlineEntry) -> Self {
+        Self {
+            kind: e.kind.json_name(),
+            name: &e.name,
+            start_line: e.start_line,
+            end_line: e.end_line,
+            byte_range: &e.byte_range,
+            signature: e.signature.as_deref(),
+            children: e.children.iter().map(EntryJson::from).collect(),
+            doc: e.doc.as_deref(),
+            deprecated: e.deprecated,
+            visibility: e.visibility.as_deref(),
+            modifiers: &e.modifiers,
+            type_params: e.type_params.as_deref(),
+            decorators: &e.decorators,
+        }
+    }
+}
+
+/// A ranked search result as JSON: counts, then one object per match with
+/// its path relative to `scope`.
+pub(crate) fn search_json(result: &SearchResult, scope: &Path) -> Value {
+    let matches: Vec<Value> = result
+        .matches
+        .iter()
+        .map(|m| {
+            json!({
+                "path": rel(&m.path, scope),
+                "line": m.line,
+                "text": m.text,
+                "kind": if m.is_definition { "definition" } else { "usage" },
+                "name": m.def_name,
+                "range": m.def_range.map(|(start, end)| [start, end]),
+                "implements": m.impl_target,
+            })
+        })
+        .collect();
+    json!({
+        "query": result.query,
+        "total_found": result.total_found,
+        "definitions": result.definitions,
+        "usages": result.usages,
+        "offset": result.offset,
+        "matches": matches,
+    })
+}
+
+/// Glob matches as JSON: the paths, relative to `scope`.
+pub(crate) fn glob_json(result: &GlobResult, scope: &Path) -> Value {
+    let files: Vec<String> = result.files.iter().map(|f| rel(&f.path, scope)).collect();
+    json!({
+        "pattern": result.pattern,
+        "total_found": result.total_found,
+        "files": files,
+    })
+}
@@ -187,6 +187,33 @@ impl SymbolIndex {
             .collect()
     }
 
+    /// Every definition within `scope`, as `(name, location)` pairs in no particular order.
+    #[must_use]
+    pub fn definitions(&self, scope: &Path) -> Vec<(Arc<str>, SymbolLocation)> {
+        self.symbols
+            .iter()
+            .flat_map(|entry| {
+                let name = entry.key().clone();
+                entry
+                    .value()
+                    .iter()
+                    .filter(|loc| loc.is_definition && loc.path.starts_with(scope))
+                    .map(|loc| (name.clone(), loc.clone()))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Indexed files within `scope`.
+    #[must_use]
+    pub fn files(&self, scope: &Path) -> Vec<PathBuf> {
+        self.indexed_files
+            .iter()
+            .filter(|entry| entry.key().starts_with(scope))
+            .map(|entry| entry.key().clone())
+            .collect()
+    }
+
     /// Index a single file, updating the symbol maps.
     ///
     /// Used for incremental updates when a file changes.
@@ -240,6 +267,7 @@ impl SymbolIndex {
 /// Only extracts definitions (function, struct, trait, class, etc.) --
 /// not usages. This keeps the index focused and compact.
 fn extract_symbols(path: &Path, content: &str) -> Vec<(Arc<str>, u32, bool)> {
+    let content = crate::lang::strip_bom(content);
     let FileType::Code(lang) = detect_file_type(path) else {
         return Vec::new();
     };
@@ -335,7 +363,7 @@ mod tests {
 
     #[test]
     fn test_extract_symbols_rust() {
-        let content = r#"
+        let content = r"
 pub struct Foo {
     bar: u32,
 }
@@ -353,7 +381,7 @@ trait MyTrait {
 impl MyTrait for Foo {
     fn do_thing(&self) {}
 }
-"#;
+";
         let dir = std::env::temp_dir().join("tilth_test_extract_symbols");
         let _ = fs::create_dir_all(&dir);
         let path = dir.join("test.rs");
@@ -437,7 +465,7 @@ impl MyTrait for Foo {
 
     #[test]
     fn test_extract_symbols_typescript() {
-        let content = r#"
+        let content = r"
 function greet(name: string): string {
     return `Hello, ${name}!`;
 }
@@ -452,7 +480,7 @@ class Greeter {
 interface Printable {
     print(): void;
 }
-"#;
+";
         let dir = std::env::temp_dir().join("tilth_test_extract_ts");
         let _ = fs::create_dir_all(&dir);
         let path = dir.join("test.ts");
@@ -479,14 +507,14 @@ interface Printable {
 
     #[test]
     fn test_extract_symbols_python() {
-        let content = r#"
+        let content = r"
 def hello():
     pass
 
 class MyClass:
     def method(self):
         pass
-"#;
+";
         let dir = std::env::temp_dir().join("tilth_test_extract_py");
         let _ = fs::create_dir_all(&dir);
         let path = dir.join("test.py");
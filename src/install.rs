@@ -1,8 +1,10 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use serde_json::{json, Value};
 
+use crate::error::TilthError;
+
 // Supported MCP hosts and their config locations.
 //
 // Paths verified from official docs (2025):
@@ -28,7 +30,7 @@ use serde_json::{json, Value};
 //   qwen-code:      ~/.qwen/settings.json                     (user scope)
 //   crush:          ~/.config/crush/crush.json                 (user scope)
 //   pi:             ~/.pi/agent/mcp.json                       (user scope)
-const SUPPORTED_HOSTS: &[&str] = &[
+pub(crate) const SUPPORTED_HOSTS: &[&str] = &[
     "claude-code",
     "cursor",
     "windsurf",
@@ -63,12 +65,11 @@ fn tilth_server_entry(edit: bool) -> Value {
 }
 
 /// Write MCP config for the given host, preserving existing config.
-pub fn run(host: &str, edit: bool) -> Result<(), String> {
+pub fn run(host: &str, edit: bool) -> Result<(), TilthError> {
     let host_info = resolve_host(host)?;
 
     if let Some(parent) = host_info.path.parent() {
-        fs::create_dir_all(parent)
-            .map_err(|e| format!("failed to create {}: {e}", parent.display()))?;
+        fs::create_dir_all(parent).map_err(|e| config_io("create", parent, e))?;
     }
 
     match host_info.format {
@@ -87,7 +88,7 @@ pub fn run(host: &str, edit: bool) -> Result<(), String> {
     Ok(())
 }
 
-fn write_json_config(host_info: &HostInfo, edit: bool) -> Result<(), String> {
+fn write_json_config(host_info: &HostInfo, edit: bool) -> Result<(), TilthError> {
     let servers_key = match host_info.format {
         ConfigFormat::Json { servers_key } => servers_key,
         ConfigFormat::Toml => unreachable!("write_json_config called for TOML host"),
@@ -95,24 +96,24 @@ fn write_json_config(host_info: &HostInfo, edit: bool) -> Result<(), String> {
 
     let mut config: Value = if host_info.path.exists() {
         let raw = fs::read_to_string(&host_info.path)
-            .map_err(|e| format!("failed to read {}: {e}", host_info.path.display()))?;
+            .map_err(|e| config_io("read", &host_info.path, e))?;
         serde_json::from_str(&raw)
-            .map_err(|e| format!("invalid JSON in {}: {e}", host_info.path.display()))?
+            .map_err(|e| invalid_config(&host_info.path, format!("invalid JSON: {e}")))?
     } else {
         json!({})
     };
 
-    upsert_json_server(&mut config, servers_key, tilth_server_entry(edit))?;
+    upsert_json_server(&mut config, servers_key, tilth_server_entry(edit))
+        .map_err(|reason| invalid_config(&host_info.path, reason))?;
 
     let out =
         serde_json::to_string_pretty(&config).expect("serde_json::Value is always serializable");
-    fs::write(&host_info.path, &out)
-        .map_err(|e| format!("failed to write {}: {e}", host_info.path.display()))?;
+    fs::write(&host_info.path, &out).map_err(|e| config_io("write", &host_info.path, e))?;
     Ok(())
 }
 
 /// Writes a `[mcp_servers.tilth]` section into a TOML config file.
-fn write_toml_config(host_info: &HostInfo, edit: bool) -> Result<(), String> {
+fn write_toml_config(host_info: &HostInfo, edit: bool) -> Result<(), TilthError> {
     let (command, args) = tilth_command_and_args(edit);
 
     // Escape backslashes for TOML basic strings (Windows paths like C:\Users\...).
@@ -127,8 +128,7 @@ fn write_toml_config(host_info: &HostInfo, edit: bool) -> Result<(), String> {
     );
 
     let existing = if host_info.path.exists() {
-        fs::read_to_string(&host_info.path)
-            .map_err(|e| format!("failed to read {}: {e}", host_info.path.display()))?
+        fs::read_to_string(&host_info.path).map_err(|e| config_io("read", &host_info.path, e))?
     } else {
         String::new()
     };
@@ -151,8 +151,7 @@ fn write_toml_config(host_info: &HostInfo, edit: bool) -> Result<(), String> {
         format!("{existing}{sep}\n{section}")
     };
 
-    fs::write(&host_info.path, &output)
-        .map_err(|e| format!("failed to write {}: {e}", host_info.path.display()))?;
+    fs::write(&host_info.path, &output).map_err(|e| config_io("write", &host_info.path, e))?;
     Ok(())
 }
 
@@ -196,7 +195,7 @@ struct HostInfo {
     note: Option<&'static str>,
 }
 
-fn resolve_host(host: &str) -> Result<HostInfo, String> {
+fn resolve_host(host: &str) -> Result<HostInfo, TilthError> {
     let home = home_dir()?;
 
     match host {
@@ -409,26 +408,40 @@ fn resolve_host(host: &str) -> Result<HostInfo, String> {
             note: Some("User scope — available in all projects."),
         }),
 
-        _ => Err(format!(
-            "unknown host: {host}. Supported: {}",
-            SUPPORTED_HOSTS.join(", ")
-        )),
+        _ => Err(TilthError::UnknownHost {
+            host: host.to_string(),
+        }),
     }
 }
 
-fn home_dir() -> Result<PathBuf, String> {
+fn home_dir() -> Result<PathBuf, TilthError> {
     #[cfg(target_os = "windows")]
     {
         std::env::var("USERPROFILE")
             .map(PathBuf::from)
-            .map_err(|_| "USERPROFILE not set".into())
+            .map_err(|_| TilthError::MissingEnv { var: "USERPROFILE" })
     }
 
     #[cfg(not(target_os = "windows"))]
     {
         std::env::var("HOME")
             .map(PathBuf::from)
-            .map_err(|_| "HOME not set".into())
+            .map_err(|_| TilthError::MissingEnv { var: "HOME" })
+    }
+}
+
+fn config_io(action: &'static str, path: &Path, source: std::io::Error) -> TilthError {
+    TilthError::ConfigIo {
+        action,
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+fn invalid_config(path: &Path, reason: String) -> TilthError {
+    TilthError::InvalidConfig {
+        path: path.to_path_buf(),
+        reason,
     }
 }
 
@@ -447,12 +460,12 @@ fn upsert_json_server(config: &mut Value, servers_key: &str, entry: Value) -> Re
 }
 
 /// Returns the VS Code globalStorage path for a given extension and settings filename.
-fn vscode_global_storage_path(extension_id: &str, filename: &str) -> Result<PathBuf, String> {
+fn vscode_global_storage_path(extension_id: &str, filename: &str) -> Result<PathBuf, TilthError> {
     let base = vscode_global_storage_base()?;
     Ok(base.join(extension_id).join("settings").join(filename))
 }
 
-fn vscode_global_storage_base() -> Result<PathBuf, String> {
+fn vscode_global_storage_base() -> Result<PathBuf, TilthError> {
     #[cfg(target_os = "macos")]
     {
         let home = home_dir()?;
@@ -461,7 +474,8 @@ fn vscode_global_storage_base() -> Result<PathBuf, String> {
 
     #[cfg(target_os = "windows")]
     {
-        let appdata = std::env::var("APPDATA").map_err(|_| "APPDATA not set")?;
+        let appdata =
+            std::env::var("APPDATA").map_err(|_| TilthError::MissingEnv { var: "APPDATA" })?;
         Ok(PathBuf::from(appdata).join("Code/User/globalStorage"))
     }
 
@@ -473,11 +487,13 @@ fn vscode_global_storage_base() -> Result<PathBuf, String> {
 
     #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
     {
-        Err("VS Code globalStorage path unknown on this OS".into())
+        Err(TilthError::UnsupportedPlatform {
+            what: "VS Code globalStorage path",
+        })
     }
 }
 
-fn claude_desktop_path() -> Result<PathBuf, String> {
+fn claude_desktop_path() -> Result<PathBuf, TilthError> {
     #[cfg(target_os = "macos")]
     {
         let home = home_dir()?;
@@ -486,13 +502,16 @@ fn claude_desktop_path() -> Result<PathBuf, String> {
 
     #[cfg(target_os = "windows")]
     {
-        let appdata = std::env::var("APPDATA").map_err(|_| "APPDATA not set")?;
+        let appdata =
+            std::env::var("APPDATA").map_err(|_| TilthError::MissingEnv { var: "APPDATA" })?;
         Ok(PathBuf::from(appdata).join("Claude/claude_desktop_config.json"))
     }
 
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
     {
-        Err("claude-desktop config path unknown on this OS".into())
+        Err(TilthError::UnsupportedPlatform {
+            what: "claude-desktop config path",
+        })
     }
 }
 
@@ -500,6 +519,55 @@ fn claude_desktop_path() -> Result<PathBuf, String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn unknown_host_is_a_typed_error() {
+        let Err(err) = resolve_host("emacs") else {
+            panic!("emacs should not resolve");
+        };
+        assert!(matches!(&err, TilthError::UnknownHost { host } if host == "emacs"));
+        assert!(err
+            .to_string()
+            .starts_with("unknown host: emacs. Supported: claude-code, cursor"));
+    }
+
+    #[test]
+    fn unusable_json_config_is_a_typed_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let host_info = HostInfo {
+            path: dir.path().join("mcp.json"),
+            format: ConfigFormat::Json {
+                servers_key: "mcpServers",
+            },
+            note: None,
+        };
+
+        std::fs::write(&host_info.path, "{ not json").unwrap();
+        let err = write_json_config(&host_info, false).unwrap_err();
+        assert!(matches!(&err, TilthError::InvalidConfig { path, .. } if *path == host_info.path));
+        assert_eq!(err.exit_code(), 3);
+
+        std::fs::write(&host_info.path, "[1, 2]").unwrap();
+        let err = write_json_config(&host_info, false).unwrap_err();
+        assert!(
+            matches!(&err, TilthError::InvalidConfig { reason, .. } if reason == "config root is not a JSON object")
+        );
+    }
+
+    #[test]
+    fn config_write_failure_keeps_io_source() {
+        let dir = tempfile::tempdir().unwrap();
+        // A directory where the config file should be makes the write fail.
+        let host_info = HostInfo {
+            path: dir.path().to_path_buf(),
+            format: ConfigFormat::Toml,
+            note: None,
+        };
+        let err = write_toml_config(&host_info, false).unwrap_err();
+        assert!(matches!(err, TilthError::ConfigIo { action: "read", .. }));
+        assert!(std::error::Error::source(&err).is_some());
+        assert!(err.to_string().starts_with("failed to read "));
+    }
+
     #[test]
     fn amp_resolve_host() {
         let info = resolve_host("amp").expect("amp should resolve");
@@ -610,7 +678,8 @@ mod tests {
     fn unknown_host_error_includes_droid() {
         let err = resolve_host("nope")
             .err()
-            .expect("unknown host should return an error");
+            .expect("unknown host should return an error")
+            .to_string();
         assert!(
             err.contains("droid"),
             "error should list droid in supported hosts, got: {err}"
@@ -651,7 +720,8 @@ mod tests {
     fn unknown_host_error_includes_antigravity() {
         let err = resolve_host("nope")
             .err()
-            .expect("unknown host should return an error");
+            .expect("unknown host should return an error")
+            .to_string();
         assert!(
             err.contains("antigravity"),
             "error should list antigravity in supported hosts, got: {err}"
@@ -869,7 +939,8 @@ mod tests {
     fn unknown_host_error_includes_amp() {
         let err = resolve_host("nope")
             .err()
-            .expect("unknown host should return an error");
+            .expect("unknown host should return an error")
+            .to_string();
         assert!(
             err.contains("amp"),
             "error should list amp in supported hosts, got: {err}"
@@ -43,3 +43,198 @@ pub fn is_generated_by_content(buf: &[u8]) -> bool {
         .iter()
         .any(|m| memchr::memmem::find(window, m).is_some())
 }
+
+/// Bytes inspected by the minified-file heuristic. Bundles are uniform enough
+/// that the head is representative.
+const MINIFIED_WINDOW: usize = 64 * 1024;
+
+/// Line-length thresholds for [`is_minified`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinifiedThreshold {
+    /// Average line length (bytes) above which a file counts as minified.
+    pub avg_line_len: usize,
+    /// Any single line longer than this marks the file as minified.
+    pub max_line_len: usize,
+}
+
+impl Default for MinifiedThreshold {
+    fn default() -> Self {
+        Self {
+            avg_line_len: 500,
+            max_line_len: 5000,
+        }
+    }
+}
+
+impl MinifiedThreshold {
+    /// Defaults, overridden by `TILTH_MINIFIED_AVG_LINE` / `TILTH_MINIFIED_MAX_LINE`.
+    /// Read once per process.
+    pub fn from_env() -> Self {
+        static CACHED: std::sync::OnceLock<MinifiedThreshold> = std::sync::OnceLock::new();
+        *CACHED.get_or_init(|| {
+            let var = |name: &str| {
+                std::env::var(name)
+                    .ok()
+                    .and_then(|v| v.parse::<usize>().ok())
+            };
+            let default = Self::default();
+            Self {
+                avg_line_len: var("TILTH_MINIFIED_AVG_LINE").unwrap_or(default.avg_line_len),
+                max_line_len: var("TILTH_MINIFIED_MAX_LINE").unwrap_or(default.max_line_len),
+            }
+        })
+    }
+}
+
+/// Long-line heuristic for minified bundles, over the first 64 KiB.
+/// Files shorter than `max_line_len` are never flagged — too small to matter.
+pub fn is_minified(buf: &[u8], threshold: MinifiedThreshold) -> bool {
+    if buf.len() < threshold.max_line_len {
+        return false;
+    }
+    let window = &buf[..buf.len().min(MINIFIED_WINDOW)];
+
+    let mut lines = 0;
+    let mut start = 0;
+    for end in memchr::memchr_iter(b'\n', window).chain(std::iter::once(window.len())) {
+        if end.saturating_sub(start) > threshold.max_line_len {
+            return true;
+        }
+        lines += 1;
+        start = end + 1;
+    }
+    window.len() / lines > threshold.avg_line_len
+}
+
+/// [`is_minified`] for a file on disk, reading only the inspected window.
+pub fn is_minified_file(path: &std::path::Path, threshold: MinifiedThreshold) -> bool {
+    use std::io::Read;
+
+    let Ok(file) = std::fs::File::open(path) else {
+        return false;
+    };
+    let mut buf = Vec::with_capacity(MINIFIED_WINDOW);
+    if file
+        .take(MINIFIED_WINDOW as u64)
+        .read_to_end(&mut buf)
+        .is_err()
+    {
+        return false;
+    }
+    is_minified(&buf, threshold)
+}
+
+/// Directory names whose contents are treated as tests, at any depth.
+pub const TEST_DIRS: &[&str] = &["test", "tests", "__tests__", "spec"];
+
+/// File-name suffixes that mark tests, per language convention:
+/// Go/Python/Ruby `_test`, `RSpec` `_spec`, JVM/.NET/Swift `Test`/`Tests`/`Spec`.
+pub const TEST_FILE_SUFFIXES: &[&str] = &[
+    "_test.go",
+    "_test.py",
+    "_test.rb",
+    "_spec.rb",
+    "_test.c",
+    "_test.cc",
+    "_test.cpp",
+    "Test.java",
+    "Tests.java",
+    "Test.kt",
+    "Tests.kt",
+    "Test.scala",
+    "Spec.scala",
+    "Test.cs",
+    "Tests.cs",
+    "Test.php",
+    "Tests.swift",
+];
+
+/// File-name infixes that mark JS/TS tests: `foo.test.ts`, `foo.spec.js`.
+pub const TEST_FILE_INFIXES: &[&str] = &[".test.", ".spec."];
+
+/// Whether `path` looks like a test file: inside a [`TEST_DIRS`] directory, or named
+/// with a [`TEST_FILE_SUFFIXES`] / [`TEST_FILE_INFIXES`] pattern, or `test_*.py` /
+/// `conftest.py`. Pass a path relative to the project root — a scope that itself sits
+/// under `tests/` would otherwise match everything.
+pub fn is_test_path(path: &std::path::Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    let in_test_dir = path
+        .parent()
+        .into_iter()
+        .flat_map(std::path::Path::components)
+        .any(|c| {
+            c.as_os_str()
+                .to_str()
+                .is_some_and(|c| TEST_DIRS.contains(&c))
+        });
+
+    in_test_dir
+        || TEST_FILE_SUFFIXES.iter().any(|s| name.ends_with(s))
+        || TEST_FILE_INFIXES.iter().any(|s| name.contains(s))
+        || (name.starts_with("test_")
+            && std::path::Path::new(name)
+                .extension()
+                .is_some_and(|e| e == "py"))
+        || name == "conftest.py"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minified_single_long_line() {
+        let bundle = "var a=1;".repeat(1000);
+        assert!(is_minified(bundle.as_bytes(), MinifiedThreshold::default()));
+    }
+
+    #[test]
+    fn minified_high_average_line_length() {
+        let line = format!("{}\n", "x".repeat(800));
+        let buf = line.repeat(20);
+        assert!(is_minified(buf.as_bytes(), MinifiedThreshold::default()));
+    }
+
+    #[test]
+    fn normal_source_not_minified() {
+        let src = "fn main() {\n    println!(\"hi\");\n}\n".repeat(500);
+        assert!(!is_minified(src.as_bytes(), MinifiedThreshold::default()));
+        // Short single-line files are never flagged.
+        assert!(!is_minified(b"{\"a\": 1}", MinifiedThreshold::default()));
+    }
+
+    #[test]
+    fn minified_threshold_is_configurable() {
+        let line = format!("{}\n", "x".repeat(100));
+        let buf = line.repeat(100);
+        assert!(!is_minified(buf.as_bytes(), MinifiedThreshold::default()));
+        let strict = MinifiedThreshold {
+            avg_line_len: 80,
+            max_line_len: 1000,
+        };
+        assert!(is_minified(buf.as_bytes(), strict));
+    }
+
+    #[test]
+    fn test_paths_by_convention() {
+        use std::path::Path;
+        for p in [
+            "server_test.go",
+            "pkg/test_client.py",
+            "conftest.py",
+            "src/app.test.ts",
+            "web/button.spec.jsx",
+            "spec/models/user_spec.rb",
+            "tests/integration.rs",
+            "src/__tests__/util.js",
+            "core/src/ParserTest.java",
+        ] {
+            assert!(is_test_path(Path::new(p)), "{p} should be a test");
+        }
+        for p in ["src/main.rs", "contest.py", "latest.go", "src/testing.rs"] {
+            assert!(!is_test_path(Path::new(p)), "{p} should not be a test");
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//! Dockerfile outline scanner. There is no Dockerfile tree-sitter grammar in
+//! the build; instructions are line-based, so a scanner that joins `\`
+//! continuations and skips heredoc bodies is enough. Each `FROM` opens a build
+//! stage (`Module`, named after its `AS` alias or image) with its `RUN`,
+//! `COPY`/`ADD` and `EXPOSE` instructions as children.
+
+use crate::types::{OutlineEntry, OutlineKind};
+
+/// One logical instruction: continuation lines joined, heredoc bodies included
+/// in its extent but not its text.
+struct Instruction {
+    keyword: String,
+    /// Whitespace-collapsed text of the instruction line(s), keyword included.
+    text: String,
+    start_line: u32,
+    end_line: u32,
+    start: usize,
+    end: usize,
+}
+
+/// Longest child name before it is cut with `...`.
+const MAX_TEXT: usize = 80;
+
+/// Outline entries for a Dockerfile: one `Module` per build stage.
+pub fn outline_entries(content: &str) -> Vec<OutlineEntry> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut stages: Vec<OutlineEntry> = Vec::new();
+    for ins in instructions(content) {
+        if ins.keyword == "FROM" {
+            let name = stage_name(&ins.text);
+            stages.push(OutlineEntry {
+                byte_range: ins.start..ins.end,
+                signature: Some(ins.text),
+                doc: doc_above(ins.start_line, &lines),
+                ..OutlineEntry::new(OutlineKind::Module, name, ins.start_line, ins.end_line)
+            });
+            continue;
+        }
+        // Instructions before the first `FROM` (global `ARG`s) belong to no stage.
+        let Some(stage) = stages.last_mut() else {
+            continue;
+        };
+        stage.end_line = ins.end_line;
+        stage.byte_range.end = ins.end;
+        if matches!(ins.keyword.as_str(), "RUN" | "COPY" | "ADD" | "EXPOSE") {
+            let name = if ins.text.len() > MAX_TEXT {
+                format!("{}...", crate::types::truncate_str(&ins.text, MAX_TEXT - 3))
+            } else {
+                ins.text
+            };
+            stage.children.push(OutlineEntry {
+                byte_range: ins.start..ins.end,
+                ..OutlineEntry::new(OutlineKind::Property, name, ins.start_line, ins.end_line)
+            });
+        }
+    }
+    stages
+}
+
+/// `FROM [--platform=...] image [AS name]` → the alias, else the image.
+fn stage_name(from: &str) -> String {
+    let words: Vec<&str> = from
+        .split_whitespace()
+        .skip(1)
+        .filter(|w| !w.starts_with("--"))
+        .collect();
+    match words.as_slice() {
+        [_, as_kw, alias, ..] if as_kw.eq_ignore_ascii_case("as") => (*alias).to_string(),
+        [image, ..] => (*image).to_string(),
+        [] => "FROM".to_string(),
+    }
+}
+
+/// Split `content` into instructions. Comment lines (also inside a
+/// continuation) are skipped; the escape character honors a leading
+/// `# escape=` parser directive.
+fn instructions(content: &str) -> Vec<Instruction> {
+    let escape = escape_char(content);
+    let mut out = Vec::new();
+    let mut current: Option<Instruction> = None;
+    // Heredoc delimiters still to be consumed, in order.
+    let mut heredocs: Vec<String> = Vec::new();
+    let mut offset = 0;
+
+    for (i, raw) in content.split_inclusive('\n').enumerate() {
+        let line_num = i as u32 + 1;
+        let start = offset;
+        offset += raw.len();
+        let line = raw.trim_end_matches(['\n', '\r']);
+        let end = start + line.len();
+
+        if let Some(delim) = heredocs.first() {
+            let ins = current.as_mut().expect("heredoc belongs to an instruction");
+            ins.end_line = line_num;
+            ins.end = end;
+            if line.trim_start_matches('\t') == delim {
+                heredocs.remove(0);
+                if heredocs.is_empty() {
+                    out.extend(current.take());
+                }
+            }
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let (body, continued) = match trimmed.strip_suffix(escape) {
+            Some(body) => (body.trim_end(), true),
+            None => (trimmed, false),
+        };
+
+        if let Some(ins) = current.as_mut() {
+            if !body.is_empty() {
+                ins.text.push(' ');
+                ins.text.push_str(body);
+            }
+            ins.end_line = line_num;
+            ins.end = end;
+        } else {
+            let keyword = body
+                .split_whitespace()
+                .next()
+                .unwrap_or("")
+                .to_ascii_uppercase();
+            current = Some(Instruction {
+                keyword,
+                text: body.to_string(),
+                start_line: line_num,
+                end_line: line_num,
+                start: start + (line.len() - line.trim_start().len()),
+                end,
+            });
+        }
+        if continued {
+            continue;
+        }
+
+        let ins = current.as_mut().expect("set above");
+        heredocs = heredoc_delimiters(&ins.text);
+        ins.text = ins.text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if heredocs.is_empty() {
+            out.extend(current.take());
+        }
+    }
+    out.extend(current);
+    out
+}
+
+/// The escape character: `\`, or `` ` `` from a `# escape=`` ` directive.
+fn escape_char(content: &str) -> char {
+    for line in content.lines() {
+        let Some(directive) = line.trim().strip_prefix('#') else {
+            break;
+        };
+        let Some((key, value)) = directive.split_once('=') else {
+            break;
+        };
+        if key.trim().eq_ignore_ascii_case("escape") && value.trim() == "`" {
+            return '`';
+        }
+    }
+    '\\'
+}
+
+/// Delimiters of the heredocs an instruction opens: `<<EOF`, `<<-EOF`, `<<"EOF"`.
+fn heredoc_delimiters(text: &str) -> Vec<String> {
+    text.split("<<")
+        .skip(1)
+        .filter_map(|rest| {
+            let rest = rest.strip_prefix('-').unwrap_or(rest);
+            let word: String = rest
+                .trim_start_matches(['"', '\''])
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+                .collect();
+            (!word.is_empty()).then_some(word)
+        })
+        .collect()
+}
+
+/// `#` comment lines directly above a `FROM`, markers stripped.
+fn doc_above(start_line: u32, lines: &[&str]) -> Option<String> {
+    let end = (start_line as usize).saturating_sub(1).min(lines.len());
+    let mut block: Vec<&str> = lines[..end]
+        .iter()
+        .rev()
+        .map(|l| l.trim())
+        .take_while(|l| l.starts_with('#'))
+        .map(|l| l.trim_start_matches('#').trim())
+        .collect();
+    block.reverse();
+    let first = block.iter().position(|l| !l.is_empty())?;
+    let last = block.iter().rposition(|l| !l.is_empty())?;
+    Some(block[first..=last].join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::read::outline::code::outline;
+    use crate::types::Lang;
+
+    #[test]
+    fn multi_stage_outline() {
+        let dockerfile = r#"# syntax=docker/dockerfile:1
+ARG NODE_VERSION=20
+
+# Install dependencies and build the bundle.
+FROM node:${NODE_VERSION} AS build
+WORKDIR /app
+COPY package.json package-lock.json ./
+RUN npm ci \
+    # no audit in CI
+    --no-audit
+COPY . .
+RUN <<EOF
+npm run build
+npm prune --omit=dev
+EOF
+
+FROM --platform=linux/amd64 nginx:1.27-alpine
+COPY --from=build /app/dist /usr/share/nginx/html
+EXPOSE 80
+CMD ["nginx", "-g", "daemon off;"]
+"#;
+        let expected = "\
+[5-15]       mod build
+           FROM node:${NODE_VERSION} AS build  // Install dependencies and build the bundle.
+  [7]          prop COPY package.json package-lock.json ./
+  [8-10]       prop RUN npm ci --no-audit
+  [11]         prop COPY . .
+  [12-15]      prop RUN <<EOF
+[17-20]      mod nginx:1.27-alpine
+           FROM --platform=linux/amd64 nginx:1.27-alpine
+  [18]         prop COPY --from=build /app/dist /usr/share/nginx/html
+  [19]         prop EXPOSE 80";
+        assert_eq!(outline(dockerfile, Lang::Dockerfile, usize::MAX), expected);
+    }
+}
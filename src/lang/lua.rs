@@ -0,0 +1,419 @@
+//! Lua outline scanner. There is no Lua tree-sitter grammar in the build; a
+//! tokenizer that tracks `function`/`do`/`if`/`repeat` blocks is enough to find
+//! top-level functions and `local` bindings. Module-table functions
+//! (`function M.foo()`, `M.bar = function()`) are listed under the table's
+//! `local M = {}` entry when there is one.
+
+use crate::types::{OutlineEntry, OutlineKind};
+
+struct Token<'a> {
+    text: &'a str,
+    start: usize,
+    end: usize,
+    line: u32,
+}
+
+/// A definition found at the top level, before nesting under its table.
+struct Def {
+    entry: OutlineEntry,
+    /// `M` for `M.foo` / `M:foo`, with the rest kept as the entry name.
+    table: Option<String>,
+}
+
+/// Outline entries for a Lua file: top-level functions and `local` bindings,
+/// with table functions as children of their table.
+pub fn outline_entries(content: &str) -> Vec<OutlineEntry> {
+    let lines: Vec<&str> = content.lines().collect();
+    let toks = tokens(content);
+    let mut defs: Vec<Def> = Vec::new();
+    let mut depth = 0usize;
+    // Definition whose extent closes when depth next returns to 0.
+    let mut open: Option<usize> = None;
+    // Tokens of the last definition's head are not re-scanned as definitions.
+    let mut skip_until = 0;
+
+    for (k, t) in toks.iter().enumerate() {
+        if depth == 0 && k >= skip_until {
+            if let Some((def, value)) = definition(content, &toks, k, &lines) {
+                let opens = matches!(toks[value].text, "function" | "{")
+                    || toks
+                        .get(value + 1)
+                        .is_some_and(|n| n.text == "(" && n.line == toks[value].line);
+                defs.push(def);
+                open = opens.then_some(defs.len() - 1);
+                skip_until = value + 1;
+            }
+        }
+        match t.text {
+            "function" | "if" | "do" | "repeat" | "(" | "{" | "[" => depth += 1,
+            "end" | "until" | ")" | "}" | "]" => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    if let Some(idx) = open.take() {
+                        let entry = &mut defs[idx].entry;
+                        entry.end_line = t.line;
+                        entry.byte_range.end = t.end;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut entries: Vec<OutlineEntry> = Vec::new();
+    for def in defs {
+        let parent = def.table.as_ref().and_then(|table| {
+            entries
+                .iter_mut()
+                .find(|e| e.name == *table && e.kind == OutlineKind::Variable)
+        });
+        let mut entry = def.entry;
+        if let Some(parent) = parent {
+            // Strip the separator so children read `foo`, keeping `:` for methods.
+            if let Some(name) = entry.name.strip_prefix('.') {
+                entry.name = name.to_string();
+            }
+            parent.children.push(entry);
+        } else {
+            if let Some(table) = def.table {
+                entry.name = format!("{table}{}", entry.name);
+            }
+            entries.push(entry);
+        }
+    }
+    entries
+}
+
+/// A definition starting at token `k`, and the index of its value token
+/// (the `function` keyword for function statements).
+fn definition(content: &str, toks: &[Token<'_>], k: usize, lines: &[&str]) -> Option<(Def, usize)> {
+    let text = |i: usize| toks.get(i).map_or("", |t| t.text);
+    let is_local = text(k) == "local";
+    let head = if is_local { k + 1 } else { k };
+
+    let (name_start, value) = if text(head) == "function" {
+        (head + 1, head)
+    } else {
+        // `name = value`, `M.name = value`, `local name = value`. Not a
+        // continuation of `a, b = ...` or a field access.
+        if !is_local && k > 0 && matches!(text(k - 1), "," | "." | ":") {
+            return None;
+        }
+        let end = name_end(toks, head);
+        if end == head || text(end) != "=" || (is_local && end != head + 1) {
+            return None;
+        }
+        (head, end + 1)
+    };
+    let name_end = name_end(toks, name_start);
+    if name_end == name_start {
+        return None;
+    }
+    let name: String = toks[name_start..name_end].iter().map(|t| t.text).collect();
+    let is_fn = text(value) == "function";
+
+    let (table, name) = match name.find(['.', ':']) {
+        Some(i) if !is_local => (Some(name[..i].to_string()), name[i..].to_string()),
+        _ => (None, name),
+    };
+    // Dotted non-function assignments (`M.VERSION = 1`) are fields, not outlined.
+    if table.is_some() && !is_fn {
+        return None;
+    }
+
+    let start = &toks[k];
+    let value_tok = toks.get(value)?;
+    let signature = is_fn
+        .then(|| {
+            let open = toks[value..].iter().position(|t| t.text == "(")? + value;
+            let close = matching(toks, open)?;
+            let sig = content[start.start..toks[close].end]
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+            Some(if sig.len() > 120 {
+                format!("{}...", crate::types::truncate_str(&sig, 117))
+            } else {
+                sig
+            })
+        })
+        .flatten();
+
+    let kind = if is_fn {
+        OutlineKind::Function
+    } else {
+        OutlineKind::Variable
+    };
+    let entry = OutlineEntry {
+        byte_range: start.start..value_tok.end,
+        signature,
+        doc: doc_above(start.line, lines),
+        ..OutlineEntry::new(kind, name, start.line, value_tok.line)
+    };
+    Some((Def { entry, table }, value))
+}
+
+/// End (exclusive) of a dotted name starting at `i`: `a`, `a.b`, `a.b:c`.
+fn name_end(toks: &[Token<'_>], mut i: usize) -> usize {
+    let is_ident = |t: &Token<'_>| {
+        t.text.starts_with(|c: char| c.is_alphabetic() || c == '_') && !is_keyword(t.text)
+    };
+    if !toks.get(i).is_some_and(is_ident) {
+        return i;
+    }
+    i += 1;
+    while matches!(toks.get(i).map(|t| t.text), Some("." | ":"))
+        && toks.get(i + 1).is_some_and(is_ident)
+    {
+        i += 2;
+    }
+    i
+}
+
+/// Index of the `)` closing the `(` at `open`.
+fn matching(toks: &[Token<'_>], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, t) in toks.iter().enumerate().skip(open) {
+        match t.text {
+            "(" => depth += 1,
+            ")" => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn is_keyword(s: &str) -> bool {
+    matches!(
+        s,
+        "and"
+            | "break"
+            | "do"
+            | "else"
+            | "elseif"
+            | "end"
+            | "false"
+            | "for"
+            | "function"
+            | "goto"
+            | "if"
+            | "in"
+            | "local"
+            | "nil"
+            | "not"
+            | "or"
+            | "repeat"
+            | "return"
+            | "then"
+            | "true"
+            | "until"
+            | "while"
+    )
+}
+
+/// Words and punctuation, with comments and string contents skipped. Strings
+/// become a single `""` token; `==`, `~=`, `<=`, `>=` stay whole so they are
+/// never mistaken for assignment.
+fn tokens(content: &str) -> Vec<Token<'_>> {
+    let b = content.as_bytes();
+    let mut out = Vec::new();
+    let mut line: u32 = 1;
+    let mut i = 0;
+    while i < b.len() {
+        let c = b[i];
+        let start = i;
+        let start_line = line;
+        match c {
+            b'\n' => {
+                line += 1;
+                i += 1;
+                continue;
+            }
+            c if c.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'-' if b.get(i + 1) == Some(&b'-') => {
+                i += 2;
+                if let Some(len) = long_bracket(&b[i..]) {
+                    i = skip_long(b, i + len, len, &mut line);
+                } else {
+                    while i < b.len() && b[i] != b'\n' {
+                        i += 1;
+                    }
+                }
+                continue;
+            }
+            b'"' | b'\'' => {
+                i += 1;
+                while i < b.len() && b[i] != c && b[i] != b'\n' {
+                    if b[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                i += 1;
+                out.push(Token {
+                    text: "\"\"",
+                    start,
+                    end: i.min(b.len()),
+                    line: start_line,
+                });
+                continue;
+            }
+            b'[' if long_bracket(&b[i..]).is_some() => {
+                let len = long_bracket(&b[i..]).unwrap_or(2);
+                i = skip_long(b, i + len, len, &mut line);
+                out.push(Token {
+                    text: "\"\"",
+                    start,
+                    end: i,
+                    line: start_line,
+                });
+                continue;
+            }
+            c if is_ident_byte(c) => {
+                while i < b.len() && is_ident_byte(b[i]) {
+                    i += 1;
+                }
+            }
+            b'=' | b'~' | b'<' | b'>' if b.get(i + 1) == Some(&b'=') => i += 2,
+            _ => i += 1,
+        }
+        out.push(Token {
+            text: &content[start..i],
+            start,
+            end: i,
+            line: start_line,
+        });
+    }
+    out
+}
+
+/// Identifier bytes; any non-ASCII byte counts, so a token never ends inside
+/// a multi-byte character.
+fn is_ident_byte(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || !c.is_ascii()
+}
+
+/// Length of a long-bracket opener (`[[`, `[==[`) at the start of `b`.
+fn long_bracket(b: &[u8]) -> Option<usize> {
+    if b.first() != Some(&b'[') {
+        return None;
+    }
+    let eqs = b[1..].iter().take_while(|&&c| c == b'=').count();
+    (b.get(1 + eqs) == Some(&b'[')).then_some(eqs + 2)
+}
+
+/// Skip to just past the long-bracket closer matching an opener of `len` bytes.
+fn skip_long(b: &[u8], mut i: usize, len: usize, line: &mut u32) -> usize {
+    let eqs = len - 2;
+    while i < b.len() {
+        if b[i] == b']'
+            && b[i + 1..].iter().take(eqs).all(|&c| c == b'=')
+            && b.get(i + 1 + eqs) == Some(&b']')
+        {
+            return i + len;
+        }
+        if b[i] == b'\n' {
+            *line += 1;
+        }
+        i += 1;
+    }
+    b.len()
+}
+
+/// `--` / `---` comment lines directly above a definition, markers stripped.
+fn doc_above(start_line: u32, lines: &[&str]) -> Option<String> {
+    let end = (start_line as usize).saturating_sub(1).min(lines.len());
+    let mut block: Vec<&str> = lines[..end]
+        .iter()
+        .rev()
+        .map(|l| l.trim())
+        .take_while(|l| l.starts_with("--") && !l.starts_with("--[["))
+        .map(|l| l.trim_start_matches('-').trim())
+        .collect();
+    block.reverse();
+    let first = block.iter().position(|l| !l.is_empty())?;
+    let last = block.iter().rposition(|l| !l.is_empty())?;
+    Some(block[first..=last].join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::read::outline::code::outline;
+    use crate::types::Lang;
+
+    #[test]
+    fn module_table_outline() {
+        let code = r#"local util = require("util")
+
+--- Buffer helpers.
+local M = {}
+
+M.VERSION = "1.0"
+
+--[[ Internal: strips
+     whitespace ]]
+local function trim(s)
+  return (s:gsub("^%s+", ""))
+end
+
+--- Open a scratch buffer.
+function M.open(name, opts)
+  if opts == nil then
+    opts = {}
+  end
+  for _, v in ipairs(opts) do print(v) end
+  return util.buf(trim(name))
+end
+
+function M:close()
+  self.closed = true
+end
+
+M.reset = function(buf)
+  local s = [[ end ]]
+  return s
+end
+
+function string.shout(s) return s:upper() end
+
+config = {
+  width = 80,
+}
+
+return M
+"#;
+        let expected = "\
+[1]          let util
+[4]          let M  // Buffer helpers.
+  [15-21]      fn open
+             function M.open(name, opts)  // Open a scratch buffer.
+  [23-25]      fn :close
+             function M:close()
+  [27-30]      fn reset
+             M.reset = function(buf)
+[10-12]      fn trim
+           local function trim(s)
+[32]         fn string.shout
+           function string.shout(s)
+[34-36]      let config";
+        assert_eq!(outline(code, Lang::Lua, usize::MAX), expected);
+    }
+
+    #[test]
+    fn non_ascii_names_outline() {
+        // Non-ASCII identifiers and text must not be split mid-character.
+        let code = "local café = 1\n\nlocal function naïve(s)\n  return s .. \"é\"\nend\n";
+        let expected = "[1]          let café
+[3-5]        fn naïve
+           local function naïve(s)";
+        assert_eq!(outline(code, Lang::Lua, usize::MAX), expected);
+    }
+}
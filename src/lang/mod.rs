@@ -1,14 +1,30 @@
 pub mod detection;
+pub mod dockerfile;
+pub mod lua;
+pub mod nix;
+pub mod ocaml;
 pub mod outline;
+pub mod perl;
+pub mod r;
+pub mod registry;
+pub mod toml;
 pub mod treesitter;
+pub mod vue;
+pub mod yaml;
+pub mod zig;
 
-use std::path::Path;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
 
-use crate::types::{FileType, Lang};
+use crate::types::{FileType, Lang, OutlineEntry};
 
 /// Detect file type by extension, then by name.
 pub fn detect_file_type(path: &Path) -> FileType {
-    match path.extension().and_then(|e| e.to_str()) {
+    let ext = path.extension().and_then(|e| e.to_str());
+    if let Some(lang) = ext.and_then(registry::lang_for_extension) {
+        return FileType::Code(lang);
+    }
+    match ext {
         Some("ts") => FileType::Code(Lang::TypeScript),
         Some("tsx") => FileType::Code(Lang::Tsx),
         Some("js" | "jsx") => FileType::Code(Lang::JavaScript),
@@ -24,9 +40,18 @@ pub fn detect_file_type(path: &Path) -> FileType {
         Some("swift") => FileType::Code(Lang::Swift),
         Some("kt" | "kts") => FileType::Code(Lang::Kotlin),
         Some("cs") => FileType::Code(Lang::CSharp),
+        Some("nix") => FileType::Code(Lang::Nix),
+        Some("R" | "r") => FileType::Code(Lang::R),
+        Some("lua") => FileType::Code(Lang::Lua),
+        Some("pl" | "pm") => FileType::Code(Lang::Perl),
+        Some("zig") => FileType::Code(Lang::Zig),
+        Some("ml" | "mli") => FileType::Code(Lang::OCaml),
+        Some("toml") => FileType::Code(Lang::Toml),
+        Some("yaml" | "yml") => FileType::Code(Lang::Yaml),
+        Some("vue") => FileType::Code(Lang::Vue),
 
         Some("md" | "mdx" | "rst") => FileType::Markdown,
-        Some("json" | "yaml" | "yml" | "toml" | "xml" | "ini") => FileType::StructuredData,
+        Some("json" | "xml" | "ini") => FileType::StructuredData,
         Some("csv" | "tsv") => FileType::Tabular,
         Some("log") => FileType::Log,
 
@@ -45,6 +70,78 @@ fn file_type_from_name(path: &Path) -> FileType {
     }
 }
 
+/// Outline entries for languages outlined by a line scanner rather than a
+/// tree-sitter grammar. `None` for every other language.
+pub(crate) fn scanner_entries(lang: Lang, content: &str) -> Option<Vec<OutlineEntry>> {
+    let entries = match lang {
+        Lang::Nix => nix::outline_entries(content),
+        Lang::R => r::outline_entries(content),
+        Lang::Lua => lua::outline_entries(content),
+        Lang::Dockerfile => dockerfile::outline_entries(content),
+        Lang::Perl => perl::outline_entries(content),
+        Lang::Zig => zig::outline_entries(content),
+        Lang::OCaml => ocaml::outline_entries(content),
+        Lang::Toml => toml::outline_entries(content),
+        Lang::Yaml => yaml::outline_entries(content),
+        Lang::Vue => vue::outline_entries(content),
+        _ => return None,
+    };
+    Some(entries)
+}
+
+/// Drop a leading UTF-8 byte-order mark. Windows editors often save one; left in,
+/// it glues onto the first token and the parser loses the first symbol.
+/// Line numbers are unaffected — the BOM never precedes a newline.
+pub(crate) fn strip_bom(content: &str) -> &str {
+    content.strip_prefix('\u{feff}').unwrap_or(content)
+}
+
+/// Expand a leading `~` (current user) or `~user` to a home directory, as a
+/// shell would. Paths without one — or whose home can't be found — are returned
+/// unchanged, so the caller's usual "not found" error still applies.
+pub(crate) fn expand_home(path: &Path) -> Cow<'_, Path> {
+    let home = std::env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" });
+    expand_home_with(path, home.as_deref().map(Path::new), user_home)
+}
+
+fn expand_home_with<'a>(
+    path: &'a Path,
+    home: Option<&Path>,
+    user_home: impl Fn(&str) -> Option<PathBuf>,
+) -> Cow<'a, Path> {
+    let Some(s) = path.to_str().filter(|s| s.starts_with('~')) else {
+        return Cow::Borrowed(path);
+    };
+    let (user, rest) = s[1..]
+        .split_once(['/', std::path::MAIN_SEPARATOR])
+        .unwrap_or((&s[1..], ""));
+    let base = if user.is_empty() {
+        home.map(Path::to_path_buf)
+    } else {
+        user_home(user)
+    };
+    match base {
+        Some(base) if rest.is_empty() => Cow::Owned(base),
+        Some(base) => Cow::Owned(base.join(rest)),
+        None => Cow::Borrowed(path),
+    }
+}
+
+/// Home directory of `user` from `/etc/passwd`.
+#[cfg(unix)]
+fn user_home(user: &str) -> Option<PathBuf> {
+    let passwd = std::fs::read_to_string("/etc/passwd").ok()?;
+    passwd.lines().find_map(|line| {
+        let fields: Vec<&str> = line.split(':').collect();
+        (fields.len() >= 6 && fields[0] == user).then(|| PathBuf::from(fields[5]))
+    })
+}
+
+#[cfg(not(unix))]
+fn user_home(_user: &str) -> Option<PathBuf> {
+    None
+}
+
 /// Find the nearest package root by looking for manifest files.
 pub(crate) fn package_root(path: &Path) -> Option<&Path> {
     const MANIFESTS: &[&str] = &[
@@ -67,3 +164,24 @@ pub(crate) fn package_root(path: &Path) -> Option<&Path> {
         dir = dir.parent()?;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_home_resolves_tilde() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(tmp.path().join("project")).unwrap();
+        let users = |u: &str| (u == "bob").then(|| PathBuf::from("/srv/bob"));
+        let expand = |p: &str| expand_home_with(Path::new(p), Some(tmp.path()), users).into_owned();
+
+        assert_eq!(expand("~/project"), tmp.path().join("project"));
+        assert!(expand("~/project").is_dir());
+        assert_eq!(expand("~"), tmp.path());
+        assert_eq!(expand("~bob/src"), Path::new("/srv/bob/src"));
+        // Unknown users and paths without a leading tilde are left alone.
+        assert_eq!(expand("~nobody/src"), Path::new("~nobody/src"));
+        assert_eq!(expand("src/~/x"), Path::new("src/~/x"));
+    }
+}
@@ -0,0 +1,387 @@
+//! Nix outline scanner. There is no Nix tree-sitter grammar in the build, and
+//! the interesting structure is shallow: bindings of the outermost attrsets and
+//! `let` blocks, plus one level of bindings nested in their values
+//! (`outputs = { self, ... }: { packages = ...; }`).
+
+use crate::types::{OutlineEntry, OutlineKind};
+
+enum Frame {
+    /// `{ ... }` — an attrset, or a lambda's argument set.
+    Attrs,
+    /// `let ... in`.
+    Let,
+    /// `[ ... ]` / `( ... )` — no bindings inside.
+    Group,
+    /// A binding's value, closed by `;`. `None` when nested too deep to outline.
+    Value(Option<usize>),
+    /// `with e;` / `assert e;` / `inherit ...;` — their `;` closes only themselves.
+    Stmt,
+}
+
+/// Outline entries for a Nix file. Attrset bindings are `Property`, `let`
+/// bindings `Variable`, and bindings whose value is a lambda `Function`.
+pub fn outline_entries(content: &str) -> Vec<OutlineEntry> {
+    let b = content.as_bytes();
+    let lines: Vec<&str> = content.lines().collect();
+    let mut stack: Vec<Frame> = Vec::new();
+    // Flat list of (entry, parent index); assembled into a tree at the end.
+    let mut flat: Vec<(OutlineEntry, Option<usize>)> = Vec::new();
+    // Start of the attrpath being read in binding position.
+    let mut pending: Option<(usize, u32)> = None;
+    let mut line: u32 = 1;
+    let mut i = 0;
+
+    while i < b.len() {
+        let binding_pos = matches!(stack.last(), Some(Frame::Attrs | Frame::Let));
+        match b[i] {
+            b'\n' => line += 1,
+            b'#' => {
+                while i < b.len() && b[i] != b'\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            b'/' if b.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i < b.len() && !(b[i] == b'*' && b.get(i + 1) == Some(&b'/')) {
+                    if b[i] == b'\n' {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+                i += 2;
+                continue;
+            }
+            b'"' => {
+                if binding_pos && pending.is_none() {
+                    pending = Some((i, line));
+                }
+                i += 1;
+                while i < b.len() && b[i] != b'"' {
+                    match b[i] {
+                        b'\\' => i += 1,
+                        b'\n' => line += 1,
+                        _ => {}
+                    }
+                    i += 1;
+                }
+            }
+            b'\'' if b.get(i + 1) == Some(&b'\'') => {
+                i += 2;
+                while i < b.len() {
+                    if b[i] == b'\'' && b.get(i + 1) == Some(&b'\'') {
+                        // `''$`, `'''` and `''\` are escapes, not the terminator.
+                        if matches!(b.get(i + 2), Some(b'$' | b'\'' | b'\\')) {
+                            i += 3;
+                            continue;
+                        }
+                        i += 1;
+                        break;
+                    }
+                    if b[i] == b'\n' {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+            }
+            // `${...}` in an attrpath (`packages.${system}.default`) is part of the name.
+            b'$' if binding_pos && b.get(i + 1) == Some(&b'{') => {
+                if let Some(close) = matching_brace(&content[i + 1..]) {
+                    pending.get_or_insert((i, line));
+                    i += close + 2;
+                    continue;
+                }
+            }
+            b'{' => {
+                stack.push(Frame::Attrs);
+                pending = None;
+            }
+            b'}' => {
+                // Close any value left open by a missing `;`, then the attrset.
+                while let Some(frame) = stack.pop() {
+                    match frame {
+                        Frame::Value(Some(idx)) => {
+                            flat[idx].0.end_line = line;
+                            flat[idx].0.byte_range.end = i;
+                        }
+                        Frame::Attrs => break,
+                        _ => {}
+                    }
+                }
+                pending = None;
+            }
+            b'[' | b'(' => stack.push(Frame::Group),
+            b']' | b')' => {
+                if matches!(stack.last(), Some(Frame::Group)) {
+                    stack.pop();
+                }
+            }
+            b';' => {
+                match stack.last() {
+                    Some(Frame::Value(idx)) => {
+                        if let Some(idx) = *idx {
+                            flat[idx].0.end_line = line;
+                            flat[idx].0.byte_range.end = i + 1;
+                        }
+                        stack.pop();
+                    }
+                    Some(Frame::Stmt) => {
+                        stack.pop();
+                    }
+                    _ => {}
+                }
+                pending = None;
+            }
+            b'=' if b.get(i + 1) == Some(&b'=') => i += 1,
+            b'=' if i > 0 && matches!(b[i - 1], b'!' | b'<' | b'>') => {}
+            b'=' => {
+                if let (true, Some((start, start_line))) = (binding_pos, pending.take()) {
+                    let level = stack
+                        .iter()
+                        .filter(|f| matches!(f, Frame::Value(_)))
+                        .count();
+                    let parent = stack.iter().rev().find_map(|f| match f {
+                        Frame::Value(idx) => *idx,
+                        _ => None,
+                    });
+                    let idx = (level <= 1).then(|| {
+                        let in_let = matches!(stack.last(), Some(Frame::Let));
+                        let name = content[start..i].split_whitespace().collect::<String>();
+                        let entry = OutlineEntry {
+                            byte_range: start..i + 1,
+                            ..binding_entry(name, &content[i + 1..], start_line, in_let, &lines)
+                        };
+                        flat.push((entry, parent));
+                        flat.len() - 1
+                    });
+                    stack.push(Frame::Value(idx));
+                }
+            }
+            b',' | b'?' | b'@' | b':' => pending = None,
+            c if c.is_ascii_alphabetic() || c == b'_' => {
+                let start = i;
+                while i < b.len() && is_ident_byte(b[i]) {
+                    i += 1;
+                }
+                match &content[start..i] {
+                    "in" if matches!(stack.last(), Some(Frame::Let)) => {
+                        stack.pop();
+                        pending = None;
+                    }
+                    "inherit" if binding_pos => stack.push(Frame::Stmt),
+                    "let" if !binding_pos => stack.push(Frame::Let),
+                    "with" | "assert" if !binding_pos => stack.push(Frame::Stmt),
+                    _ if binding_pos && pending.is_none() => pending = Some((start, line)),
+                    _ => {}
+                }
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    let mut entries: Vec<Option<OutlineEntry>> = Vec::with_capacity(flat.len());
+    let mut parents = Vec::with_capacity(flat.len());
+    for (entry, parent) in flat {
+        entries.push(Some(entry));
+        parents.push(parent);
+    }
+    // Children always come after their parent — attach back to front.
+    for idx in (0..entries.len()).rev() {
+        if let Some(parent) = parents[idx] {
+            if let Some(child) = entries[idx].take() {
+                if let Some(p) = entries[parent].as_mut() {
+                    p.children.insert(0, child);
+                }
+            }
+        }
+    }
+    entries.into_iter().flatten().collect()
+}
+
+fn is_ident_byte(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, b'_' | b'-' | b'\'')
+}
+
+fn binding_entry(
+    name: String,
+    value: &str,
+    start_line: u32,
+    in_let: bool,
+    lines: &[&str],
+) -> OutlineEntry {
+    let (kind, signature) = match lambda_heads(value) {
+        Some(heads) => {
+            let sig = format!("{name} = {heads}");
+            let sig = if sig.len() > 120 {
+                format!("{}...", crate::types::truncate_str(&sig, 117))
+            } else {
+                sig
+            };
+            (OutlineKind::Function, Some(sig))
+        }
+        None if in_let => (OutlineKind::Variable, None),
+        None => (OutlineKind::Property, None),
+    };
+    OutlineEntry {
+        signature,
+        doc: doc_above(start_line, lines),
+        ..OutlineEntry::new(kind, name, start_line, start_line)
+    }
+}
+
+/// The lambda parameters a value opens with: `x: y: ...` → `x: y:`,
+/// `{ pkgs, ... }: ...` → `{ pkgs, ... }:`. `None` when the value isn't a lambda.
+fn lambda_heads(value: &str) -> Option<String> {
+    let mut heads = Vec::new();
+    let mut rest = value;
+    loop {
+        let s = rest.trim_start();
+        let (head, after) = if s.starts_with('{') {
+            let close = matching_brace(s)?;
+            let after = s[close + 1..].trim_start();
+            // `{ ... } @ args:`
+            let after = match after.strip_prefix('@') {
+                Some(a) => a
+                    .trim_start()
+                    .trim_start_matches(|c: char| c.is_ascii() && is_ident_byte(c as u8)),
+                None => after,
+            };
+            (&s[..s.len() - after.len()], after)
+        } else {
+            let end = s.bytes().position(|c| !is_ident_byte(c)).unwrap_or(s.len());
+            if end == 0 || !s.as_bytes()[0].is_ascii_alphabetic() && s.as_bytes()[0] != b'_' {
+                break;
+            }
+            let after = s[end..].trim_start();
+            // `args @ { ... }:`
+            if let Some(set) = after.strip_prefix('@') {
+                let set = set.trim_start();
+                if !set.starts_with('{') {
+                    break;
+                }
+                let close = matching_brace(set)?;
+                let after = &set[close + 1..];
+                (&s[..s.len() - after.len()], after.trim_start())
+            } else {
+                (&s[..end], after)
+            }
+        };
+        // A lambda's `:` is followed by whitespace; `github:owner/repo` is not a lambda.
+        let Some(body) = after.strip_prefix(':') else {
+            break;
+        };
+        if !body.starts_with(char::is_whitespace) {
+            break;
+        }
+        heads.push(format!(
+            "{}:",
+            head.split_whitespace().collect::<Vec<_>>().join(" ")
+        ));
+        rest = body;
+    }
+    (!heads.is_empty()).then(|| heads.join(" "))
+}
+
+/// Byte offset of the `}` closing the `{` that starts `s`.
+fn matching_brace(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in s.bytes().enumerate() {
+        match c {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// `#` comment lines directly above a binding, markers stripped.
+fn doc_above(start_line: u32, lines: &[&str]) -> Option<String> {
+    let end = (start_line as usize).saturating_sub(1).min(lines.len());
+    let block: Vec<&str> = lines[..end]
+        .iter()
+        .rev()
+        .map(|l| l.trim())
+        .take_while(|l| l.starts_with('#'))
+        .map(|l| l.trim_start_matches('#').trim())
+        .collect();
+    let text: Vec<&str> = block
+        .into_iter()
+        .rev()
+        .skip_while(|l| l.is_empty())
+        .collect();
+    let last = text.iter().rposition(|l| !l.is_empty())?;
+    Some(text[..=last].join("\n"))
+}
+#[cfg(test)]
+mod tests {
+    use crate::read::outline::code::outline;
+    use crate::types::Lang;
+
+    #[test]
+    fn module_outline() {
+        let code = r#"{ config, lib, pkgs, ... }:
+let
+  cfg = config.services.greeter;
+  # Render the greeting script.
+  mkScript = name: greeting: pkgs.writeShellScriptBin name ''
+    echo "${greeting}" ''${USER}
+  '';
+in
+{
+  options.services.greeter = {
+    enable = lib.mkEnableOption "greeter";
+    message = lib.mkOption { type = lib.types.str; };
+  };
+
+  config = lib.mkIf cfg.enable {
+    environment.systemPackages = with pkgs; [ (mkScript "greet" cfg.message) ];
+    systemd.services.${cfg.name} = { wantedBy = [ "multi-user.target" ]; };
+  };
+  url = github:NixOS/nixpkgs;
+}
+"#;
+        let expected = "\
+[3]          let cfg
+[5-7]        fn mkScript
+           mkScript = name: greeting:  // Render the greeting script.
+[10-13]      prop options.services.greeter
+  [11]         prop enable
+  [12]         prop message
+[15-18]      prop config
+  [16]         prop environment.systemPackages
+  [17]         prop systemd.services.${cfg.name}
+[19]         prop url";
+        assert_eq!(outline(code, Lang::Nix, usize::MAX), expected);
+
+        let entries = super::outline_entries(code);
+        let text = |e: &crate::types::OutlineEntry| &code[e.byte_range.clone()];
+        assert_eq!(text(&entries[0]), "cfg = config.services.greeter;");
+        assert_eq!(
+            text(&entries[2].children[0]),
+            r#"enable = lib.mkEnableOption "greeter";"#
+        );
+        assert_eq!(text(&entries[4]), "url = github:NixOS/nixpkgs;");
+    }
+
+    #[test]
+    fn lambda_heads() {
+        assert_eq!(
+            super::lambda_heads(" { self, nixpkgs }:\n  {}").as_deref(),
+            Some("{ self, nixpkgs }:")
+        );
+        assert_eq!(
+            super::lambda_heads(" args @ { pkgs, ... }: x: y").as_deref(),
+            Some("args @ { pkgs, ... }: x:")
+        );
+        assert_eq!(super::lambda_heads(" github:NixOS/nixpkgs;"), None);
+        assert_eq!(super::lambda_heads(" { a = 1; }.a;"), None);
+    }
+}
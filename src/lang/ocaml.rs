@@ -0,0 +1,642 @@
+//! OCaml outline scanner. There is no OCaml tree-sitter grammar in the build;
+//! a tokenizer that skips comments and strings is enough, because structure
+//! items start with a keyword (`let`, `type`, `module`, `open`, ...) at the
+//! item column, while `let ... in` inside a body sits further right. Modules
+//! and module types are outlined with their items as children.
+
+use crate::types::{OutlineEntry, OutlineKind};
+
+struct Token<'a> {
+    text: &'a str,
+    start: usize,
+    end: usize,
+    line: u32,
+    col: usize,
+    /// First token on its line.
+    first: bool,
+}
+
+/// Longest signature before it is cut with `...`.
+const MAX_SIGNATURE: usize = 120;
+
+/// Keywords that start a structure or signature item.
+const ITEM_KEYWORDS: &[&str] = &[
+    "let",
+    "and",
+    "type",
+    "module",
+    "open",
+    "include",
+    "exception",
+    "external",
+    "val",
+    "class",
+    ";;",
+];
+
+/// Outline entries for an OCaml implementation (`.ml`) or interface (`.mli`).
+pub fn outline_entries(content: &str) -> Vec<OutlineEntry> {
+    let lines: Vec<&str> = content.lines().collect();
+    let toks = tokens(content);
+    let scan = Scanner {
+        content,
+        toks: &toks,
+        lines: &lines,
+    };
+    scan.items(0, toks.len())
+}
+
+struct Scanner<'a> {
+    content: &'a str,
+    toks: &'a [Token<'a>],
+    lines: &'a [&'a str],
+}
+
+impl Scanner<'_> {
+    fn text(&self, i: usize) -> &str {
+        self.toks.get(i).map_or("", |t| t.text)
+    }
+
+    /// Items between tokens `i` and `end` (exclusive). `and` continues the
+    /// previous `let` or `type` group.
+    fn items(&self, mut i: usize, end: usize) -> Vec<OutlineEntry> {
+        let mut out = Vec::new();
+        let mut group = "let";
+        while i < end {
+            if !ITEM_KEYWORDS.contains(&self.text(i)) {
+                i += 1;
+                continue;
+            }
+            let next = self.item_end(i, end);
+            let keyword = match self.text(i) {
+                "and" => group,
+                kw => kw,
+            };
+            let entry = match keyword {
+                "let" => self.binding(i, next),
+                "type" => self.type_def(i, next),
+                "module" => self.module(i, next),
+                "open" => {
+                    Some(self.entry(OutlineKind::Import, self.signature(i, next), i, next, None))
+                }
+                "external" | "val" => self.value(i, next),
+                _ => None,
+            };
+            if matches!(keyword, "let" | "type") {
+                group = keyword;
+            }
+            out.extend(entry);
+            i = next;
+        }
+        out
+    }
+
+    /// Start of the next item after the one at `i`: an item keyword that opens
+    /// its line no further right than this one, outside any bracket or
+    /// `struct`/`sig`/`begin`/`object` block.
+    fn item_end(&self, i: usize, end: usize) -> usize {
+        let col = self.toks[i].col;
+        let mut depth = 0usize;
+        for k in i + 1..end {
+            let t = &self.toks[k];
+            match t.text {
+                "(" | "[" | "{" | "struct" | "sig" | "begin" | "object" => depth += 1,
+                ")" | "]" | "}" | "end" => depth = depth.saturating_sub(1),
+                ";;" if depth == 0 => return k,
+                text if depth == 0 && t.first && t.col <= col && ITEM_KEYWORDS.contains(&text) => {
+                    return k;
+                }
+                _ => {}
+            }
+        }
+        end
+    }
+
+    /// `let [rec] name params = ...`, `let%test "name" = ...`.
+    fn binding(&self, i: usize, end: usize) -> Option<OutlineEntry> {
+        let mut k = i + 1;
+        if self.text(k) == "%" {
+            let ext = self.text(k + 1);
+            if ext.ends_with("test") || ext.ends_with("test_unit") {
+                let name = self.text(k + 2);
+                let name = if name.starts_with('"') {
+                    name.trim_matches('"').to_string()
+                } else {
+                    ext.to_string()
+                };
+                return Some(self.entry(OutlineKind::TestCase, name, i, end, None));
+            }
+            k += 2;
+        }
+        while matches!(self.text(k), "rec" | "nonrec") {
+            k += 1;
+        }
+        let (name, after) = self.binding_name(k)?;
+        let eq = self.top_level_eq(after, end)?;
+        let annotation = self.text(after) == ":";
+        let has_params = after < eq && !annotation;
+        let arrow_type = annotation && (after..eq).any(|j| self.text(j) == "->");
+        let fun_value = matches!(self.text(eq + 1), "fun" | "function");
+        if has_params || arrow_type || fun_value {
+            let signature = self.signature(i, eq);
+            Some(self.entry(OutlineKind::Function, name, i, end, Some(signature)))
+        } else {
+            Some(self.entry(OutlineKind::ImmutableVariable, name, i, end, None))
+        }
+    }
+
+    /// A bound name at `k` — `name` or an operator `( +! )` — and the index
+    /// after it. `()` and `_` bind nothing.
+    fn binding_name(&self, k: usize) -> Option<(String, usize)> {
+        let text = self.text(k);
+        if text == "(" && self.text(k + 2) == ")" && is_operator(self.text(k + 1)) {
+            return Some((format!("({})", self.text(k + 1)), k + 3));
+        }
+        (is_lower_ident(text) && text != "_").then(|| (text.to_string(), k + 1))
+    }
+
+    /// `type [nonrec] params name = ...`: the name is the last word before
+    /// `=`, `+=`, `:=` or the end of the item.
+    fn type_def(&self, i: usize, end: usize) -> Option<OutlineEntry> {
+        let mut depth = 0usize;
+        let mut name = None;
+        for k in i + 1..end {
+            match self.text(k) {
+                "(" => depth += 1,
+                ")" => depth = depth.saturating_sub(1),
+                "=" | "+=" | ":=" | "constraint" if depth == 0 => break,
+                "nonrec" => {}
+                text if depth == 0 && is_lower_ident(text) => name = Some(text),
+                _ => {}
+            }
+        }
+        let signature = self.first_line(i, end);
+        Some(self.entry(
+            OutlineKind::TypeAlias,
+            name?.to_string(),
+            i,
+            end,
+            Some(signature),
+        ))
+    }
+
+    /// `module Name ... = struct ... end`, `module type S = sig ... end`, or
+    /// an alias / signature-only declaration without children.
+    fn module(&self, i: usize, end: usize) -> Option<OutlineEntry> {
+        let mut k = i + 1;
+        let is_type = self.text(k) == "type";
+        if is_type || self.text(k) == "rec" {
+            k += 1;
+        }
+        let name = self.text(k);
+        if !name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            return None;
+        }
+        let kind = if is_type {
+            OutlineKind::Interface
+        } else {
+            OutlineKind::Module
+        };
+        // The block after `=` is the body; a `: sig ... end` before it is
+        // only the constraint, used when there is no body (`.mli` files).
+        let mut depth = 0usize;
+        let mut blocks = Vec::new();
+        for j in k + 1..end {
+            match self.text(j) {
+                "struct" | "sig" | "begin" | "object" => {
+                    if depth == 0 {
+                        blocks.push(j);
+                    }
+                    depth += 1;
+                }
+                "end" => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        let body = blocks
+            .iter()
+            .find(|&&j| self.text(j - 1) == "=")
+            .or(blocks.first())
+            .copied()
+            .filter(|&j| matches!(self.text(j), "struct" | "sig"));
+        let Some(open) = body else {
+            let signature = self.signature(i, end);
+            return Some(self.entry(kind, name.to_string(), i, end, Some(signature)));
+        };
+        let close = self.block_end(open, end);
+        let sig_end = if self.text(open - 1) == "=" {
+            open - 1
+        } else {
+            open
+        };
+        let signature = self.signature(i, sig_end);
+        let mut entry = self.entry(kind, name.to_string(), i, end, Some(signature));
+        entry.children = self.items(open + 1, close);
+        Some(entry)
+    }
+
+    /// `external name : type = "stub"` and `val name : type` in signatures.
+    fn value(&self, i: usize, end: usize) -> Option<OutlineEntry> {
+        let (name, _) = self.binding_name(i + 1)?;
+        let is_fn = self.text(i) == "external" || (i..end).any(|k| self.text(k) == "->");
+        let kind = if is_fn {
+            OutlineKind::Function
+        } else {
+            OutlineKind::ImmutableVariable
+        };
+        let sig_end = if self.text(i) == "external" {
+            (i..end).rev().find(|&k| self.text(k) == "=").unwrap_or(end)
+        } else {
+            end
+        };
+        let signature = self.signature(i, sig_end);
+        Some(self.entry(kind, name, i, end, Some(signature)))
+    }
+
+    /// The `=` ending a binding's head, outside parentheses.
+    fn top_level_eq(&self, from: usize, end: usize) -> Option<usize> {
+        let mut depth = 0usize;
+        (from..end).find(|&k| match self.text(k) {
+            "(" | "[" | "{" => {
+                depth += 1;
+                false
+            }
+            ")" | "]" | "}" => {
+                depth = depth.saturating_sub(1);
+                false
+            }
+            "=" => depth == 0,
+            _ => false,
+        })
+    }
+
+    /// Index of the `end` closing the `struct`/`sig` at `open`.
+    fn block_end(&self, open: usize, end: usize) -> usize {
+        let mut depth = 0usize;
+        for k in open..end {
+            match self.text(k) {
+                "struct" | "sig" | "begin" | "object" => depth += 1,
+                "end" => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return k;
+                    }
+                }
+                _ => {}
+            }
+        }
+        end
+    }
+
+    /// Whitespace-collapsed source from token `start` up to (not including) `stop`.
+    fn signature(&self, start: usize, stop: usize) -> String {
+        let from = self.toks[start].start;
+        let to = self.toks[stop.max(start + 1) - 1].end;
+        cap(self.content[from..to]
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+
+    /// The item's first source line, for type definitions whose body runs on.
+    fn first_line(&self, start: usize, stop: usize) -> String {
+        let line = self.toks[start].line;
+        let last = (start..stop)
+            .take_while(|&k| self.toks[k].line == line)
+            .last()
+            .unwrap_or(start);
+        self.signature(start, last + 1)
+    }
+
+    fn entry(
+        &self,
+        kind: OutlineKind,
+        name: String,
+        first: usize,
+        stop: usize,
+        signature: Option<String>,
+    ) -> OutlineEntry {
+        let last = &self.toks[stop.max(first + 1) - 1];
+        let first = &self.toks[first];
+        OutlineEntry {
+            byte_range: first.start..last.end,
+            signature,
+            doc: doc_above(first.line, self.lines),
+            ..OutlineEntry::new(kind, name, first.line, last.line)
+        }
+    }
+}
+
+fn cap(sig: String) -> String {
+    if sig.len() > MAX_SIGNATURE {
+        format!("{}...", crate::types::truncate_str(&sig, MAX_SIGNATURE - 3))
+    } else {
+        sig
+    }
+}
+
+fn is_lower_ident(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+        && !matches!(
+            s,
+            "let" | "rec" | "in" | "and" | "type" | "module" | "open" | "val" | "fun" | "of"
+        )
+}
+
+fn is_operator(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|c| OPERATOR_CHARS.contains(&c))
+}
+
+const OPERATOR_CHARS: &[u8] = b"!$%&*+-./:<=>?@^|~#";
+
+/// Words, operators, literals and single punctuation. Comments (nested) are
+/// skipped; strings, quoted strings (`{id|...|id}`) and characters become one
+/// token. `'a` type variables are words.
+fn tokens(content: &str) -> Vec<Token<'_>> {
+    let b = content.as_bytes();
+    let mut out: Vec<Token<'_>> = Vec::new();
+    let mut line: u32 = 1;
+    let mut line_start = 0;
+    let mut i = 0;
+    while i < b.len() {
+        let c = b[i];
+        let start = i;
+        let start_line = line;
+        let col = i - line_start;
+        match c {
+            b'\n' => {
+                line += 1;
+                i += 1;
+                line_start = i;
+                continue;
+            }
+            c if c.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'(' if b.get(i + 1) == Some(&b'*') && b.get(i + 2) != Some(&b')') => {
+                i = skip_comment(b, i, &mut line, &mut line_start);
+                continue;
+            }
+            b'"' => i = skip_string(b, i, &mut line, &mut line_start),
+            b'{' if quoted_id(&b[i + 1..]).is_some() => {
+                let id = quoted_id(&b[i + 1..]).unwrap_or(0);
+                let close = [b"|", &b[i + 1..i + 1 + id], b"}"].concat();
+                i += id + 2;
+                while i < b.len() && !b[i..].starts_with(&close) {
+                    if b[i] == b'\n' {
+                        line += 1;
+                        line_start = i + 1;
+                    }
+                    i += 1;
+                }
+                i = (i + close.len()).min(b.len());
+            }
+            b'\'' => {
+                // `'x'`, `'\n'`, `'\''` are characters; `'a` is a type variable.
+                let len = if b.get(i + 1) == Some(&b'\\') {
+                    b.get(i + 3..)
+                        .and_then(|rest| rest.iter().position(|&c| c == b'\''))
+                        .map(|p| p + 4)
+                } else {
+                    (b.get(i + 2) == Some(&b'\'')).then_some(3)
+                };
+                if let Some(len) = len {
+                    i += len;
+                } else {
+                    i += 1;
+                    while i < b.len() && is_ident_byte(b[i]) {
+                        i += 1;
+                    }
+                }
+            }
+            b';' if b.get(i + 1) == Some(&b';') => i += 2,
+            c if is_ident_byte(c) => {
+                while i < b.len() && is_ident_byte(b[i]) {
+                    i += 1;
+                }
+            }
+            c if OPERATOR_CHARS.contains(&c) => {
+                while i < b.len() && OPERATOR_CHARS.contains(&b[i]) {
+                    i += 1;
+                }
+            }
+            _ => i += 1,
+        }
+        let first = out.last().is_none_or(|t| t.line != start_line);
+        out.push(Token {
+            text: &content[start..i.min(b.len())],
+            start,
+            end: i.min(b.len()),
+            line: start_line,
+            col,
+            first,
+        });
+    }
+    out
+}
+
+/// Identifier bytes; any non-ASCII byte counts, so a token never ends inside
+/// a multi-byte character.
+fn is_ident_byte(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || c == b'\'' || !c.is_ascii()
+}
+
+/// Length of `id` in a quoted-string opener `{id|`, when `b` follows the `{`.
+fn quoted_id(b: &[u8]) -> Option<usize> {
+    let id = b
+        .iter()
+        .take_while(|&&c| c.is_ascii_lowercase() || c == b'_')
+        .count();
+    (b.get(id) == Some(&b'|')).then_some(id)
+}
+
+/// Skip a string literal starting at `i`, returning the index past its quote.
+fn skip_string(b: &[u8], mut i: usize, line: &mut u32, line_start: &mut usize) -> usize {
+    i += 1;
+    while i < b.len() && b[i] != b'"' {
+        if b[i] == b'\\' {
+            i += 1;
+        }
+        if b.get(i) == Some(&b'\n') {
+            *line += 1;
+            *line_start = i + 1;
+        }
+        i += 1;
+    }
+    (i + 1).min(b.len())
+}
+
+/// Skip a (nested) comment starting at `i`. Strings inside comments are
+/// lexed, so a `*)` in one does not close the comment.
+fn skip_comment(b: &[u8], mut i: usize, line: &mut u32, line_start: &mut usize) -> usize {
+    let mut depth = 0usize;
+    while i < b.len() {
+        if b[i..].starts_with(b"(*") {
+            depth += 1;
+            i += 2;
+        } else if b[i..].starts_with(b"*)") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else if b[i] == b'"' {
+            i = skip_string(b, i, line, line_start);
+        } else {
+            if b[i] == b'\n' {
+                *line += 1;
+                *line_start = i + 1;
+            }
+            i += 1;
+        }
+    }
+    b.len()
+}
+
+/// The `(** ... *)` doc comment ending directly above a definition, markers
+/// stripped.
+fn doc_above(start_line: u32, lines: &[&str]) -> Option<String> {
+    let end = (start_line as usize).saturating_sub(1).min(lines.len());
+    let above = &lines[..end];
+    if !above.last()?.trim_end().ends_with("*)") {
+        return None;
+    }
+    let open = above.iter().rposition(|l| l.contains("(*"))?;
+    let text = above[open..].join("\n");
+    let body = text.trim().strip_prefix("(**")?.strip_suffix("*)")?;
+    let block: Vec<&str> = body.lines().map(str::trim).collect();
+    let first = block.iter().position(|l| !l.is_empty())?;
+    let last = block.iter().rposition(|l| !l.is_empty())?;
+    Some(block[first..=last].join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::read::outline::code::outline;
+    use crate::types::Lang;
+
+    #[test]
+    fn module_outline() {
+        let code = r#"(* Stack utilities. *)
+open Core
+open! Stdio
+
+(** A stack of ['a] values. *)
+type 'a t = {
+  mutable items : 'a list;
+  mutable size : int;
+}
+
+and 'a view = Empty | Top of 'a
+
+exception Empty_stack
+
+let default_capacity = 16
+
+(** Create an empty stack. *)
+let create () = { items = []; size = 0 }
+
+let push s x =
+  let items = x :: s.items in
+  s.items <- items;
+  s.size <- s.size + 1
+
+let rec drain s f =
+  match s.items with
+  | [] -> ()
+  | x :: rest ->
+      let () = f x in
+      s.items <- rest;
+      drain s f
+
+and clear s = s.items <- []
+
+let ( |>> ) s x = push s x; s
+
+let to_string : int t -> string = fun s -> "(* not a comment *)" ^ string_of_int s.size
+
+let c = '"'
+
+module type ORDERED = sig
+  type t
+  val compare : t -> t -> int
+  val zero : t
+end
+
+module Make (O : ORDERED) : sig
+  val max : O.t list -> O.t
+end = struct
+  let max l = List.fold_left (fun a b -> if O.compare a b > 0 then a else b) O.zero l
+end
+
+module Int_stack = struct
+  type elt = int
+  let sum s = List.fold_left ( + ) 0 s.items
+end
+
+module L = List
+
+external raw_size : 'a t -> int = "stack_size"
+
+let () = print_endline "loaded"
+
+let%test "push grows" = let s = create () in push s 1; s.size = 1
+"#;
+        let expected = "\
+[2-]   imports: Core, Stdio
+[6-9]        type t
+           type 'a t = {  // A stack of ['a] values.
+[11]         type view
+           and 'a view = Empty | Top of 'a
+[15]         val default_capacity
+[18]         fn create
+           let create ()  // Create an empty stack.
+[20-23]      fn push
+           let push s x
+[25-31]      fn drain
+           let rec drain s f
+[33]         fn clear
+           and clear s
+[35]         fn (|>>)
+           let ( |>> ) s x
+[37]         fn to_string
+           let to_string : int t -> string
+[39]         val c
+[41-45]      interface ORDERED
+           module type ORDERED
+  [42]         type t
+             type t
+  [43]         fn compare
+             val compare : t -> t -> int
+  [44]         val zero
+             val zero : t
+[47-51]      mod Make
+           module Make (O : ORDERED) : sig val max : O.t list -> O.t end
+  [50]         fn max
+             let max l
+[53-56]      mod Int_stack
+           module Int_stack
+  [54]         type elt
+             type elt = int
+  [55]         fn sum
+             let sum s
+[58]         mod L
+           module L = List
+[60]         fn raw_size
+           external raw_size : 'a t -> int
+[64]         test push grows";
+        assert_eq!(outline(code, Lang::OCaml, usize::MAX), expected);
+    }
+
+    #[test]
+    fn non_ascii_text_outline() {
+        // Non-ASCII identifiers and text must not be split mid-character.
+        let code = "let café = 1\n\nlet y = «z»\n\nlet naïve s = s ^ \"é\"\n";
+        let expected = "[1]          val café
+[3]          val y
+[5]          fn naïve
+           let naïve s";
+        assert_eq!(outline(code, Lang::OCaml, usize::MAX), expected);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::read::outline::FormatVersion;
 use crate::types::{Lang, OutlineEntry, OutlineKind};
 
 /// Get the tree-sitter Language for a given Lang variant.
@@ -19,24 +20,80 @@ pub fn outline_language(lang: Lang) -> Option<tree_sitter::Language> {
         Lang::CSharp => tree_sitter_c_sharp::LANGUAGE,
         Lang::Swift => tree_sitter_swift::LANGUAGE,
         Lang::Kotlin => tree_sitter_kotlin_ng::LANGUAGE,
-        Lang::Dockerfile | Lang::Make => {
+        Lang::Registered(id) => return super::registry::language(id),
+        // Dockerfile, Nix, R, Lua, Perl, Zig, OCaml, TOML and YAML are outlined
+        // by scanners (`lang::dockerfile`, `lang::nix`, `lang::r`, `lang::lua`,
+        // `lang::perl`, `lang::zig`, `lang::ocaml`, `lang::toml`, `lang::yaml`);
+        // Vue splits into blocks and outlines its script as JS/TS (`lang::vue`)
+        Lang::Dockerfile
+        | Lang::Make
+        | Lang::Nix
+        | Lang::R
+        | Lang::Lua
+        | Lang::Perl
+        | Lang::Zig
+        | Lang::OCaml
+        | Lang::Toml
+        | Lang::Yaml
+        | Lang::Vue => {
             return None;
         }
     };
     Some(lang.into())
 }
 
-/// Walk top-level children of the root node, extracting outline entries.
+/// Walk top-level children of the root node, extracting outline entries as
+/// `version` of the outline text shows them.
 pub(crate) fn walk_top_level(
     root: tree_sitter::Node,
     lines: &[&str],
     lang: Lang,
+    version: FormatVersion,
 ) -> Vec<OutlineEntry> {
     let mut entries = Vec::new();
     let mut cursor = root.walk();
+    // Go receiver type → index of its method group in `entries`.
+    let mut receivers: Vec<(String, usize)> = Vec::new();
 
     for child in root.children(&mut cursor) {
-        if let Some(entry) = node_to_entry(child, lines, lang, 0) {
+        // Go `type ( A ...; B ... )` declares several types in one node.
+        // C/C++ conditionals (include guards, `#ifdef` branches) are transparent.
+        if version >= FormatVersion::V4
+            && matches!(
+                child.kind(),
+                "preproc_if"
+                    | "preproc_ifdef"
+                    | "preproc_else"
+                    | "preproc_elif"
+                    | "preproc_elifdef"
+            )
+        {
+            entries.extend(walk_top_level(child, lines, lang, version));
+        } else if let Some(ty) =
+            receiver_type(child, lines).filter(|_| version >= FormatVersion::V4)
+        {
+            let Some(method) = node_to_entry(child, lines, lang, 1, version) else {
+                continue;
+            };
+            if let Some(&(_, i)) = receivers.iter().find(|(t, _)| *t == ty) {
+                let group: &mut OutlineEntry = &mut entries[i];
+                group.end_line = group.end_line.max(method.end_line);
+                group.byte_range.end = group.byte_range.end.max(method.byte_range.end);
+                group.children.push(method);
+            } else {
+                receivers.push((ty.clone(), entries.len()));
+                let (start, end) = (method.start_line, method.end_line);
+                entries.push(OutlineEntry {
+                    byte_range: method.byte_range.clone(),
+                    children: vec![method],
+                    ..OutlineEntry::new(OutlineKind::Module, ty, start, end)
+                });
+            }
+        } else if child.kind() == "type_declaration" && type_specs(child).len() > 1 {
+            for spec in type_specs(child) {
+                entries.extend(node_to_entry(spec, lines, lang, 0, version));
+            }
+        } else if let Some(entry) = node_to_entry(child, lines, lang, 0, version) {
             entries.push(entry);
         }
     }
@@ -44,30 +101,154 @@ pub(crate) fn walk_top_level(
     entries
 }
 
+/// The type a Go method is declared on, pointer and type arguments dropped:
+/// `func (s *Server[T]) Start()` → `Server`.
+fn receiver_type(node: tree_sitter::Node, lines: &[&str]) -> Option<String> {
+    if node.kind() != "method_declaration" {
+        return None;
+    }
+    let receiver = node.child_by_field_name("receiver")?;
+    let mut cursor = receiver.walk();
+    let param = receiver
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "parameter_declaration")?;
+    let ty = find_child_text(param, "type", lines)?;
+    let ty = ty
+        .trim_start_matches('*')
+        .split('[')
+        .next()
+        .unwrap_or_default();
+    Some(ty.trim().to_string())
+}
+
+/// The `type_spec` / `type_alias` children of a Go `type_declaration`.
+fn type_specs(node: tree_sitter::Node) -> Vec<tree_sitter::Node> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .filter(|c| matches!(c.kind(), "type_spec" | "type_alias"))
+        .collect()
+}
+
+/// Whether `version` of the outline lists nodes of `kind`. Each version keeps
+/// the declarations it shipped with; later ones only ever add.
+fn outlined_in(kind: &str, lang: Lang, version: FormatVersion) -> bool {
+    match kind {
+        // Go type declarations and Kotlin `typealias`
+        "type_declaration" | "type_spec" | "type_alias" => version >= FormatVersion::V2,
+        // Rust trait items, macros, Go interface methods, C/C++ preprocessor
+        // lines, templates, classes and structs
+        "function_signature_item"
+        | "associated_type"
+        | "macro_definition"
+        | "method_elem"
+        | "method_spec"
+        | "preproc_def"
+        | "preproc_function_def"
+        | "preproc_include"
+        | "template_declaration"
+        | "class_specifier"
+        | "struct_specifier" => version >= FormatVersion::V4,
+        // Ruby methods, classes, constants and requires
+        "method" | "singleton_method" | "class" | "assignment" | "call" if lang == Lang::Ruby => {
+            version >= FormatVersion::V4
+        }
+        _ => true,
+    }
+}
+
 /// Convert a tree-sitter node to an `OutlineEntry` based on its kind.
 fn node_to_entry(
     node: tree_sitter::Node,
     lines: &[&str],
     lang: Lang,
     depth: usize,
+    version: FormatVersion,
 ) -> Option<OutlineEntry> {
     let kind_str = node.kind();
+    if !outlined_in(kind_str, lang, version) {
+        return None;
+    }
     let start_line = node.start_position().row as u32 + 1;
-    let end_line = node.end_position().row as u32 + 1;
+    // Preprocessor lines own their newline, ending at column 0 of the next row.
+    let end = node.end_position();
+    let end_line = if end.column == 0 && end.row > node.start_position().row {
+        end.row as u32
+    } else {
+        end.row as u32 + 1
+    };
+
+    // Python decorators wrap the definition; the entry spans both.
+    if kind_str == "decorated_definition" {
+        let inner = node.child_by_field_name("definition")?;
+        let mut entry = node_to_entry(inner, lines, lang, depth, version)?;
+        entry.start_line = start_line;
+        entry.byte_range.start = node.start_byte();
+        entry.deprecated |= is_deprecated(node, lines, None);
+        let mut cursor = node.walk();
+        entry.decorators = node
+            .children(&mut cursor)
+            .filter(|c| c.kind() == "decorator")
+            .map(|d| decorator_text(d, lines))
+            .collect();
+        return Some(entry);
+    }
+
+    // C++ `template <...>` wraps a class or function; the entry spans both and
+    // its signature leads with the template parameters.
+    if kind_str == "template_declaration" {
+        let params = node.child_by_field_name("parameters")?;
+        let mut cursor = node.walk();
+        let inner = node
+            .named_children(&mut cursor)
+            .filter(|c| c.id() != params.id())
+            .find_map(|c| node_to_entry(c, lines, lang, depth, version));
+        let mut entry = inner?;
+        let template = joined_text(lines, node.start_position(), params.end_position());
+        entry.signature = Some(match entry.signature {
+            Some(sig) => format!("{template} {sig}"),
+            None => template,
+        });
+        entry.start_line = start_line;
+        entry.byte_range.start = node.start_byte();
+        entry.doc = extract_doc(node, lines, lang);
+        return Some(entry);
+    }
+
+    // Go `type X struct {...}` wraps a single spec; the entry spans both.
+    if kind_str == "type_declaration" {
+        let [spec] = type_specs(node)[..] else {
+            return None;
+        };
+        let mut entry = node_to_entry(spec, lines, lang, depth, version)?;
+        entry.start_line = start_line;
+        entry.byte_range.start = node.start_byte();
+        entry.doc = extract_doc(node, lines, lang);
+        entry.deprecated |= is_deprecated(node, lines, entry.doc.as_deref());
+        return Some(entry);
+    }
 
     let (kind, name, signature) = match kind_str {
         // Functions
         "function_declaration"
         | "function_definition"
         | "function_item"
+        // Rust trait methods without a default body
+        | "function_signature_item"
+        // Go interface methods
+        | "method_elem"
+        | "method_spec"
         | "method_definition"
         | "method_declaration"
         | "constructor_declaration"
         | "init_declaration"
         | "deinit_declaration"
-        | "protocol_function_declaration" => {
+        | "protocol_function_declaration"
+        // TS overload signatures: `function f(x: number): void;`
+        | "function_signature"
+        | "method_signature" => {
             let name = find_child_text(node, "name", lines)
                 .or_else(|| find_child_text(node, "identifier", lines))
+                .or_else(|| declarator_name(node, lines).filter(|_| version >= FormatVersion::V4))
                 .unwrap_or_else(|| {
                     // Swift deinit has no name field — use the node kind as name
                     if kind_str == "deinit_declaration" {
@@ -76,17 +257,37 @@ fn node_to_entry(
                         "<anonymous>".into()
                     }
                 });
-            let sig = extract_signature(node, lines);
+            let sig = extract_signature(node, lines, version);
+            (OutlineKind::Function, name, Some(sig))
+        }
+
+        // Ruby methods; `def self.x` is a class-level method
+        "method" | "singleton_method" if lang == Lang::Ruby => {
+            let name = find_child_text(node, "name", lines).unwrap_or_else(|| "<method>".into());
+            let sig = extract_signature(node, lines, version);
             (OutlineKind::Function, name, Some(sig))
         }
 
         // Classes & structs
+        "class" if lang == Lang::Ruby => {
+            let name = find_child_text(node, "name", lines).unwrap_or_else(|| "<class>".into());
+            (OutlineKind::Class, name, None)
+        }
         "class_declaration" | "class_definition" => {
             let name = find_child_text(node, "name", lines)
                 .or_else(|| find_child_text(node, "identifier", lines))
                 .unwrap_or_else(|| "<anonymous>".into());
             (OutlineKind::Class, name, None)
         }
+        // C++ classes; C/C++ structs only where they have a body
+        "class_specifier" => {
+            let name = find_child_text(node, "name", lines).unwrap_or_else(|| "<anonymous>".into());
+            (OutlineKind::Class, name, None)
+        }
+        "struct_specifier" if node.child_by_field_name("body").is_some() => {
+            let name = find_child_text(node, "name", lines).unwrap_or_else(|| "<anonymous>".into());
+            (OutlineKind::Struct, name, None)
+        }
         "struct_item" | "struct_declaration" => {
             let name = find_child_text(node, "name", lines).unwrap_or_else(|| "<anonymous>".into());
             (OutlineKind::Struct, name, None)
@@ -102,17 +303,84 @@ fn node_to_entry(
             let name = find_child_text(node, "name", lines).unwrap_or_else(|| "<anonymous>".into());
             (OutlineKind::Interface, name, None)
         }
-        "type_item" | "type_definition" | "typealias_declaration" => {
+        "type_item" | "type_definition" | "typealias_declaration" | "associated_type" => {
             let name = find_child_text(node, "name", lines).unwrap_or_else(|| "<anonymous>".into());
             (OutlineKind::TypeAlias, name, None)
         }
 
+        // Go type specs, named by what they declare
+        "type_spec" | "type_alias" => {
+            let name = find_child_text(node, "name", lines).unwrap_or_else(|| "<anonymous>".into());
+            let kind = match node.child_by_field_name("type").map(|t| t.kind()) {
+                Some("struct_type") if kind_str == "type_spec" => OutlineKind::Struct,
+                Some("interface_type") if kind_str == "type_spec" => OutlineKind::Interface,
+                _ => OutlineKind::TypeAlias,
+            };
+            (kind, name, None)
+        }
+
+        // Struct fields (Rust) and interface properties (TS), typed in the signature
+        "field_declaration" if lang == Lang::Rust => {
+            let name = find_child_text(node, "name", lines).unwrap_or_else(|| "<field>".into());
+            (OutlineKind::Property, name, Some(member_signature(node, lines)))
+        }
+        "property_signature" => {
+            let name = find_child_text(node, "name", lines).unwrap_or_else(|| "<field>".into());
+            (OutlineKind::Property, name, Some(member_signature(node, lines)))
+        }
+
         // Enums
         "enum_item" | "enum_declaration" | "enum_definition" => {
             let name = find_child_text(node, "name", lines).unwrap_or_else(|| "<anonymous>".into());
             (OutlineKind::Enum, name, None)
         }
 
+        // Enum variants, collected as children of their enum
+        "enum_variant"
+        | "enum_constant"
+        | "enum_member_declaration"
+        | "enum_assignment"
+        | "enum_case"
+        | "enum_entry"
+        | "simple_enum_case"
+        | "full_enum_case" => {
+            let name = find_child_text(node, "name", lines)
+                .or_else(|| first_identifier_text(node, lines))
+                .unwrap_or_else(|| "<variant>".into());
+            (OutlineKind::Constant, name.clone(), variant_signature(node, &name, lines))
+        }
+
+        // Declarative macros (Rust), named as invoked: `vec!`
+        "macro_definition" => {
+            let name = find_child_text(node, "name", lines).unwrap_or_else(|| "<macro>".into());
+            (OutlineKind::Macro, format!("{name}!"), None)
+        }
+
+        // C/C++ preprocessor: `#define` constants show their value,
+        // function-like macros their parameters
+        "preproc_def" => {
+            let name = find_child_text(node, "name", lines).unwrap_or_else(|| "<const>".into());
+            if is_include_guard(node, &name, lines) {
+                return None;
+            }
+            let sig = node.child_by_field_name("value").map(|value| {
+                let text = joined_text(lines, node.start_position(), value.end_position());
+                if text.len() > 120 {
+                    format!("{}...", crate::types::truncate_str(&text, 117))
+                } else {
+                    text
+                }
+            });
+            (OutlineKind::Constant, name, sig)
+        }
+        "preproc_function_def" => {
+            let name = find_child_text(node, "name", lines).unwrap_or_else(|| "<macro>".into());
+            let sig = node.child_by_field_name("parameters").map(|params| {
+                joined_text(lines, node.start_position(), params.end_position())
+            });
+            (OutlineKind::Macro, name, sig)
+        }
+
         // Impl blocks (Rust)
         "impl_item" => {
             let name = find_child_text(node, "type", lines).unwrap_or_else(|| "<impl>".into());
@@ -134,6 +402,14 @@ fn node_to_entry(
                 .unwrap_or_else(|| "<const>".into());
             (OutlineKind::Constant, name, None)
         }
+        // Ruby `VERSION = 2`
+        "assignment"
+            if lang == Lang::Ruby
+                && node.child_by_field_name("left").map(|l| l.kind()) == Some("constant") =>
+        {
+            let name = find_child_text(node, "left", lines).unwrap_or_else(|| "<const>".into());
+            (OutlineKind::Constant, name, None)
+        }
         "val_definition" => {
             let name = first_identifier_text(node, lines).unwrap_or_else(|| "<val>".into());
             (OutlineKind::ImmutableVariable, name, None)
@@ -148,7 +424,7 @@ fn node_to_entry(
             let name = find_child_text(node, "name", lines)
                 .or_else(|| first_identifier_text(node, lines))
                 .unwrap_or_else(|| "<property>".into());
-            let sig = extract_signature(node, lines);
+            let sig = extract_signature(node, lines, version);
             (OutlineKind::Property, name, Some(sig))
         }
 
@@ -159,11 +435,17 @@ fn node_to_entry(
         | "use_declaration"
         | "namespace_use_declaration"
         | "use_item"
-        | "using_directive" => {
+        | "using_directive"
+        | "preproc_include" => {
             let text = node_text(node, lines);
             (OutlineKind::Import, text, None)
         }
 
+        // Ruby `require 'json'` / `require_relative 'lib/x'`
+        "call" if lang == Lang::Ruby && is_require(node, lines) => {
+            (OutlineKind::Import, node_text(node, lines), None)
+        }
+
         // Exports
         "export_statement" => {
             let name = node_text(node, lines);
@@ -176,7 +458,13 @@ fn node_to_entry(
         | "namespace_declaration"
         | "namespace_definition"
         | "file_scoped_namespace_declaration" => {
-            let name = find_child_text(node, "name", lines).unwrap_or_else(|| "<module>".into());
+            let fallback = if kind_str == "namespace_definition" && version >= FormatVersion::V4 {
+                // C++ `namespace { ... }`
+                "<anonymous>"
+            } else {
+                "<module>"
+            };
+            let name = find_child_text(node, "name", lines).unwrap_or_else(|| fallback.into());
             (OutlineKind::Module, name, None)
         }
 
@@ -184,58 +472,282 @@ fn node_to_entry(
     };
 
     // Collect children for classes, impls, modules, traits/interfaces
-    let is_namespace = matches!(
-        kind_str,
-        "namespace_declaration" | "namespace_definition" | "file_scoped_namespace_declaration"
-    );
-    let children = if matches!(
+    let is_namespace =
+        matches!(
+            kind_str,
+            "namespace_declaration" | "namespace_definition" | "file_scoped_namespace_declaration"
+        ) || (lang == Lang::Ruby && kind_str == "module" && version >= FormatVersion::V4);
+    // Java nested types go one level deeper so their own members show.
+    let max_depth = if lang == Lang::Java { 2 } else { 1 };
+    let is_type = matches!(
         kind,
-        OutlineKind::Class | OutlineKind::Struct | OutlineKind::Module | OutlineKind::Interface
-    ) && depth < 1
-    {
+        OutlineKind::Class
+            | OutlineKind::Struct
+            | OutlineKind::Module
+            | OutlineKind::Interface
+            | OutlineKind::Enum
+    );
+    let children = if is_type && depth < max_depth {
         // Namespaces are transparent wrappers — don't consume a depth level,
         // so classes inside namespaces still collect their methods.
         let child_depth = if is_namespace { depth } else { depth + 1 };
-        collect_children(node, lines, lang, child_depth)
+        // A Go type spec's members live in its `struct_type`.
+        let container = if kind_str == "type_spec" {
+            node.child_by_field_name("type").unwrap_or(node)
+        } else {
+            node
+        };
+        collect_children(container, lines, lang, child_depth, version)
+    } else if kind_str == "export_statement" && version >= FormatVersion::V4 {
+        // `export enum` / `export class` list the wrapped declaration's members.
+        node.child_by_field_name("declaration")
+            .and_then(|decl| node_to_entry(decl, lines, lang, depth, version))
+            .map(|decl| decl.children)
+            .unwrap_or_default()
     } else {
         Vec::new()
     };
 
     // Extract doc comment if present
-    let doc = extract_doc(node, lines);
+    let doc = extract_doc(node, lines, lang);
+
+    // Static nested classes don't capture the outer instance; tell them apart from inner ones.
+    let name = if lang == Lang::Java
+        && depth > 0
+        && kind_str == "class_declaration"
+        && has_modifier(node, "static", lines)
+    {
+        format!("{name} (static)")
+    } else {
+        name
+    };
+
+    let deprecated = is_deprecated(node, lines, doc.as_deref());
+
+    // Annotations show on their own lines, not again in the signature.
+    let decorators = if lang == Lang::Java {
+        annotations(node, lines)
+    } else {
+        Vec::new()
+    };
+    let signature = signature.map(|mut sig| {
+        for annotation in &decorators {
+            if let Some(rest) = sig.strip_prefix(annotation.as_str()) {
+                sig = rest.trim_start().to_string();
+            }
+        }
+        sig
+    });
 
     Some(OutlineEntry {
         kind,
         name,
         start_line,
         end_line,
+        byte_range: node.byte_range(),
         signature,
         children,
         doc,
+        deprecated,
+        visibility: visibility(node, lines, lang),
+        // An impl's name already carries its type's generics.
+        type_params: if kind_str == "impl_item" {
+            None
+        } else {
+            type_parameters(node, lines)
+        },
+        modifiers: if kind_str == "singleton_method" {
+            vec!["self".to_string()]
+        } else if kind == OutlineKind::Function {
+            modifiers(node)
+        } else if kind_str == "enum_declaration"
+            && version >= FormatVersion::V4
+            && has_child_kind(node, "const")
+        {
+            // TS `const enum`: members are inlined, no runtime object.
+            vec!["const".to_string()]
+        } else {
+            Vec::new()
+        },
+        decorators,
     })
 }
 
+/// Java annotations in a declaration's `modifiers`: `@Override`, `@GetMapping("/x")`.
+fn annotations(node: tree_sitter::Node, lines: &[&str]) -> Vec<String> {
+    let mut cursor = node.walk();
+    let Some(modifiers) = node.children(&mut cursor).find(|c| c.kind() == "modifiers") else {
+        return Vec::new();
+    };
+    let mut cursor = modifiers.walk();
+    modifiers
+        .children(&mut cursor)
+        .filter(|c| matches!(c.kind(), "marker_annotation" | "annotation"))
+        .map(|a| decorator_text(a, lines))
+        .collect()
+}
+
+/// A decorator or annotation on one line, capped like a signature.
+fn decorator_text(node: tree_sitter::Node, lines: &[&str]) -> String {
+    let text = joined_text(lines, node.start_position(), node.end_position());
+    if text.len() > 120 {
+        format!("{}...", crate::types::truncate_str(&text, 117))
+    } else {
+        text
+    }
+}
+
+/// The name inside a C/C++ declarator chain: `*f(int)` → `f`, `Point::norm() const` → `Point::norm`.
+fn declarator_name(node: tree_sitter::Node, lines: &[&str]) -> Option<String> {
+    let mut declarator = node.child_by_field_name("declarator")?;
+    while let Some(inner) = declarator.child_by_field_name("declarator") {
+        declarator = inner;
+    }
+    Some(node_text(declarator, lines))
+}
+
+/// A Ruby `require` / `require_relative` call.
+fn is_require(node: tree_sitter::Node, lines: &[&str]) -> bool {
+    node.child_by_field_name("receiver").is_none()
+        && matches!(
+            find_child_text(node, "method", lines).as_deref(),
+            Some("require" | "require_relative")
+        )
+}
+
+/// `#define NAME` with no value directly inside `#ifndef NAME`.
+fn is_include_guard(node: tree_sitter::Node, name: &str, lines: &[&str]) -> bool {
+    node.child_by_field_name("value").is_none()
+        && node.parent().is_some_and(|p| {
+            p.kind() == "preproc_ifdef"
+                && find_child_text(p, "name", lines).as_deref() == Some(name)
+        })
+}
+
+/// Deprecation markers: Rust `#[deprecated]` (attribute siblings above), Java/
+/// Kotlin `@Deprecated` and C# `[Obsolete]` (modifier/attribute children), Python
+/// `@deprecated` decorators, and a JSDoc/Javadoc/PHPDoc `@deprecated` tag.
+fn is_deprecated(node: tree_sitter::Node, lines: &[&str], doc: Option<&str>) -> bool {
+    let marked = |n: tree_sitter::Node| {
+        let text = lines
+            .get(n.start_position().row..=n.end_position().row)
+            .map(|rows| rows.join("\n").to_ascii_lowercase())
+            .unwrap_or_default();
+        text.contains("deprecated") || text.contains("obsolete")
+    };
+
+    if doc.is_some_and(|d| d.lines().any(|l| l.trim_start().starts_with("@deprecated"))) {
+        return true;
+    }
+    let mut prev = node.prev_sibling();
+    while let Some(p) = prev {
+        match p.kind() {
+            "attribute_item" if marked(p) => return true,
+            "attribute_item" => {}
+            k if k.contains("comment") => {}
+            _ => break,
+        }
+        prev = p.prev_sibling();
+    }
+    let mut cursor = node.walk();
+    let found = node
+        .children(&mut cursor)
+        .filter(|c| matches!(c.kind(), "modifiers" | "attribute_list" | "decorator"))
+        .any(marked);
+    found
+}
+
 /// Collect child entries from a class/struct/impl body.
 fn collect_children(
     node: tree_sitter::Node,
     lines: &[&str],
     lang: Lang,
     depth: usize,
+    version: FormatVersion,
 ) -> Vec<OutlineEntry> {
     let mut children = Vec::new();
     let mut cursor = node.walk();
 
-    // Look for a body node first (C# uses `declaration_list` instead of `*_body`/`*_block`)
+    // Look for a body node first (C# uses `declaration_list` instead of `*_body`/`*_block`;
+    // enums hold `enum_variant_list`, `enum_member_declaration_list`, ...)
     let body = node.children(&mut cursor).find(|c| {
         let k = c.kind();
-        k.contains("body") || k.contains("block") || k == "declaration_list"
+        k.contains("body")
+            || k.contains("block")
+            || k.ends_with("declaration_list")
+            || k == "enum_variant_list"
     });
 
     let parent = body.unwrap_or(node);
     let mut cursor2 = parent.walk();
 
     for child in parent.children(&mut cursor2) {
-        if let Some(entry) = node_to_entry(child, lines, lang, depth) {
+        match child.kind() {
+            // Java enum members follow the constants in `enum_body_declarations`;
+            // Scala groups `case A, B` lines in `enum_case_definitions`.
+            "enum_body_declarations" | "enum_case_definitions" => {
+                children.extend(collect_children(child, lines, lang, depth, version));
+                continue;
+            }
+            // TS members without an initializer are bare identifiers.
+            "property_identifier" if parent.kind() == "enum_body" => {
+                let name = node_text(child, lines);
+                children.push(member(child, OutlineKind::Constant, name, None));
+                continue;
+            }
+            // Swift `case circle(Double), square` declares one variant per name.
+            "enum_entry" if lang == Lang::Swift => {
+                let mut names = child.walk();
+                for name in child.children_by_field_name("name", &mut names) {
+                    let name = node_text(name, lines);
+                    children.push(member(child, OutlineKind::Constant, name, None));
+                }
+                continue;
+            }
+            // Go `X, Y int` declares one field per name; an embedded field is
+            // named by its type.
+            "field_declaration" if lang == Lang::Go => {
+                let ty = find_child_text(child, "type", lines).unwrap_or_default();
+                let mut names = child.walk();
+                let mut fields: Vec<OutlineEntry> = child
+                    .children_by_field_name("name", &mut names)
+                    .map(|n| {
+                        let name = node_text(n, lines);
+                        let sig = format!("{name} {ty}");
+                        member(child, OutlineKind::Property, name, Some(sig))
+                    })
+                    .collect();
+                if fields.is_empty() {
+                    fields.push(member(child, OutlineKind::Property, ty, None));
+                }
+                let doc = extract_doc(child, lines, lang);
+                for field in &mut fields {
+                    field.doc.clone_from(&doc);
+                }
+                children.extend(fields);
+                continue;
+            }
+            // Ruby `class << self` holds class-level methods.
+            "singleton_class" if lang == Lang::Ruby && version >= FormatVersion::V4 => {
+                for mut entry in collect_children(child, lines, lang, depth, version) {
+                    if entry.kind == OutlineKind::Function {
+                        entry.modifiers = vec!["self".to_string()];
+                    }
+                    children.push(entry);
+                }
+                continue;
+            }
+            // An embedded interface (`io.Closer`) adds its methods to the set.
+            "type_elem" if lang == Lang::Go && version >= FormatVersion::V4 => {
+                let name = node_text(child, lines);
+                let mut entry = member(child, OutlineKind::Interface, name, None);
+                entry.doc = extract_doc(child, lines, lang);
+                children.push(entry);
+                continue;
+            }
+            _ => {}
+        }
+        if let Some(entry) = node_to_entry(child, lines, lang, depth, version) {
             children.push(entry);
         }
     }
@@ -243,30 +755,186 @@ fn collect_children(
     children
 }
 
-/// Extract the first line as a function signature (name + params + return type).
-fn extract_signature(node: tree_sitter::Node, lines: &[&str]) -> String {
-    let start_row = node.start_position().row;
-    if start_row < lines.len() {
-        let line = lines[start_row].trim();
-        // Truncate at opening brace
-        if let Some(pos) = line.find('{') {
-            return line[..pos].trim().to_string();
-        }
-        if line.ends_with(':') {
-            // Python — truncate at trailing colon (for `def foo(x: int):` etc.)
-            if let Some(pos) = line.rfind(':') {
-                return line[..pos].trim().to_string();
+/// A variant or field entry spanning `node`, for declarations that name
+/// several members at once.
+fn member(
+    node: tree_sitter::Node,
+    kind: OutlineKind,
+    name: String,
+    signature: Option<String>,
+) -> OutlineEntry {
+    OutlineEntry {
+        byte_range: node.byte_range(),
+        signature,
+        ..OutlineEntry::new(
+            kind,
+            name,
+            node.start_position().row as u32 + 1,
+            node.end_position().row as u32 + 1,
+        )
+    }
+}
+
+/// A variant's declaration (`Green = 2`, `MERCURY(3.3e23)`) when it says
+/// more than the bare name.
+fn variant_signature(node: tree_sitter::Node, name: &str, lines: &[&str]) -> Option<String> {
+    Some(member_signature(node, lines)).filter(|text| text != name)
+}
+
+/// A field's declaration (`pub name: String`, `readonly id: number`), without
+/// the trailing separator.
+fn member_signature(node: tree_sitter::Node, lines: &[&str]) -> String {
+    let text = node_text(node, lines);
+    text.trim()
+        .trim_end_matches([',', ';'])
+        .trim_end()
+        .to_string()
+}
+
+/// Visibility marker for a declaration: Rust's `visibility_modifier` as
+/// written (`pub`, `pub(crate)`), Java `modifiers` and TypeScript
+/// `accessibility_modifier` as `pub`/`priv`/`protected`.
+fn visibility(node: tree_sitter::Node, lines: &[&str], lang: Lang) -> Option<String> {
+    let wanted = match lang {
+        Lang::Rust => "visibility_modifier",
+        Lang::Java => "modifiers",
+        Lang::TypeScript | Lang::Tsx => "accessibility_modifier",
+        _ => return None,
+    };
+    let mut cursor = node.walk();
+    let modifier = node.children(&mut cursor).find(|c| c.kind() == wanted)?;
+    let text = node_text(modifier, lines);
+    if lang == Lang::Rust {
+        return Some(text);
+    }
+    text.split_whitespace().find_map(|w| match w {
+        "public" => Some("pub".to_string()),
+        "private" => Some("priv".to_string()),
+        "protected" => Some("protected".to_string()),
+        _ => None,
+    })
+}
+
+/// `async` / `unsafe` keywords on a function: Rust's `function_modifiers`,
+/// the `async` token of TypeScript/JavaScript functions and Python `async def`.
+fn modifiers(node: tree_sitter::Node) -> Vec<String> {
+    let mut found = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        let mut inner = child.walk();
+        let tokens: Vec<_> = if child.kind() == "function_modifiers" {
+            child.children(&mut inner).collect()
+        } else {
+            vec![child]
+        };
+        for token in tokens {
+            if matches!(token.kind(), "async" | "unsafe") && !found.contains(&token.kind()) {
+                found.push(token.kind());
             }
         }
-        // Full first line, truncated
-        if line.len() > 120 {
-            format!("{}...", crate::types::truncate_str(line, 117))
+    }
+    found.into_iter().map(String::from).collect()
+}
+
+/// Does `node` have a direct child (token or node) of `kind`?
+fn has_child_kind(node: tree_sitter::Node, kind: &str) -> bool {
+    let mut cursor = node.walk();
+    let found = node.children(&mut cursor).any(|c| c.kind() == kind);
+    found
+}
+
+/// Does a declaration's `modifiers` child include `keyword`?
+fn has_modifier(node: tree_sitter::Node, keyword: &str, lines: &[&str]) -> bool {
+    let mut cursor = node.walk();
+    let found = node
+        .children(&mut cursor)
+        .find(|c| c.kind() == "modifiers")
+        .is_some_and(|m| node_text(m, lines).split_whitespace().any(|w| w == keyword));
+    found
+}
+
+/// A declaration's signature (name + params + return type): its text up to the
+/// body, lines joined and whitespace collapsed so parameters spread over
+/// several lines, a `where` clause or a return type on its own line all read
+/// as one line. Without a body node the text stops at the first `{`.
+fn extract_signature(node: tree_sitter::Node, lines: &[&str], version: FormatVersion) -> String {
+    let mut cursor = node.walk();
+    let body = node.child_by_field_name("body").or_else(|| {
+        node.children(&mut cursor)
+            .find(|c| c.kind().ends_with("body"))
+    });
+    let end = body.map_or(node.end_position(), |b| b.start_position());
+    let joined = joined_text(lines, node.start_position(), end);
+
+    let sig = if body.is_none() {
+        joined.split('{').next().unwrap_or(&joined)
+    } else {
+        &joined
+    };
+    // Python's trailing colon, a `where` clause's trailing comma and, since
+    // version 4, the semicolon ending a declaration without a body.
+    let ends: &[char] = if version >= FormatVersion::V4 {
+        &[':', ',', ';']
+    } else {
+        &[':', ',']
+    };
+    let sig = sig.trim_end().trim_end_matches(ends).trim_end();
+    if sig.len() > 120 {
+        format!("{}...", crate::types::truncate_str(sig, 117))
+    } else {
+        sig.to_string()
+    }
+}
+
+/// Source text between two points as one line: whitespace collapsed, no
+/// space just inside brackets, and a trailing comma before a closer dropped.
+fn joined_text(lines: &[&str], start: tree_sitter::Point, end: tree_sitter::Point) -> String {
+    let mut joined = String::new();
+    let rows = lines.iter().enumerate().take(end.row + 1).skip(start.row);
+    for (row, line) in rows {
+        let from = if row == start.row {
+            start.column.min(line.len())
         } else {
-            line.to_string()
+            0
+        };
+        let to = if row == end.row {
+            end.column.min(line.len())
+        } else {
+            line.len()
+        };
+        let piece = line.get(from..to).unwrap_or_default();
+        let piece = piece.split_whitespace().collect::<Vec<_>>().join(" ");
+        if piece.is_empty() {
+            continue;
         }
-    } else {
-        String::new()
+        if !joined.is_empty()
+            && !joined.ends_with(['(', '<', '['])
+            && !piece.starts_with([')', '>', ']'])
+        {
+            joined.push(' ');
+        }
+        joined.push_str(&piece);
     }
+    joined
+        .replace(",)", ")")
+        .replace(",>", ">")
+        .replace(",]", "]")
+}
+
+/// Generic parameters as written (`<T: Clone>`, Go's `[T any]`), on one line.
+fn type_parameters(node: tree_sitter::Node, lines: &[&str]) -> Option<String> {
+    let params = node.child_by_field_name("type_parameters").or_else(|| {
+        let mut cursor = node.walk();
+        let found = node
+            .children(&mut cursor)
+            .find(|c| matches!(c.kind(), "type_parameters" | "type_parameter_list"));
+        found
+    })?;
+    Some(joined_text(
+        lines,
+        params.start_position(),
+        params.end_position(),
+    ))
 }
 
 /// Find a named child and return its text.
@@ -328,26 +996,71 @@ fn first_identifier_text(node: tree_sitter::Node, lines: &[&str]) -> Option<Stri
     None
 }
 
-/// Extract a doc comment from the previous sibling.
-fn extract_doc(node: tree_sitter::Node, lines: &[&str]) -> Option<String> {
-    let prev = node.prev_sibling()?;
-    let kind = prev.kind();
-    if kind.contains("comment") || kind.contains("doc") {
-        let text = node_text(prev, lines);
-        let trimmed = text
-            .trim_start_matches("///")
-            .trim_start_matches("//!")
-            .trim_start_matches("/**")
-            .trim_start_matches('#')
-            .trim();
-        if trimmed.is_empty() {
-            None
+/// Comment delimiters per language, longest first so `///` wins over `//`.
+fn comment_markers(lang: Lang) -> &'static [&'static str] {
+    match lang {
+        Lang::Python
+        | Lang::Ruby
+        | Lang::Dockerfile
+        | Lang::Make
+        | Lang::Perl
+        | Lang::Toml
+        | Lang::Yaml => &["#"],
+        Lang::Rust => &["///", "//!", "/**", "/*!", "/*", "//"],
+        Lang::Php => &["/**", "/*", "//", "#"],
+        _ => &["/**", "/*", "///", "//"],
+    }
+}
+
+/// Doc comment directly above a node: the contiguous run of comment siblings
+/// (Rust attributes may sit in between), delimiters stripped, blank edges trimmed.
+fn extract_doc(node: tree_sitter::Node, lines: &[&str], lang: Lang) -> Option<String> {
+    let mut block: Option<(usize, usize)> = None;
+    let mut next_row = node.start_position().row;
+    let mut cursor = node.prev_sibling();
+
+    while let Some(prev) = cursor {
+        let kind = prev.kind();
+        if kind == "attribute_item" {
+            next_row = prev.start_position().row;
+        } else if kind.contains("comment") || kind.contains("doc") {
+            // Line comments can include their trailing newline — end at column 0 of the next row.
+            let end = prev.end_position();
+            let last_row = if end.column == 0 {
+                end.row.saturating_sub(1)
+            } else {
+                end.row
+            };
+            if last_row + 1 < next_row {
+                break; // blank line — not attached
+            }
+            let start_row = prev.start_position().row;
+            block = Some((start_row, block.map_or(last_row, |(_, last)| last)));
+            next_row = start_row;
         } else {
-            Some(trimmed.to_string())
+            break;
         }
-    } else {
-        None
+        cursor = prev.prev_sibling();
     }
+
+    let (first, last) = block?;
+    let markers = comment_markers(lang);
+    let text: Vec<&str> = lines
+        .get(first..=last.min(lines.len().saturating_sub(1)))?
+        .iter()
+        .map(|line| {
+            let line = line.trim();
+            let line = markers
+                .iter()
+                .find_map(|m| line.strip_prefix(m))
+                .unwrap_or(line);
+            let line = line.trim_end().trim_end_matches("*/");
+            line.trim_start().trim_start_matches('*').trim()
+        })
+        .skip_while(|l| l.is_empty())
+        .collect();
+    let end = text.iter().rposition(|l| !l.is_empty())?;
+    Some(text[..=end].join("\n"))
 }
 
 /// Extract the source module name from an import statement text.
@@ -392,6 +1105,19 @@ pub(crate) fn extract_import_source(text: &str) -> String {
         return rest.split_whitespace().next().unwrap_or("").to_string();
     }
 
+    // Zig: `const std = @import("std")` → `std`
+    if let Some((_, rest)) = trimmed.split_once("@import(\"") {
+        return rest.split('"').next().unwrap_or(rest).to_string();
+    }
+
+    // Ruby: `require 'json'` / `require_relative "lib/x"`
+    if let Some(rest) = trimmed
+        .strip_prefix("require_relative ")
+        .or_else(|| trimmed.strip_prefix("require "))
+    {
+        return rest.trim().trim_matches(['"', '\'']).to_string();
+    }
+
     // C/C++: #include "file.h" or #include <header>
     if let Some(rest) = trimmed.strip_prefix("#include") {
         return rest.trim().to_string(); // preserves quotes/angles for external detection
@@ -408,6 +1134,10 @@ pub(crate) fn extract_import_source(text: &str) -> String {
 
 /// Get structured outline entries for file content.
 pub fn get_outline_entries(content: &str, lang: Lang) -> Vec<OutlineEntry> {
+    let content = super::strip_bom(content);
+    if let Some(entries) = super::scanner_entries(lang, content) {
+        return entries;
+    }
     let Some(ts_lang) = outline_language(lang) else {
         return Vec::new();
     };
@@ -422,5 +1152,5 @@ pub fn get_outline_entries(content: &str, lang: Lang) -> Vec<OutlineEntry> {
     };
 
     let lines: Vec<&str> = content.lines().collect();
-    walk_top_level(tree.root_node(), &lines, lang)
+    walk_top_level(tree.root_node(), &lines, lang, FormatVersion::LATEST)
 }
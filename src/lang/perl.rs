@@ -0,0 +1,555 @@
+//! Perl outline scanner. There is no Perl tree-sitter grammar in the build; a
+//! tokenizer that skips strings, quote-like operators, regexes, heredocs and
+//! POD is enough to find `package`, `sub` and `use`/`require` statements at
+//! the top level. Subs are grouped under the package they follow (or the
+//! `package Name { ... }` block they sit in).
+
+use crate::types::{OutlineEntry, OutlineKind};
+
+struct Token<'a> {
+    text: &'a str,
+    start: usize,
+    end: usize,
+    line: u32,
+}
+
+/// Outline entries for a Perl file: packages as `Module` with their subs as
+/// children, subs outside any package at the top, and `use`/`require` of
+/// modules as imports. Pragmas (`use strict`) and version requirements are
+/// left out.
+pub fn outline_entries(content: &str) -> Vec<OutlineEntry> {
+    let lines: Vec<&str> = content.lines().collect();
+    let toks = tokens(content);
+    // Flat list of (entry, parent index); assembled into a tree at the end.
+    let mut flat: Vec<(OutlineEntry, Option<usize>)> = Vec::new();
+    // `package Name;` in effect at depth 0.
+    let mut stmt_pkg: Option<usize> = None;
+    // `package Name { ... }` blocks: (entry index, depth inside the block).
+    let mut block_pkgs: Vec<(usize, usize)> = Vec::new();
+    let mut depth = 0usize;
+
+    for (k, t) in toks.iter().enumerate() {
+        let stmt_start = k == 0 || matches!(toks[k - 1].text, ";" | "{" | "}");
+        let parent = match block_pkgs.last() {
+            Some(&(idx, inner)) if inner == depth => Some(Some(idx)),
+            _ if depth == 0 => Some(stmt_pkg),
+            _ => None,
+        };
+        if let (true, Some(parent)) = (stmt_start, parent) {
+            match t.text {
+                "package" => {
+                    if let Some(entry) = package(&toks, k, &lines) {
+                        let block = toks.get(k + 2).is_some_and(|n| n.text == "{");
+                        if depth == 0 {
+                            if let Some(prev) = stmt_pkg.take() {
+                                close(&mut flat[prev].0, &toks[..k]);
+                            }
+                        }
+                        flat.push((entry, None));
+                        let idx = flat.len() - 1;
+                        if block {
+                            block_pkgs.push((idx, depth + 1));
+                        } else if depth == 0 {
+                            stmt_pkg = Some(idx);
+                        }
+                    }
+                }
+                "sub" => {
+                    if let Some(entry) = sub(content, &toks, k, &lines) {
+                        flat.push((entry, parent));
+                    }
+                }
+                "use" | "require" => {
+                    if let Some(entry) = import(&toks, k) {
+                        flat.push((entry, None));
+                    }
+                }
+                _ => {}
+            }
+        }
+        match t.text {
+            "{" => depth += 1,
+            "}" => {
+                depth = depth.saturating_sub(1);
+                if block_pkgs
+                    .last()
+                    .is_some_and(|&(_, inner)| inner == depth + 1)
+                {
+                    let (idx, _) = block_pkgs.pop().expect("checked above");
+                    let entry = &mut flat[idx].0;
+                    entry.end_line = t.line;
+                    entry.byte_range.end = t.end;
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(prev) = stmt_pkg {
+        close(&mut flat[prev].0, &toks);
+    }
+
+    let mut entries: Vec<OutlineEntry> = Vec::new();
+    // Position in `entries` of each flat package entry.
+    let mut placed: Vec<Option<usize>> = Vec::with_capacity(flat.len());
+    for (entry, parent) in flat {
+        if let Some(p) = parent.and_then(|p| placed[p]) {
+            entries[p].children.push(entry);
+            placed.push(None);
+        } else {
+            entries.push(entry);
+            placed.push(Some(entries.len() - 1));
+        }
+    }
+    entries
+}
+
+/// A statement-form package runs to the last token before the next one.
+fn close(entry: &mut OutlineEntry, before: &[Token<'_>]) {
+    if let Some(last) = before.last() {
+        entry.end_line = last.line.max(entry.start_line);
+        entry.byte_range.end = last.end.max(entry.byte_range.end);
+    }
+}
+
+fn package(toks: &[Token<'_>], k: usize, lines: &[&str]) -> Option<OutlineEntry> {
+    let name = toks.get(k + 1).filter(|t| is_word(t.text))?;
+    let start = &toks[k];
+    let end = toks.get(k + 2).filter(|t| t.text == ";").unwrap_or(name);
+    Some(OutlineEntry {
+        byte_range: start.start..end.end,
+        doc: doc_above(start.line, lines),
+        ..OutlineEntry::new(OutlineKind::Module, name.text, start.line, end.line)
+    })
+}
+
+/// `sub name [(proto)] [:attrs] { ... }`. Forward declarations and anonymous
+/// subs are skipped.
+fn sub(content: &str, toks: &[Token<'_>], k: usize, lines: &[&str]) -> Option<OutlineEntry> {
+    let name = toks.get(k + 1).filter(|t| is_word(t.text))?;
+    let open = toks[k + 2..]
+        .iter()
+        .position(|t| matches!(t.text, "{" | ";"))?
+        + k
+        + 2;
+    if toks[open].text != "{" {
+        return None;
+    }
+    let close = matching(toks, open).unwrap_or(toks.len() - 1);
+    let start = &toks[k];
+    let head = content[start.start..toks[open].start]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(OutlineEntry {
+        byte_range: start.start..toks[close].end,
+        signature: Some(head),
+        doc: doc_above(start.line, lines),
+        ..OutlineEntry::new(
+            OutlineKind::Function,
+            name.text,
+            start.line,
+            toks[close].line,
+        )
+    })
+}
+
+/// `use Module ...;` / `require Module;` as `use Module`, so the import
+/// summary shows the module. Pragmas are lowercase by convention.
+fn import(toks: &[Token<'_>], k: usize) -> Option<OutlineEntry> {
+    let module = toks
+        .get(k + 1)
+        .filter(|t| is_word(t.text) && t.text.starts_with(|c: char| c.is_ascii_uppercase()))?;
+    let start = &toks[k];
+    let end = toks[k..].iter().find(|t| t.text == ";").unwrap_or(module);
+    Some(OutlineEntry {
+        byte_range: start.start..end.end,
+        ..OutlineEntry::new(
+            OutlineKind::Import,
+            format!("{} {}", start.text, module.text),
+            start.line,
+            end.line,
+        )
+    })
+}
+
+/// Index of the `}` closing the `{` at `open`.
+fn matching(toks: &[Token<'_>], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, t) in toks.iter().enumerate().skip(open) {
+        match t.text {
+            "{" => depth += 1,
+            "}" => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn is_word(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_alphabetic() || c == '_')
+}
+
+/// Identifier bytes; any non-ASCII byte counts, so a token never ends inside
+/// a multi-byte character.
+fn is_ident_byte(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || !c.is_ascii()
+}
+
+/// Whether a `/` after `prev` starts a regex rather than dividing.
+fn regex_follows(prev: Option<&Token<'_>>) -> bool {
+    match prev.map(|t| t.text) {
+        None => true,
+        Some(p) if is_word(p) => matches!(
+            p,
+            "split" | "grep" | "map" | "if" | "unless" | "and" | "or" | "not" | "return" | "when"
+        ),
+        Some(p) => !matches!(p, ")" | "]" | "}" | "\"\"") && !p.starts_with(['$', '@', '%']),
+    }
+}
+
+/// Words, variables and punctuation, with comments, POD, string and regex
+/// contents and heredoc bodies skipped. Strings and quote-like operators
+/// become a single `""` token. Stops at `__END__` / `__DATA__`.
+fn tokens(content: &str) -> Vec<Token<'_>> {
+    let b = content.as_bytes();
+    let mut out: Vec<Token<'_>> = Vec::new();
+    let mut line: u32 = 1;
+    // Heredoc terminators waiting for the end of the current line: (word, indented).
+    let mut heredocs: Vec<(String, bool)> = Vec::new();
+    let mut i = 0;
+
+    while i < b.len() {
+        let at_line_start = i == 0 || b[i - 1] == b'\n';
+        if at_line_start {
+            let rest = &content[i..];
+            if rest.starts_with("__END__") || rest.starts_with("__DATA__") {
+                break;
+            }
+            // POD: `=word` at the start of a line, through `=cut`.
+            if b[i] == b'=' && b.get(i + 1).is_some_and(u8::is_ascii_alphabetic) {
+                i = skip_lines(content, i, &mut line, |l| l.starts_with("=cut"));
+                continue;
+            }
+        }
+        let c = b[i];
+        let start = i;
+        let start_line = line;
+        match c {
+            b'\n' => {
+                line += 1;
+                i += 1;
+                for (word, indented) in std::mem::take(&mut heredocs) {
+                    i = skip_lines(content, i, &mut line, |l| {
+                        (if indented { l.trim() } else { l }) == word
+                    });
+                }
+                continue;
+            }
+            c if c.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'#' => {
+                while i < b.len() && b[i] != b'\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            b'"' | b'\'' | b'`' => {
+                i = skip_delimited(b, i, &mut line);
+                out.push(string_token(start, i, start_line));
+                continue;
+            }
+            b'<' if b.get(i + 1) == Some(&b'<') => {
+                if let Some((word, indented, len)) = heredoc(&content[i + 2..]) {
+                    heredocs.push((word, indented));
+                    i += 2 + len;
+                    out.push(string_token(start, i, start_line));
+                    continue;
+                }
+                i += 2;
+            }
+            b'/' if regex_follows(out.last()) => {
+                i = skip_delimited(b, i, &mut line);
+                out.push(string_token(start, i, start_line));
+                continue;
+            }
+            // Division, or the `//` defined-or operator.
+            b'/' => i += if b.get(i + 1) == Some(&b'/') { 2 } else { 1 },
+            b'$' | b'@' | b'%' | b'&' => {
+                i += 1;
+                // `$#array`, `$#{...}`, and punctuation variables like `$"`.
+                if c == b'$'
+                    && b.get(i)
+                        .is_some_and(|&n| !is_ident_byte(n) && !matches!(n, b'{' | b':' | b'\n'))
+                {
+                    i += 1;
+                }
+                while i < b.len() && (is_ident_byte(b[i]) || b[i] == b':') {
+                    i += 1;
+                }
+            }
+            c if is_ident_byte(c) && !c.is_ascii_digit() => {
+                while i < b.len()
+                    && (is_ident_byte(b[i]) || (b[i] == b':' && b.get(i + 1) == Some(&b':')))
+                {
+                    i += if b[i] == b':' { 2 } else { 1 };
+                }
+                let word = &content[start..i];
+                let parts = match word {
+                    "q" | "qq" | "qw" | "qr" | "m" => 1,
+                    "s" | "tr" | "y" => 2,
+                    _ => 0,
+                };
+                if parts > 0 {
+                    if let Some(end) = skip_quote_like(b, i, parts, &mut line) {
+                        i = end;
+                        out.push(string_token(start, i, start_line));
+                        continue;
+                    }
+                }
+            }
+            _ => i += 1,
+        }
+        out.push(Token {
+            text: &content[start..i],
+            start,
+            end: i,
+            line: start_line,
+        });
+    }
+    out
+}
+
+fn string_token(start: usize, end: usize, line: u32) -> Token<'static> {
+    Token {
+        text: "\"\"",
+        start,
+        end,
+        line,
+    }
+}
+
+/// Skip from the line starting at `i` through the first line matching `done`;
+/// returns the offset after it.
+fn skip_lines(content: &str, mut i: usize, line: &mut u32, done: impl Fn(&str) -> bool) -> usize {
+    while i < content.len() {
+        let end = content[i..].find('\n').map_or(content.len(), |n| i + n);
+        let text = content[i..end].trim_end_matches('\r');
+        i = (end + 1).min(content.len());
+        if end < content.len() {
+            *line += 1;
+        }
+        if done(text) {
+            break;
+        }
+    }
+    i
+}
+
+/// `<<"EOF"`, `<<'EOF'`, `<<EOF`, `<<~EOF` after the `<<`: terminator,
+/// whether it may be indented, and the length of the marker. A quoted
+/// terminator may follow spaces (`<< "EOF"`).
+fn heredoc(rest: &str) -> Option<(String, bool, usize)> {
+    let (indented, body) = match rest.strip_prefix('~') {
+        Some(body) => (true, body),
+        None => (false, rest),
+    };
+    let spaced = body.trim_start_matches(' ');
+    let body = if spaced.starts_with(['"', '\'']) {
+        spaced
+    } else {
+        body
+    };
+    let skipped = rest.len() - body.len();
+    if let Some(quote) = body.strip_prefix(['"', '\'']) {
+        let end = quote.find(['"', '\''])?;
+        return Some((quote[..end].to_string(), indented, skipped + end + 2));
+    }
+    let word: String = body
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .collect();
+    (word.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_'))
+        .then(|| (word.clone(), indented, skipped + word.len()))
+}
+
+/// Skip a string or regex whose opening delimiter is at `i`. Bracket
+/// delimiters nest. Returns the offset after the closing delimiter.
+fn skip_delimited(b: &[u8], mut i: usize, line: &mut u32) -> usize {
+    let open = b[i];
+    let close = match open {
+        b'(' => b')',
+        b'[' => b']',
+        b'{' => b'}',
+        b'<' => b'>',
+        c => c,
+    };
+    let mut depth = 0usize;
+    i += 1;
+    while i < b.len() {
+        match b[i] {
+            b'\\' => i += 1,
+            b'\n' => *line += 1,
+            c if c == close && depth == 0 => return i + 1,
+            c if c == close => depth -= 1,
+            c if c == open => depth += 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    b.len()
+}
+
+/// `q(...)`, `s{...}{...}`, `tr/a/b/` starting after the operator word at `i`.
+/// `None` when no delimiter follows (the word is something else, e.g. a hash
+/// key `s => 1`).
+fn skip_quote_like(b: &[u8], mut i: usize, parts: usize, line: &mut u32) -> Option<usize> {
+    let is_delim = |c: u8| {
+        c.is_ascii()
+            && !c.is_ascii_alphanumeric()
+            && !c.is_ascii_whitespace()
+            && !matches!(c, b'_' | b',' | b';' | b')' | b'=')
+    };
+    while b.get(i).is_some_and(|c| *c == b' ' || *c == b'\t') {
+        i += 1;
+    }
+    let open = *b.get(i).filter(|&&c| is_delim(c))?;
+    if open == b'=' || (open == b'>' && i > 0 && b[i - 1] == b'=') {
+        return None;
+    }
+    let bracketed = matches!(open, b'(' | b'[' | b'{' | b'<');
+    i = skip_delimited(b, i, line);
+    if parts == 2 {
+        if bracketed {
+            while b.get(i).is_some_and(u8::is_ascii_whitespace) {
+                if b[i] == b'\n' {
+                    *line += 1;
+                }
+                i += 1;
+            }
+            if b.get(i).is_some_and(|&c| is_delim(c)) {
+                i = skip_delimited(b, i, line);
+            }
+        } else {
+            // `s/a/b/`: the first part's closer opens the second.
+            i = skip_delimited(b, i - 1, line);
+        }
+    }
+    Some(i)
+}
+
+/// `#` comment lines directly above `start_line`, or the text of a POD block
+/// ending in `=cut` just above it (blank lines between allowed).
+fn doc_above(start_line: u32, lines: &[&str]) -> Option<String> {
+    let end = (start_line as usize).saturating_sub(1).min(lines.len());
+    let above = &lines[..end];
+    let comments: Vec<&str> = above
+        .iter()
+        .rev()
+        .map(|l| l.trim())
+        .take_while(|l| l.starts_with('#'))
+        .map(|l| l.trim_start_matches('#').trim())
+        .collect();
+    let mut block: Vec<&str> = if comments.is_empty() {
+        let mut pod = above.iter().rev().skip_while(|l| l.trim().is_empty());
+        if !pod.next().is_some_and(|l| l.starts_with("=cut")) {
+            return None;
+        }
+        // The paragraphs under the block's last directive (`=head2 name`).
+        pod.take_while(|l| !l.starts_with('='))
+            .map(|l| l.trim())
+            .collect()
+    } else {
+        comments
+    };
+    block.reverse();
+    let first = block.iter().position(|l| !l.is_empty())?;
+    let last = block.iter().rposition(|l| !l.is_empty())?;
+    Some(block[first..=last].join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::read::outline::code::outline;
+    use crate::types::Lang;
+
+    #[test]
+    fn module_outline() {
+        let pm = r#"package My::Cache;
+
+use strict;
+use warnings;
+use List::Util qw(first max);
+require Storable;
+
+our $VERSION = '0.01';
+
+=head2 new
+
+Build an empty cache.
+
+=cut
+
+sub new {
+    my ($class, %args) = @_;
+    my $self = { max => $args{max} // 10, items => {} };
+    return bless $self, $class;
+}
+
+# Fetch a value; counts a miss otherwise.
+sub get {
+    my ($self, $key) = @_;
+    my $n = $#{ $self->{order} };
+    return $self->{items}{$key} if $key =~ /^\w+\{$/;
+    (my $clean = $key) =~ s{\s+}{ }g;
+    return;
+}
+
+sub dump {
+    my $self = shift;
+    print <<"EOT";
+sub not_a_sub {
+EOT
+    return 1;
+}
+
+package My::Cache::Entry {
+    sub value { $_[0]{value} }
+}
+
+1;
+
+__END__
+
+sub after_end { }
+"#;
+        let expected = "\
+[1-37]       mod My::Cache
+  [16-20]      fn new
+             sub new  // Build an empty cache.
+  [23-29]      fn get
+             sub get  // Fetch a value; counts a miss otherwise.
+  [31-37]      fn dump
+             sub dump
+[5-]   imports: List::Util, Storable
+[39-41]      mod My::Cache::Entry
+  [40]         fn value
+             sub value";
+        assert_eq!(outline(pm, Lang::Perl, usize::MAX), expected);
+    }
+
+    #[test]
+    fn non_ascii_names_outline() {
+        // Non-ASCII identifiers and text must not be split mid-character.
+        let code = "my $café = 1;\nmy $s = q é;\n\nsub naïve {\n    return \"é\";\n}\n";
+        let expected = "[4-6]        fn naïve
+           sub naïve";
+        assert_eq!(outline(code, Lang::Perl, usize::MAX), expected);
+    }
+}
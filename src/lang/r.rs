@@ -0,0 +1,265 @@
+//! R outline scanner. There is no R tree-sitter grammar in the build, and R
+//! defines functions by assignment (`name <- function(x) ...`), so the outline
+//! is the file's top-level assignments: `Function` when the value is a
+//! `function` or `\(x)` lambda, `Variable` otherwise.
+
+use crate::types::{OutlineEntry, OutlineKind};
+
+/// A top-level expression: byte range and 1-based lines.
+struct Stmt {
+    start: usize,
+    end: usize,
+    start_line: u32,
+    end_line: u32,
+}
+
+/// Outline entries for an R script, one per top-level assignment.
+pub fn outline_entries(content: &str) -> Vec<OutlineEntry> {
+    let lines: Vec<&str> = content.lines().collect();
+    statements(content)
+        .iter()
+        .filter_map(|s| assignment_entry(content, s, &lines))
+        .collect()
+}
+
+/// Split `content` into top-level expressions. One ends at a newline or `;`
+/// outside brackets, unless the line ends in an operator or a function header
+/// still waits for its body.
+fn statements(content: &str) -> Vec<Stmt> {
+    let b = content.as_bytes();
+    let mut out = Vec::new();
+    let mut depth = 0usize;
+    let mut line: u32 = 1;
+    let mut start: Option<(usize, u32)> = None;
+    // Last byte of the last token, for continuation checks.
+    let mut last = b'\n';
+    // Saw `function` at the top level; its body hasn't started yet.
+    let mut awaiting_body = false;
+    let mut i = 0;
+
+    let mut close = |start: &mut Option<(usize, u32)>, end: usize, end_line: u32| {
+        if let Some((s, start_line)) = start.take() {
+            out.push(Stmt {
+                start: s,
+                end: s.max(end),
+                start_line,
+                end_line,
+            });
+        }
+    };
+
+    while i < b.len() {
+        let c = b[i];
+        match c {
+            b'#' => {
+                while i < b.len() && b[i] != b'\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            b'\n' => {
+                let header_done = awaiting_body && last == b')' && depth == 0;
+                if depth == 0 && !continues(last) && !header_done {
+                    close(&mut start, trimmed_end(b, i), line);
+                    awaiting_body = false;
+                }
+                line += 1;
+            }
+            b';' if depth == 0 => {
+                close(&mut start, i, line);
+                awaiting_body = false;
+                last = b'\n';
+            }
+            b'"' | b'\'' | b'`' => {
+                start.get_or_insert((i, line));
+                i += 1;
+                while i < b.len() && b[i] != c {
+                    match b[i] {
+                        b'\\' => i += 1,
+                        b'\n' => line += 1,
+                        _ => {}
+                    }
+                    i += 1;
+                }
+                last = c;
+            }
+            b'(' | b'{' | b'[' => {
+                start.get_or_insert((i, line));
+                if depth == 0 && (c == b'{' || last == b')') {
+                    awaiting_body = false;
+                }
+                depth += 1;
+                last = c;
+            }
+            b')' | b'}' | b']' => {
+                depth = depth.saturating_sub(1);
+                last = c;
+            }
+            c if c.is_ascii_whitespace() => {}
+            _ => {
+                start.get_or_insert((i, line));
+                if depth == 0 && last == b')' {
+                    awaiting_body = false;
+                }
+                let word_start = i;
+                while i + 1 < b.len() && is_ident_byte(b[i + 1]) && is_ident_byte(c) {
+                    i += 1;
+                }
+                if depth == 0 && (&b[word_start..=i] == b"function" || c == b'\\') {
+                    awaiting_body = true;
+                }
+                last = b[i];
+            }
+        }
+        i += 1;
+    }
+    close(&mut start, trimmed_end(b, b.len()), line);
+    out
+}
+
+/// Whether a line ending in `c` carries on to the next line.
+fn continues(c: u8) -> bool {
+    matches!(
+        c,
+        b'+' | b'-' | b'*' | b'/' | b'^' | b'|' | b'&' | b',' | b'=' | b'<' | b'>' | b'~' | b'%'
+    )
+}
+
+/// `end` moved back over trailing whitespace.
+fn trimmed_end(b: &[u8], mut end: usize) -> usize {
+    while end > 0 && b[end - 1].is_ascii_whitespace() {
+        end -= 1;
+    }
+    end
+}
+
+fn is_ident_byte(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, b'.' | b'_')
+}
+
+/// `name <- value`, `name <<- value` or `name = value` as an entry.
+fn assignment_entry(content: &str, stmt: &Stmt, lines: &[&str]) -> Option<OutlineEntry> {
+    let text = &content[stmt.start..stmt.end];
+    let (name, rest) = if let Some(quoted) = text.strip_prefix('`') {
+        quoted.split_once('`')?
+    } else {
+        let end = text.bytes().position(|c| !is_ident_byte(c))?;
+        (&text[..end], &text[end..])
+    };
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let rest = rest.trim_start();
+    let value = rest
+        .strip_prefix("<<-")
+        .or_else(|| rest.strip_prefix("<-"))
+        .or_else(|| rest.strip_prefix('=').filter(|v| !v.starts_with('=')))?
+        .trim_start();
+
+    let params = value
+        .strip_prefix("function")
+        .or_else(|| value.strip_prefix('\\'))
+        .map(str::trim_start)
+        .filter(|p| p.starts_with('('));
+    let (kind, signature) = match params {
+        Some(params) => {
+            let close = matching_paren(params)?;
+            let head = &text[..=text.len() - params.len() + close];
+            let sig = head.split_whitespace().collect::<Vec<_>>().join(" ");
+            let sig = if sig.len() > 120 {
+                format!("{}...", crate::types::truncate_str(&sig, 117))
+            } else {
+                sig
+            };
+            (OutlineKind::Function, Some(sig))
+        }
+        None => (OutlineKind::Variable, None),
+    };
+
+    Some(OutlineEntry {
+        byte_range: stmt.start..stmt.end,
+        signature,
+        doc: doc_above(stmt.start_line, lines),
+        ..OutlineEntry::new(kind, name, stmt.start_line, stmt.end_line)
+    })
+}
+
+/// Byte offset of the `)` closing the `(` that starts `s`.
+fn matching_paren(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in s.bytes().enumerate() {
+        match c {
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Roxygen (`#'`) or plain `#` comment lines directly above an assignment,
+/// markers stripped.
+fn doc_above(start_line: u32, lines: &[&str]) -> Option<String> {
+    let end = (start_line as usize).saturating_sub(1).min(lines.len());
+    let mut block: Vec<&str> = lines[..end]
+        .iter()
+        .rev()
+        .map(|l| l.trim())
+        .take_while(|l| l.starts_with('#'))
+        .map(|l| l.trim_start_matches("#'").trim_start_matches('#').trim())
+        .collect();
+    block.reverse();
+    let first = block.iter().position(|l| !l.is_empty())?;
+    let last = block.iter().rposition(|l| !l.is_empty())?;
+    Some(block[first..=last].join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::read::outline::code::outline;
+    use crate::types::Lang;
+
+    #[test]
+    fn script_outline() {
+        let code = r#"library(dplyr)
+
+#' Summarise sales by region.
+#' @param df A data frame.
+summarise_sales <- function(df, by = "region") {
+  df %>%
+    group_by(.data[[by]]) %>%
+    summarise(total = sum(amount))
+}
+
+THRESHOLD = 100
+cutoff <- c(low = 1,
+            high = 10)
+
+scale01 <- \(x) (x - min(x)) / (max(x) - min(x))
+
+`%+%` <- function(a, b)
+{
+  paste(a, b)
+}
+
+if (interactive()) { print("hi") }
+result <- summarise_sales(read.csv("sales.csv"))
+"#;
+        let expected = "\
+[5-9]        fn summarise_sales
+           summarise_sales <- function(df, by = \"region\")  // Summarise sales by region.
+[11]         let THRESHOLD
+[12-13]      let cutoff
+[15]         fn scale01
+           scale01 <- \\(x)
+[17-20]      fn %+%
+           `%+%` <- function(a, b)
+[23]         let result";
+        assert_eq!(outline(code, Lang::R, usize::MAX), expected);
+    }
+}
@@ -0,0 +1,100 @@
+//! Grammars registered at runtime by downstream binaries. A registered language
+//! is `Lang::Registered(id)`; detection and outlining consult this table first.
+
+use std::sync::RwLock;
+
+use crate::types::Lang;
+
+struct Registered {
+    name: &'static str,
+    language: tree_sitter::Language,
+    extensions: Vec<String>,
+}
+
+static REGISTRY: RwLock<Vec<Registered>> = RwLock::new(Vec::new());
+
+/// Register a tree-sitter grammar for files with the given extensions (without
+/// the dot). Takes precedence over built-in detection for those extensions, so
+/// outlines, the map and search all use it. Registering the same name again
+/// replaces that grammar and its extensions.
+pub fn register_language(name: &str, language: tree_sitter::Language, extensions: &[&str]) {
+    let mut registry = REGISTRY
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let extensions = extensions
+        .iter()
+        .map(|e| e.trim_start_matches('.').to_string())
+        .collect();
+    if let Some(existing) = registry.iter_mut().find(|r| r.name == name) {
+        existing.language = language;
+        existing.extensions = extensions;
+        return;
+    }
+    // Names live for the process — registration happens a handful of times at startup.
+    let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+    registry.push(Registered {
+        name,
+        language,
+        extensions,
+    });
+}
+
+/// The registered language claiming `ext`, if any.
+pub(crate) fn lang_for_extension(ext: &str) -> Option<Lang> {
+    let registry = REGISTRY
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    registry
+        .iter()
+        .position(|r| r.extensions.iter().any(|e| e == ext))
+        .map(|id| Lang::Registered(id as u16))
+}
+
+pub(crate) fn language(id: u16) -> Option<tree_sitter::Language> {
+    let registry = REGISTRY
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    registry.get(usize::from(id)).map(|r| r.language.clone())
+}
+
+pub(crate) fn name(id: u16) -> &'static str {
+    let registry = REGISTRY
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    registry.get(usize::from(id)).map_or("unknown", |r| r.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FileType;
+    use std::path::Path;
+
+    #[test]
+    fn registered_grammar_outlines_its_extension() {
+        // A Rust grammar stands in for a downstream DSL under a made-up extension.
+        register_language("FakeDsl", tree_sitter_rust::LANGUAGE.into(), &[".fakedsl"]);
+
+        let path = Path::new("rules/main.fakedsl");
+        let FileType::Code(lang) = crate::lang::detect_file_type(path) else {
+            panic!("extension should be detected as code");
+        };
+        let Lang::Registered(id) = lang else {
+            panic!("expected a registered language, got {lang:?}");
+        };
+        assert_eq!(name(id), "FakeDsl");
+
+        let content = "pub fn evaluate(rule: &Rule) -> bool {\n    true\n}\n";
+        let outline = crate::read::outline::generate(
+            path,
+            FileType::Code(lang),
+            content,
+            content.as_bytes(),
+            false,
+        );
+        assert_eq!(
+            outline,
+            "[1-3]        fn evaluate\n           pub fn evaluate(rule: &Rule) -> bool"
+        );
+    }
+}
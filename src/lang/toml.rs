@@ -0,0 +1,314 @@
+//! TOML outline scanner. There is no TOML tree-sitter grammar in the build; a
+//! scanner that follows strings and brackets across lines is enough to find
+//! where each key/value pair ends. Every `[table]` and `[[array]]` header is a
+//! `Module` with its pairs as children; pairs above the first header are
+//! top-level properties.
+
+use crate::types::{OutlineEntry, OutlineKind};
+
+/// Longest pair signature before it is cut with `...`.
+const MAX_TEXT: usize = 80;
+
+/// Outline entries for a TOML file, in source order.
+pub fn outline_entries(content: &str) -> Vec<OutlineEntry> {
+    let lines: Vec<&str> = content.lines().collect();
+    let b = content.as_bytes();
+    let mut out: Vec<OutlineEntry> = Vec::new();
+    let mut in_table = false;
+    let mut line: u32 = 1;
+    let mut i = 0;
+
+    loop {
+        // Blank lines, indentation and comments between statements.
+        while i < b.len() {
+            match b[i] {
+                b'\n' => {
+                    line += 1;
+                    i += 1;
+                }
+                b'#' => {
+                    while i < b.len() && b[i] != b'\n' {
+                        i += 1;
+                    }
+                }
+                c if c.is_ascii_whitespace() => i += 1,
+                _ => break,
+            }
+        }
+        if i >= b.len() {
+            break;
+        }
+        let start = i;
+        let start_line = line;
+        let eol = content[i..].find('\n').map_or(b.len(), |p| i + p);
+
+        if b[i] == b'[' {
+            let (name, end) = header(&content[i..eol]);
+            out.push(OutlineEntry {
+                byte_range: start..start + end,
+                doc: doc_above(start_line, &lines),
+                ..OutlineEntry::new(OutlineKind::Module, name, start_line, start_line)
+            });
+            in_table = true;
+            i = eol;
+            continue;
+        }
+
+        let Some(eq) = key_end(b, i, eol) else {
+            // Not a pair; skip the line.
+            i = eol;
+            continue;
+        };
+        let mut comments = Vec::new();
+        let end = value_end(b, eq + 1, &mut line, &mut comments);
+        let key = content[start..eq].trim().to_string();
+        let mut text = String::new();
+        let mut from = start;
+        // Comments after the value's last byte are not part of it.
+        let inside = comments.iter().filter(|c| c.start < end);
+        for comment in inside.chain([&(end..end)]) {
+            text.push_str(&content[from..comment.start]);
+            text.push(' ');
+            from = comment.end;
+        }
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let signature = if text.len() > MAX_TEXT {
+            format!("{}...", crate::types::truncate_str(&text, MAX_TEXT - 3))
+        } else {
+            text
+        };
+        let entry = OutlineEntry {
+            byte_range: start..end,
+            signature: Some(signature),
+            doc: doc_above(start_line, &lines),
+            ..OutlineEntry::new(OutlineKind::Property, key, start_line, line)
+        };
+        match out.last_mut() {
+            Some(table) if in_table => {
+                table.end_line = entry.end_line;
+                table.byte_range.end = entry.byte_range.end;
+                table.children.push(entry);
+            }
+            _ => out.push(entry),
+        }
+        i = end;
+    }
+    out
+}
+
+/// The header's display name (`[dependencies]`, `[[bin]]`, inner spaces
+/// trimmed) and its length in `line`, without any trailing comment.
+fn header(line: &str) -> (String, usize) {
+    let (open, close) = if line.starts_with("[[") {
+        ("[[", "]]")
+    } else {
+        ("[", "]")
+    };
+    let rest = line.strip_prefix(open).unwrap_or(line);
+    // An unclosed header runs to the end of the line.
+    let (inner, end) = if let Some((inner, _)) = rest.split_once(close) {
+        (inner, open.len() + inner.len() + close.len())
+    } else {
+        let inner = rest.trim_end();
+        (inner, open.len() + inner.len())
+    };
+    let inner = inner.trim();
+    (format!("{open}{inner}{close}"), end)
+}
+
+/// Index of the `=` after a key starting at `i`, skipping quoted keys.
+fn key_end(b: &[u8], mut i: usize, eol: usize) -> Option<usize> {
+    while i < eol {
+        match b[i] {
+            b'=' => return Some(i),
+            q @ (b'"' | b'\'') => {
+                i += 1;
+                while i < eol && b[i] != q {
+                    if q == b'"' && b[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// End of the value starting at `i`: the last byte before the newline that
+/// closes it outside any string or bracket. Comment spans met on the way are
+/// collected into `comments`.
+fn value_end(
+    b: &[u8],
+    mut i: usize,
+    line: &mut u32,
+    comments: &mut Vec<std::ops::Range<usize>>,
+) -> usize {
+    let mut depth = 0usize;
+    let mut end = i;
+    while i < b.len() {
+        match b[i] {
+            b'\n' if depth == 0 => break,
+            b'\n' => {
+                *line += 1;
+                i += 1;
+                continue;
+            }
+            b'#' => {
+                let start = i;
+                while i < b.len() && b[i] != b'\n' {
+                    i += 1;
+                }
+                comments.push(start..i);
+                continue;
+            }
+            q @ (b'"' | b'\'') => {
+                i = string_end(b, i, q, line);
+                end = i;
+                continue;
+            }
+            b'[' | b'{' => depth += 1,
+            b']' | b'}' => depth = depth.saturating_sub(1),
+            c if c.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+        end = i;
+    }
+    end
+}
+
+/// Index just past the string opened by quote `q` at `i`: basic (`"`),
+/// literal (`'`), or their multi-line `"""` / `'''` forms.
+fn string_end(b: &[u8], mut i: usize, q: u8, line: &mut u32) -> usize {
+    let triple = [q; 3];
+    let multiline = b[i..].starts_with(&triple);
+    i += if multiline { 3 } else { 1 };
+    while i < b.len() {
+        if q == b'"' && b[i] == b'\\' {
+            i += 2;
+            continue;
+        }
+        if multiline {
+            if b[i..].starts_with(&triple) {
+                // Up to two quotes may sit right before the closing delimiter.
+                let mut end = i + 3;
+                while b.get(end) == Some(&q) && end < i + 5 {
+                    end += 1;
+                }
+                return end;
+            }
+            if b[i] == b'\n' {
+                *line += 1;
+            }
+        } else if b[i] == q || b[i] == b'\n' {
+            return i + usize::from(b[i] == q);
+        }
+        i += 1;
+    }
+    b.len()
+}
+
+/// `#` comment lines directly above a header or pair, markers stripped.
+fn doc_above(start_line: u32, lines: &[&str]) -> Option<String> {
+    let end = (start_line as usize).saturating_sub(1).min(lines.len());
+    let mut block: Vec<&str> = lines[..end]
+        .iter()
+        .rev()
+        .map(|l| l.trim())
+        .take_while(|l| l.starts_with('#'))
+        .map(|l| l.trim_start_matches('#').trim())
+        .collect();
+    block.reverse();
+    let first = block.iter().position(|l| !l.is_empty())?;
+    let last = block.iter().rposition(|l| !l.is_empty())?;
+    Some(block[first..=last].join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::read::outline::code::outline;
+    use crate::types::Lang;
+
+    #[test]
+    fn cargo_manifest_outline() {
+        let manifest = r#"# Workspace-level metadata.
+[package]
+name = "demo"
+version = "0.3.0"
+edition = "2021"
+description = """
+A demo crate with a [bracketed]
+multi-line description."""
+keywords = [
+    "cli", # trailing comment
+    "demo]",
+]
+
+[[bin]]
+name = "demo"
+path = "src/main.rs"
+
+[dependencies]
+serde = { version = "1", features = ["derive"] }
+regex = '1.10'
+
+[dependencies.tokio]
+version = "1"
+features = ["rt", "macros"]
+
+[ profile.release ]
+lto = true # smaller binaries
+"#;
+        let expected = r#"[2-12]       mod [package]  // Workspace-level metadata.
+  [3]          prop name
+             name = "demo"
+  [4]          prop version
+             version = "0.3.0"
+  [5]          prop edition
+             edition = "2021"
+  [6-8]        prop description
+             description = """ A demo crate with a [bracketed] multi-line description."""
+  [9-12]       prop keywords
+             keywords = [ "cli", "demo]", ]
+[14-16]      mod [[bin]]
+  [15]         prop name
+             name = "demo"
+  [16]         prop path
+             path = "src/main.rs"
+[18-20]      mod [dependencies]
+  [19]         prop serde
+             serde = { version = "1", features = ["derive"] }
+  [20]         prop regex
+             regex = '1.10'
+[22-24]      mod [dependencies.tokio]
+  [23]         prop version
+             version = "1"
+  [24]         prop features
+             features = ["rt", "macros"]
+[26-27]      mod [profile.release]
+  [27]         prop lto
+             lto = true"#;
+        assert_eq!(outline(manifest, Lang::Toml, usize::MAX), expected);
+    }
+
+    #[test]
+    fn unclosed_headers_do_not_split_characters() {
+        for (header, name) in [
+            ("[café", "[café]"),
+            ("[[bin]💥", "[[bin]💥]]"),
+            ("[\u{feff}", "[\u{feff}]"),
+        ] {
+            let out = outline(&format!("{header}\nkey = 1\n"), Lang::Toml, usize::MAX);
+            assert!(
+                out.starts_with(&format!("[1-2]        mod {name}")),
+                "got:\n{out}"
+            );
+        }
+    }
+}
@@ -1,11 +1,17 @@
 //! Shared tree-sitter utilities used by symbol search and caller search.
 
+use crate::types::OutlineKind;
+
 /// Definition node kinds across tree-sitter grammars.
 pub(crate) const DEFINITION_KINDS: &[&str] = &[
     // Functions
     "function_declaration",
     "function_definition",
     "function_item",
+    "function_signature_item",
+    // Go interface methods
+    "method_elem",
+    "method_spec",
     "method_definition",
     "method_declaration",
     // Classes, structs & Kotlin objects
@@ -30,6 +36,10 @@ pub(crate) const DEFINITION_KINDS: &[&str] = &[
     "property_declaration",
     // Rust-specific
     "trait_item",
+    "macro_definition",
+    // C/C++ preprocessor
+    "preproc_def",
+    "preproc_function_def",
     "impl_item",
     "mod_item",
     "namespace_definition",
@@ -75,6 +85,58 @@ pub(crate) fn extract_definition_name(node: tree_sitter::Node, lines: &[&str]) -
     None
 }
 
+/// Outline kind of a [`DEFINITION_KINDS`] node, matching what the outline
+/// shows for it. Wrappers (exports, decorators) take their declaration's kind.
+pub(crate) fn definition_kind(node: tree_sitter::Node) -> Option<OutlineKind> {
+    let kind = match node.kind() {
+        "function_declaration"
+        | "function_definition"
+        | "function_item"
+        | "function_signature_item"
+        | "method_elem"
+        | "method_spec"
+        | "method_definition"
+        | "method_declaration" => OutlineKind::Function,
+        "class_declaration" | "class_definition" => OutlineKind::Class,
+        "struct_item" => OutlineKind::Struct,
+        "interface_declaration" | "trait_declaration" | "trait_item" => OutlineKind::Interface,
+        "type_alias_declaration" | "type_item" => OutlineKind::TypeAlias,
+        "enum_item" | "enum_declaration" => OutlineKind::Enum,
+        "const_item" | "const_declaration" | "static_item" | "preproc_def" => OutlineKind::Constant,
+        "lexical_declaration" | "variable_declaration" => OutlineKind::Variable,
+        "property_declaration" => OutlineKind::Property,
+        "object_declaration" | "impl_item" | "mod_item" | "namespace_definition" => {
+            OutlineKind::Module
+        }
+        "macro_definition" | "preproc_function_def" => OutlineKind::Macro,
+        // Go: `type X struct {...}` / `type X interface {...}` / `type X Y`.
+        "type_declaration" => {
+            let mut cursor = node.walk();
+            let spec = node
+                .children(&mut cursor)
+                .find(|c| c.kind() == "type_spec")?;
+            match spec.child_by_field_name("type").map(|t| t.kind()) {
+                Some("struct_type") => OutlineKind::Struct,
+                Some("interface_type") => OutlineKind::Interface,
+                _ => OutlineKind::TypeAlias,
+            }
+        }
+        "decorated_definition" | "export_statement" => {
+            let mut cursor = node.walk();
+            let inner = node
+                .children(&mut cursor)
+                .find(|c| DEFINITION_KINDS.contains(&c.kind()));
+            return match inner {
+                Some(inner) => definition_kind(inner),
+                None if node.kind() == "export_statement" => Some(OutlineKind::Export),
+                None => None,
+            };
+        }
+        _ => return None,
+    };
+    Some(kind)
+}
+
 /// Get the text of a single-line node from pre-split source lines.
 ///
 /// Returns the text slice for single-line nodes, or the text from the start
@@ -136,6 +198,9 @@ pub(crate) fn definition_weight(kind: &str) -> u16 {
         "function_declaration"
         | "function_definition"
         | "function_item"
+        | "function_signature_item"
+        | "method_elem"
+        | "method_spec"
         | "method_definition"
         | "method_declaration"
         | "class_declaration"
@@ -144,13 +209,15 @@ pub(crate) fn definition_weight(kind: &str) -> u16 {
         | "interface_declaration"
         | "trait_declaration"
         | "trait_item"
+        | "macro_definition"
+        | "preproc_function_def"
         | "enum_item"
         | "enum_declaration"
         | "type_item"
         | "type_declaration"
         | "decorated_definition" => 100,
         "impl_item" | "object_declaration" => 90,
-        "const_item" | "const_declaration" | "static_item" => 80,
+        "const_item" | "const_declaration" | "static_item" | "preproc_def" => 80,
         "mod_item" | "namespace_definition" | "property_declaration" => 70,
         "lexical_declaration" | "variable_declaration" => 40,
         "export_statement" => 30,
@@ -0,0 +1,344 @@
+//! Vue single-file components. Each top-level block (`<template>`, `<script>`,
+//! `<style>`, custom blocks) is a `Module` section. `<script>` is outlined by
+//! the JavaScript/TypeScript path on a copy of the file with everything else
+//! blanked out, so lines and byte ranges stay those of the `.vue` file.
+//! `<template>` becomes a tag tree; `<style>` and custom blocks are a single
+//! entry.
+
+use crate::types::{Lang, OutlineEntry, OutlineKind};
+
+/// Longest tag text before it is cut with `...`.
+const MAX_TEXT: usize = 80;
+
+/// HTML elements that never have a closing tag.
+const VOID: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+struct Tag<'a> {
+    name: &'a str,
+    /// Byte offset of the `<`.
+    start: usize,
+    /// Byte offset just past the `>`.
+    end: usize,
+    closing: bool,
+    self_closing: bool,
+}
+
+/// Outline entries for a `.vue` file: one section per top-level block.
+pub fn outline_entries(content: &str) -> Vec<OutlineEntry> {
+    let starts: Vec<usize> = std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+    let line_at = |byte: usize| starts.partition_point(|&s| s <= byte) as u32;
+
+    let mut out = Vec::new();
+    let mut pos = 0;
+    while let Some(open) = next_tag(content, pos) {
+        if open.closing {
+            pos = open.end;
+            continue;
+        }
+        let (inner_end, end) = if open.self_closing {
+            (open.end, open.end)
+        } else {
+            block_end(content, &open)
+        };
+        let head = collapse(&content[open.start..open.end]);
+        let children = match open.name {
+            "template" => elements(content, open.end, inner_end, &line_at),
+            "script" => script_entries(content, open.end, inner_end, &head),
+            _ => Vec::new(),
+        };
+        out.push(OutlineEntry {
+            byte_range: open.start..end,
+            signature: (head != format!("<{}>", open.name)).then(|| cap(&head)),
+            children,
+            ..OutlineEntry::new(
+                OutlineKind::Module,
+                open.name,
+                line_at(open.start),
+                line_at(end.saturating_sub(1).max(open.start)),
+            )
+        });
+        pos = end;
+    }
+    out
+}
+
+/// End of the block's contents and of its closing tag. `<template>` may nest
+/// further `<template>` tags; other blocks are raw text up to their closer.
+fn block_end(content: &str, open: &Tag<'_>) -> (usize, usize) {
+    if open.name == "template" {
+        let mut depth = 1;
+        let mut pos = open.end;
+        while let Some(tag) = next_tag(content, pos) {
+            pos = tag.end;
+            if tag.name != "template" || tag.self_closing {
+                continue;
+            }
+            if tag.closing {
+                depth -= 1;
+                if depth == 0 {
+                    return (tag.start, tag.end);
+                }
+            } else {
+                depth += 1;
+            }
+        }
+        return (content.len(), content.len());
+    }
+    let closer = format!("</{}", open.name);
+    match content[open.end..].find(&closer) {
+        Some(p) => {
+            let start = open.end + p;
+            let end = content[start..]
+                .find('>')
+                .map_or(content.len(), |q| start + q + 1);
+            (start, end)
+        }
+        None => (content.len(), content.len()),
+    }
+}
+
+/// The next tag at or after `from`, skipping comments and stray `<`.
+fn next_tag(text: &str, mut from: usize) -> Option<Tag<'_>> {
+    let b = text.as_bytes();
+    loop {
+        let start = from + text[from..].find('<')?;
+        if text[start..].starts_with("<!--") {
+            from = text[start..]
+                .find("-->")
+                .map_or(text.len(), |p| start + p + 3);
+            continue;
+        }
+        let closing = b.get(start + 1) == Some(&b'/');
+        let name_start = start + 1 + usize::from(closing);
+        if !b.get(name_start).is_some_and(u8::is_ascii_alphabetic) {
+            from = start + 1;
+            continue;
+        }
+        let name_end = text[name_start..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.')))
+            .map_or(text.len(), |p| name_start + p);
+        // Attribute values may hold `>` (`v-if="a > b"`).
+        let mut i = name_end;
+        let mut quote = None;
+        while i < b.len() {
+            match (quote, b[i]) {
+                (None, q @ (b'"' | b'\'')) => quote = Some(q),
+                (Some(q), c) if c == q => quote = None,
+                (None, b'>') => break,
+                _ => {}
+            }
+            i += 1;
+        }
+        let end = (i + 1).min(text.len());
+        return Some(Tag {
+            name: &text[name_start..name_end],
+            start,
+            end,
+            closing,
+            self_closing: !closing && text[..i].ends_with('/'),
+        });
+    }
+}
+
+/// The element tree between `from` and `to`. Each element is a `Property`
+/// named by its opening tag; an unclosed element ends with its last child.
+fn elements(
+    content: &str,
+    from: usize,
+    to: usize,
+    line_at: &dyn Fn(usize) -> u32,
+) -> Vec<OutlineEntry> {
+    fn close(stack: &mut Vec<(&str, OutlineEntry)>, end: usize, line: u32) {
+        if let Some((_, mut done)) = stack.pop() {
+            done.byte_range.end = end;
+            done.end_line = line;
+            if let Some((_, parent)) = stack.last_mut() {
+                parent.children.push(done);
+            }
+        }
+    }
+
+    // The bottom of the stack collects the top-level elements.
+    let mut stack: Vec<(&str, OutlineEntry)> = vec![("", placeholder(from..to))];
+    let mut pos = from;
+    while let Some(tag) = next_tag(content, pos).filter(|t| t.start < to) {
+        pos = tag.end;
+        if tag.closing {
+            // Close up to the matching element; a stray closer is ignored.
+            if let Some(depth) = stack
+                .iter()
+                .rposition(|(n, _)| *n == tag.name)
+                .filter(|&d| d > 0)
+            {
+                while stack.len() > depth + 1 {
+                    let (end, line) = implicit_end(&stack);
+                    close(&mut stack, end, line);
+                }
+                close(&mut stack, tag.end, line_at(tag.end - 1));
+            }
+            continue;
+        }
+        let entry = OutlineEntry {
+            byte_range: tag.start..tag.end,
+            ..OutlineEntry::new(
+                OutlineKind::Property,
+                cap(&collapse(&content[tag.start..tag.end])),
+                line_at(tag.start),
+                line_at(tag.end - 1),
+            )
+        };
+        if tag.self_closing || VOID.contains(&tag.name.to_ascii_lowercase().as_str()) {
+            if let Some((_, parent)) = stack.last_mut() {
+                parent.children.push(entry);
+            }
+        } else {
+            stack.push((tag.name, entry));
+        }
+    }
+    while stack.len() > 1 {
+        let (end, line) = implicit_end(&stack);
+        close(&mut stack, end, line);
+    }
+    stack.pop().map(|(_, r)| r.children).unwrap_or_default()
+}
+
+/// Where the unclosed element on top of the stack ends: with its last child,
+/// or its own opening tag.
+fn implicit_end(stack: &[(&str, OutlineEntry)]) -> (usize, u32) {
+    let Some((_, open)) = stack.last() else {
+        return (0, 0);
+    };
+    let last = open.children.last().unwrap_or(open);
+    (last.byte_range.end, last.end_line)
+}
+
+fn placeholder(byte_range: std::ops::Range<usize>) -> OutlineEntry {
+    OutlineEntry {
+        byte_range,
+        ..OutlineEntry::new(OutlineKind::Module, "", 0, 0)
+    }
+}
+
+/// Outline of the script between `from` and `to`, in the language its
+/// `lang` attribute names: `ts` → TypeScript, `tsx` → TSX, else JavaScript.
+fn script_entries(content: &str, from: usize, to: usize, head: &str) -> Vec<OutlineEntry> {
+    let lang = match attribute(head, "lang") {
+        Some("ts") => Lang::TypeScript,
+        Some("tsx") => Lang::Tsx,
+        _ => Lang::JavaScript,
+    };
+    let mut masked = String::with_capacity(content.len());
+    for (i, c) in content.char_indices() {
+        if (from..to).contains(&i) || c == '\n' {
+            masked.push(c);
+        } else {
+            // Same byte length keeps the script's offsets.
+            masked.extend(std::iter::repeat_n(' ', c.len_utf8()));
+        }
+    }
+    let mut entries = super::outline::get_outline_entries(&masked, lang);
+    // Nested under the section, imports read as `import vue`.
+    for entry in &mut entries {
+        if entry.kind == OutlineKind::Import {
+            entry.name = super::outline::extract_import_source(&entry.name);
+        }
+    }
+    entries
+}
+
+/// Value of attribute `name` in an opening tag.
+fn attribute<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+    let at = head.find(&format!(" {name}="))? + name.len() + 2;
+    let rest = &head[at..];
+    match rest.chars().next()? {
+        q @ ('"' | '\'') => rest[1..].split(q).next(),
+        _ => rest.split([' ', '>', '/']).next(),
+    }
+}
+
+/// Whitespace runs folded to one space.
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn cap(text: &str) -> String {
+    if text.len() > MAX_TEXT {
+        format!("{}...", crate::types::truncate_str(text, MAX_TEXT - 3))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::read::outline::code::outline;
+    use crate::types::Lang;
+
+    #[test]
+    fn single_file_component_outline() {
+        let component = r#"<template>
+  <div class="counter" :class="{ big: count > 10 }">
+    <!-- <span>ignored</span> -->
+    <h1>{{ title }}</h1>
+    <button @click="increment">+</button>
+    <template v-if="count > 0">
+      <p>Count: {{ count }}</p>
+      <br>
+    </template>
+    <ChildItem
+      v-for="item in items"
+      :key="item.id"
+      :item="item"
+    />
+  </div>
+</template>
+
+<script setup lang="ts">
+import { ref, computed } from 'vue'
+import ChildItem from './ChildItem.vue'
+
+interface Item {
+  id: number
+}
+
+const props = defineProps<{ title: string; items: Item[] }>()
+const count = ref(0)
+
+function increment(): void {
+  count.value++
+}
+</script>
+
+<style scoped lang="scss">
+.counter {
+  h1 { color: red; }
+}
+</style>
+"#;
+        let expected = r#"[1-16]       mod template
+  [2-15]       prop <div class="counter" :class="{ big: count > 10 }">
+    [4]          prop <h1>
+    [5]          prop <button @click="increment">
+    [6-9]        prop <template v-if="count > 0">
+    [10-14]      prop <ChildItem v-for="item in items" :key="item.id" :item="item" />
+[18-32]      mod script
+           <script setup lang="ts">
+  [19]         import vue
+  [20]         import ./ChildItem.vue
+  [22-24]      interface Item
+    [23]         prop id
+               id: number
+  [26]         let props
+  [27]         let count
+  [29-31]      fn increment
+             function increment(): void
+[34-38]      mod style
+           <style scoped lang="scss">"#;
+        assert_eq!(outline(component, Lang::Vue, usize::MAX), expected);
+    }
+}
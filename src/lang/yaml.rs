@@ -0,0 +1,347 @@
+//! YAML outline scanner. There is no YAML tree-sitter grammar in the build;
+//! block YAML nests by indentation, so a line scanner finds each key and its
+//! extent. Top-level keys are `Module` (mapping values) or `Property` entries
+//! with one level of nested keys as children. A multi-document stream puts
+//! each `---` document at the top, named after its `kind` and
+//! `metadata.name` when it is a Kubernetes-style manifest.
+
+use crate::types::{OutlineEntry, OutlineKind};
+
+/// Longest signature before it is cut with `...`.
+const MAX_TEXT: usize = 80;
+
+struct Line<'a> {
+    text: &'a str,
+    /// Byte offset of the line start.
+    start: usize,
+}
+
+impl Line<'_> {
+    fn indent(&self) -> usize {
+        self.text.len() - self.text.trim_start().len()
+    }
+
+    /// Blank and comment-only lines carry no structure.
+    fn is_content(&self) -> bool {
+        let t = self.text.trim();
+        !t.is_empty() && !t.starts_with('#')
+    }
+}
+
+/// Outline entries for a YAML file.
+pub fn outline_entries(content: &str) -> Vec<OutlineEntry> {
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for raw in content.split_inclusive('\n') {
+        lines.push(Line {
+            text: raw.trim_end_matches(['\n', '\r']),
+            start: offset,
+        });
+        offset += raw.len();
+    }
+    let comments: Vec<&str> = lines.iter().map(|l| l.text).collect();
+    let docs = documents(&lines);
+
+    if docs.len() <= 1 {
+        return docs
+            .first()
+            .map(|&(lo, hi)| block(&lines, &comments, lo, hi, 0))
+            .unwrap_or_default();
+    }
+    docs.iter()
+        .enumerate()
+        .map(|(n, &(lo, hi))| {
+            let children = block(&lines, &comments, lo, hi, 0);
+            let name = manifest_name(&children).unwrap_or_else(|| format!("document {}", n + 1));
+            let (first, last) = content_bounds(&lines, lo, hi).unwrap_or((lo, lo));
+            let mut entry = entry(&lines, &comments, OutlineKind::Module, name, first, last);
+            // Comments above a document's first key document the key.
+            entry.doc = None;
+            entry.children = children;
+            entry
+        })
+        .collect()
+}
+
+/// Line ranges (`lo..hi`, 0-based) of the documents holding any content.
+fn documents(lines: &[Line<'_>]) -> Vec<(usize, usize)> {
+    let mut docs = Vec::new();
+    let mut lo = 0;
+    for (i, line) in lines.iter().enumerate() {
+        let marker = line.text == "---" || line.text.starts_with("--- ") || line.text == "...";
+        if marker {
+            docs.push((lo, i));
+            lo = i + 1;
+        }
+    }
+    docs.push((lo, lines.len()));
+    docs.retain(|&(lo, hi)| lines[lo..hi].iter().any(Line::is_content));
+    docs
+}
+
+/// First and last content lines in `lo..hi`.
+fn content_bounds(lines: &[Line<'_>], lo: usize, hi: usize) -> Option<(usize, usize)> {
+    let first = (lo..hi).find(|&i| lines[i].is_content())?;
+    let last = (lo..hi).rev().find(|&i| lines[i].is_content())?;
+    Some((first, last))
+}
+
+/// Entries for the mapping (or sequence) in `lo..hi`, at the indentation of
+/// its first content line. Children are read only at `depth` 0.
+fn block(
+    lines: &[Line<'_>],
+    comments: &[&str],
+    lo: usize,
+    hi: usize,
+    depth: usize,
+) -> Vec<OutlineEntry> {
+    let Some((first, _)) = content_bounds(lines, lo, hi) else {
+        return Vec::new();
+    };
+    let level = lines[first].indent();
+    let mut out = Vec::new();
+    let mut i = first;
+    while i < hi {
+        let line = &lines[i];
+        if !line.is_content() || line.indent() != level {
+            i += 1;
+            continue;
+        }
+        let text = line.text.trim();
+        // The item runs until the next content line at or left of its level;
+        // a key's sequence may sit at the key's own indentation.
+        let is_key = split_key(text).is_some();
+        let next = (i + 1..hi)
+            .find(|&k| {
+                let l = &lines[k];
+                l.is_content()
+                    && (l.indent() < level
+                        || (l.indent() == level && !(is_key && l.text.trim().starts_with('-'))))
+            })
+            .unwrap_or(hi);
+        let last = (i..next)
+            .rev()
+            .find(|&k| lines[k].is_content())
+            .unwrap_or(i);
+
+        if let Some((key, value)) = split_key(text) {
+            let nested = content_bounds(lines, i + 1, next)
+                .is_some_and(|(f, _)| split_key(lines[f].text.trim()).is_some());
+            let kind = if value.is_empty() && nested {
+                OutlineKind::Module
+            } else {
+                OutlineKind::Property
+            };
+            let mut e = entry(lines, comments, kind, key.to_string(), i, last);
+            if !value.is_empty() {
+                e.signature = Some(cap(&format!("{key}: {value}")));
+            }
+            if depth == 0 && kind == OutlineKind::Module {
+                e.children = block(lines, comments, i + 1, next, depth + 1);
+            }
+            out.push(e);
+        } else if depth == 0 && text.starts_with('-') {
+            // A top-level sequence: one entry per item, named by its first line.
+            let item = strip_comment(text);
+            out.push(entry(
+                lines,
+                comments,
+                OutlineKind::Property,
+                cap(item),
+                i,
+                last,
+            ));
+        }
+        i = next;
+    }
+    out
+}
+
+/// `key: value` → (`key`, `value`), with quotes around the key and any
+/// trailing comment dropped. A value that is only an anchor or tag
+/// (`key: &base`) opens a block, like an empty one.
+fn split_key(text: &str) -> Option<(&str, &str)> {
+    if text.starts_with(['-', '[', '{', '&', '*', '!', '|', '>', '?', '%']) {
+        return None;
+    }
+    let colon = if let Some(q @ ('"' | '\'')) = text.chars().next() {
+        text[1..]
+            .find(q)
+            .map(|p| p + 2)
+            .filter(|&p| text[p..].starts_with(':'))?
+    } else {
+        text.char_indices()
+            .find(|&(p, c)| {
+                c == ':' && text[p + 1..].chars().next().is_none_or(char::is_whitespace)
+            })?
+            .0
+    };
+    let key = text[..colon].trim().trim_matches(['"', '\'']);
+    let value = strip_comment(text[colon + 1..].trim());
+    let block_only = value
+        .split_whitespace()
+        .all(|w| w.starts_with('&') || w.starts_with('!'));
+    Some((key, if block_only { "" } else { value }))
+}
+
+/// Text before a ` #` comment outside quotes.
+fn strip_comment(text: &str) -> &str {
+    let mut quote = None;
+    let mut prev = ' ';
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '#') if prev.is_whitespace() => return text[..i].trim_end(),
+            _ => {}
+        }
+        prev = c;
+    }
+    text
+}
+
+/// `Deployment web` for a document with `kind: Deployment` and
+/// `metadata: name: web`.
+fn manifest_name(entries: &[OutlineEntry]) -> Option<String> {
+    let value = |e: &OutlineEntry| {
+        let sig = e.signature.as_deref()?;
+        let (_, v) = sig.split_once(": ")?;
+        Some(v.trim_matches(['"', '\'']).to_string())
+    };
+    let kind = entries.iter().find(|e| e.name == "kind").and_then(value)?;
+    let name = entries
+        .iter()
+        .find(|e| e.name == "metadata")
+        .and_then(|m| m.children.iter().find(|c| c.name == "name"))
+        .and_then(value);
+    Some(match name {
+        Some(name) => format!("{kind} {name}"),
+        None => kind,
+    })
+}
+
+fn cap(text: &str) -> String {
+    if text.len() > MAX_TEXT {
+        format!("{}...", crate::types::truncate_str(text, MAX_TEXT - 3))
+    } else {
+        text.to_string()
+    }
+}
+
+fn entry(
+    lines: &[Line<'_>],
+    comments: &[&str],
+    kind: OutlineKind,
+    name: String,
+    first: usize,
+    last: usize,
+) -> OutlineEntry {
+    let (a, b) = (&lines[first], &lines[last]);
+    OutlineEntry {
+        byte_range: a.start + a.indent()..b.start + b.text.trim_end().len(),
+        doc: doc_above(first as u32 + 1, comments),
+        ..OutlineEntry::new(kind, name, first as u32 + 1, last as u32 + 1)
+    }
+}
+
+/// `#` comment lines directly above a key, markers stripped.
+fn doc_above(start_line: u32, lines: &[&str]) -> Option<String> {
+    let end = (start_line as usize).saturating_sub(1).min(lines.len());
+    let mut block: Vec<&str> = lines[..end]
+        .iter()
+        .rev()
+        .map(|l| l.trim())
+        .take_while(|l| l.starts_with('#'))
+        .map(|l| l.trim_start_matches('#').trim())
+        .collect();
+    block.reverse();
+    let first = block.iter().position(|l| !l.is_empty())?;
+    let last = block.iter().rposition(|l| !l.is_empty())?;
+    Some(block[first..=last].join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::read::outline::code::outline;
+    use crate::types::Lang;
+
+    #[test]
+    fn multi_document_manifest_outline() {
+        let manifest = r#"# Web tier.
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: web
+  labels:
+    app: web
+spec:
+  replicas: 3 # scaled by HPA
+---
+apiVersion: v1
+kind: Service
+metadata:
+  name: "web-svc"
+spec:
+  ports:
+  - port: 80
+---
+- just
+- a list
+"#;
+        let expected = r#"[2-9]        mod Deployment web
+  [2]          prop apiVersion
+             apiVersion: apps/v1  // Web tier.
+  [3]          prop kind
+             kind: Deployment
+  [4-7]        mod metadata
+    [5]          prop name
+               name: web
+    [6-7]        mod labels
+  [8-9]        mod spec
+    [9]          prop replicas
+               replicas: 3
+[11-17]      mod Service web-svc
+  [11]         prop apiVersion
+             apiVersion: v1
+  [12]         prop kind
+             kind: Service
+  [13-14]      mod metadata
+    [14]         prop name
+               name: "web-svc"
+  [15-17]      mod spec
+    [16-17]      prop ports
+[19-20]      mod document 3
+  [19]         prop - just
+  [20]         prop - a list"#;
+        assert_eq!(outline(manifest, Lang::Yaml, usize::MAX), expected);
+    }
+
+    #[test]
+    fn workflow_outline() {
+        let workflow = "name: CI
+
+on:
+  push:
+    branches: [main]
+  pull_request:
+
+jobs:
+  check:
+    runs-on: ubuntu-latest
+    steps:
+    - uses: actions/checkout@v4
+    - run: cargo test
+  lint:
+    runs-on: ubuntu-latest
+";
+        let expected = "[1]          prop name
+           name: CI
+[3-6]        mod on
+  [4-5]        mod push
+  [6]          prop pull_request
+[8-15]       mod jobs
+  [9-13]       mod check
+  [14-15]      mod lint";
+        assert_eq!(outline(workflow, Lang::Yaml, usize::MAX), expected);
+    }
+}
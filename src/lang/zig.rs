@@ -0,0 +1,466 @@
+//! Zig outline scanner. There is no Zig tree-sitter grammar in the build; a
+//! tokenizer with bracket matching is enough, since every declaration ends at
+//! a `;`, a `,` or its block's closing brace. Types are `const` bindings to
+//! `struct`/`enum`/`union`/`opaque` expressions and are outlined with their
+//! fields, methods and nested declarations as children. `test` blocks are test
+//! cases and `@import` bindings feed the imports summary.
+
+use crate::types::{OutlineEntry, OutlineKind};
+
+struct Token<'a> {
+    text: &'a str,
+    start: usize,
+    end: usize,
+    line: u32,
+}
+
+/// Longest signature before it is cut with `...`.
+const MAX_SIGNATURE: usize = 120;
+
+/// Outline entries for a Zig file. A file is itself a struct, so its
+/// declarations are read like any container body.
+pub fn outline_entries(content: &str) -> Vec<OutlineEntry> {
+    let lines: Vec<&str> = content.lines().collect();
+    let toks = tokens(content);
+    let scan = Scanner {
+        content,
+        toks: &toks,
+        lines: &lines,
+    };
+    scan.container(0, toks.len())
+}
+
+struct Scanner<'a> {
+    content: &'a str,
+    toks: &'a [Token<'a>],
+    lines: &'a [&'a str],
+}
+
+impl Scanner<'_> {
+    fn text(&self, i: usize) -> &str {
+        self.toks.get(i).map_or("", |t| t.text)
+    }
+
+    /// Declarations between tokens `i` and `end` (exclusive).
+    fn container(&self, mut i: usize, end: usize) -> Vec<OutlineEntry> {
+        let mut out = Vec::new();
+        while i < end {
+            let start = i;
+            while matches!(
+                self.text(i),
+                "pub" | "export" | "extern" | "inline" | "noinline" | "threadlocal"
+            ) {
+                i += 1;
+                // `extern "c" fn ...`
+                if self.text(i).starts_with('"') {
+                    i += 1;
+                }
+            }
+            let next = match self.text(i) {
+                "fn" => self.function(start, i, end, &mut out),
+                "const" | "var" => self.binding(start, i, end, &mut out),
+                "test" => self.test(start, i, end, &mut out),
+                word if is_ident(word) && self.text(i + 1) == ":" => {
+                    let next = self.decl_end(i, end);
+                    out.push(self.entry(
+                        OutlineKind::Property,
+                        word.to_string(),
+                        start,
+                        next - 1,
+                        None,
+                    ));
+                    next
+                }
+                _ => self.decl_end(i, end),
+            };
+            i = next.max(start + 1);
+        }
+        out
+    }
+
+    /// `fn name(params) Ret { ... }` or an extern prototype ending in `;`.
+    fn function(&self, start: usize, i: usize, end: usize, out: &mut Vec<OutlineEntry>) -> usize {
+        let name = self.text(i + 1);
+        if !is_ident(name) || self.text(i + 2) != "(" {
+            return self.decl_end(i, end);
+        }
+        let Some(mut k) = self.matching(i + 2).map(|close| close + 1) else {
+            return end;
+        };
+        // Walk the return type: brackets in it (`error{Oops}!void`,
+        // `[]const u8`) are skipped until the body or a `;`.
+        while k < end {
+            match self.text(k) {
+                ";" => break,
+                "{" if !self.opens_type(k) => break,
+                "(" | "[" | "{" => k = self.matching(k).map_or(end, |c| c + 1),
+                _ => k += 1,
+            }
+        }
+        let last = if self.text(k) == "{" {
+            self.matching(k).unwrap_or(end - 1)
+        } else {
+            k.min(end - 1)
+        };
+        let signature = self.signature(start, k);
+        out.push(self.entry(
+            OutlineKind::Function,
+            name.to_string(),
+            start,
+            last,
+            Some(signature),
+        ));
+        last + 1
+    }
+
+    /// `const`/`var` bindings: imports, containers, error sets and plain values.
+    fn binding(&self, start: usize, i: usize, end: usize, out: &mut Vec<OutlineEntry>) -> usize {
+        let name = self.text(i + 1);
+        let next = self.decl_end(i, end);
+        if !is_ident(name) {
+            return next;
+        }
+        let Some(eq) = (i + 2..next).find(|&k| self.text(k) == "=") else {
+            out.push(self.binding_entry(start, i, name, next));
+            return next;
+        };
+        let mut v = eq + 1;
+        while matches!(self.text(v), "extern" | "packed") {
+            v += 1;
+        }
+        match self.text(v) {
+            "@import" => {
+                let text = self.signature(start, next - 1);
+                out.push(self.entry(OutlineKind::Import, text, start, next - 1, None));
+            }
+            kw @ ("struct" | "enum" | "union" | "opaque" | "error") => {
+                let mut open = v + 1;
+                if self.text(open) == "(" {
+                    open = self.matching(open).map_or(next, |c| c + 1);
+                }
+                if self.text(open) != "{" {
+                    out.push(self.binding_entry(start, i, name, next));
+                    return next;
+                }
+                let close = self.matching(open).unwrap_or(end - 1);
+                let (kind, children) = match kw {
+                    "error" => (OutlineKind::Enum, Vec::new()),
+                    // Variants are not outlined; methods and nested types are.
+                    "enum" => (OutlineKind::Enum, self.container(open + 1, close)),
+                    _ => (OutlineKind::Struct, self.container(open + 1, close)),
+                };
+                let signature = self.signature(start, open);
+                let mut entry =
+                    self.entry(kind, name.to_string(), start, next - 1, Some(signature));
+                entry.children = children;
+                out.push(entry);
+            }
+            _ => out.push(self.binding_entry(start, i, name, next)),
+        }
+        next
+    }
+
+    fn binding_entry(&self, start: usize, i: usize, name: &str, next: usize) -> OutlineEntry {
+        let kind = if self.text(i) == "var" {
+            OutlineKind::Variable
+        } else {
+            OutlineKind::Constant
+        };
+        self.entry(kind, name.to_string(), start, next - 1, None)
+    }
+
+    /// `test "name" { ... }`, `test ident { ... }` or an anonymous `test { ... }`.
+    fn test(&self, start: usize, i: usize, end: usize, out: &mut Vec<OutlineEntry>) -> usize {
+        let (name, open) = match self.text(i + 1) {
+            "{" => ("test".to_string(), i + 1),
+            s => (s.trim_matches('"').to_string(), i + 2),
+        };
+        if self.text(open) != "{" {
+            return self.decl_end(i, end);
+        }
+        let close = self.matching(open).unwrap_or(end - 1);
+        out.push(self.entry(OutlineKind::TestCase, name, start, close, None));
+        close + 1
+    }
+
+    /// Index just past the `;` or `,` ending the declaration at `i`, or past
+    /// the closing brace of a block declaration (`comptime { ... }`).
+    fn decl_end(&self, mut i: usize, end: usize) -> usize {
+        while i < end {
+            match self.text(i) {
+                ";" | "," => return i + 1,
+                "{" if i > 0 && self.text(i - 1) == "comptime" => {
+                    return self.matching(i).map_or(end, |c| c + 1);
+                }
+                "(" | "[" | "{" => i = self.matching(i).map_or(end, |c| c + 1),
+                _ => i += 1,
+            }
+        }
+        end
+    }
+
+    /// True when the `{` at `k` opens a type body rather than a function body.
+    fn opens_type(&self, k: usize) -> bool {
+        match self.text(k - 1) {
+            "struct" | "enum" | "union" | "opaque" | "error" => true,
+            // `enum(u8) {`, `union(enum) {`
+            ")" => {
+                let mut depth = 0usize;
+                let open = (0..k - 1).rev().find(|&o| match self.text(o) {
+                    ")" => {
+                        depth += 1;
+                        false
+                    }
+                    "(" if depth == 0 => true,
+                    "(" => {
+                        depth -= 1;
+                        false
+                    }
+                    _ => false,
+                });
+                open.is_some_and(|o| o > 0 && matches!(self.text(o - 1), "enum" | "union"))
+            }
+            _ => false,
+        }
+    }
+
+    /// Index of the bracket closing the one at `open`.
+    fn matching(&self, open: usize) -> Option<usize> {
+        let mut depth = 0usize;
+        for (i, t) in self.toks.iter().enumerate().skip(open) {
+            match t.text {
+                "(" | "[" | "{" => depth += 1,
+                ")" | "]" | "}" => {
+                    depth = depth.checked_sub(1)?;
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Whitespace-collapsed source from token `start` up to (not including) `stop`.
+    fn signature(&self, start: usize, stop: usize) -> String {
+        let from = self.toks[start].start;
+        let to = self.toks[stop.max(start + 1) - 1].end;
+        let sig = self.content[from..to]
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if sig.len() > MAX_SIGNATURE {
+            format!("{}...", crate::types::truncate_str(&sig, MAX_SIGNATURE - 3))
+        } else {
+            sig
+        }
+    }
+
+    fn entry(
+        &self,
+        kind: OutlineKind,
+        name: String,
+        first: usize,
+        last: usize,
+        signature: Option<String>,
+    ) -> OutlineEntry {
+        let (first, last) = (&self.toks[first], &self.toks[last.max(first)]);
+        OutlineEntry {
+            byte_range: first.start..last.end,
+            signature,
+            doc: doc_above(first.line, self.lines),
+            ..OutlineEntry::new(kind, name, first.line, last.line)
+        }
+    }
+}
+
+fn is_ident(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && !matches!(
+            s,
+            "fn" | "const" | "var" | "pub" | "test" | "comptime" | "usingnamespace" | "return"
+        )
+}
+
+/// Identifier bytes; any non-ASCII byte counts, so a token never ends inside
+/// a multi-byte character.
+fn is_ident_byte(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || !c.is_ascii()
+}
+
+/// Words, builtins (`@import`), string and character literals, and single
+/// punctuation. Comments are skipped; a `\\` multiline string line becomes one
+/// string token.
+fn tokens(content: &str) -> Vec<Token<'_>> {
+    let b = content.as_bytes();
+    let mut out = Vec::new();
+    let mut line: u32 = 1;
+    let mut i = 0;
+    while i < b.len() {
+        let c = b[i];
+        let start = i;
+        match c {
+            b'\n' => {
+                line += 1;
+                i += 1;
+                continue;
+            }
+            c if c.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'/' if b.get(i + 1) == Some(&b'/') => {
+                while i < b.len() && b[i] != b'\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            b'\\' if b.get(i + 1) == Some(&b'\\') => {
+                while i < b.len() && b[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'"' | b'\'' => {
+                i += 1;
+                while i < b.len() && b[i] != c && b[i] != b'\n' {
+                    if b[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                i = (i + 1).min(b.len());
+            }
+            // `@"quoted identifier"`
+            b'@' if b.get(i + 1) == Some(&b'"') => {
+                i += 2;
+                while i < b.len() && b[i] != b'"' && b[i] != b'\n' {
+                    i += 1;
+                }
+                i = (i + 1).min(b.len());
+            }
+            c if is_ident_byte(c) || c == b'@' => {
+                i += 1;
+                while i < b.len() && is_ident_byte(b[i]) {
+                    i += 1;
+                }
+            }
+            _ => i += 1,
+        }
+        out.push(Token {
+            text: &content[start..i],
+            start,
+            end: i,
+            line,
+        });
+    }
+    out
+}
+
+/// `///` doc comment lines directly above a declaration, markers stripped.
+fn doc_above(start_line: u32, lines: &[&str]) -> Option<String> {
+    let end = (start_line as usize).saturating_sub(1).min(lines.len());
+    let mut block: Vec<&str> = lines[..end]
+        .iter()
+        .rev()
+        .map(|l| l.trim())
+        .take_while(|l| l.starts_with("///") && !l.starts_with("////"))
+        .map(|l| l.trim_start_matches('/').trim())
+        .collect();
+    block.reverse();
+    let first = block.iter().position(|l| !l.is_empty())?;
+    let last = block.iter().rposition(|l| !l.is_empty())?;
+    Some(block[first..=last].join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::read::outline::code::outline;
+    use crate::types::Lang;
+
+    #[test]
+    fn container_outline() {
+        let code = r#"//! A tiny growable list.
+const std = @import("std");
+const mem = @import("std").mem;
+
+pub const max_len: usize = 1 << 16;
+
+/// Errors the list can return.
+pub const Error = error{ OutOfMemory, TooLong };
+
+pub const Color = enum(u8) {
+    red,
+    green = 2,
+
+    pub fn isWarm(self: Color) bool {
+        return self == .red;
+    }
+};
+
+/// A list of bytes.
+pub const List = struct {
+    items: []u8 = &.{},
+    on_grow: ?*const fn (usize) void = null,
+
+    const Self = @This();
+
+    /// Create an empty list.
+    pub fn init() Self {
+        return .{};
+    }
+
+    fn grow(self: *Self) error{OutOfMemory}!void {
+        const s = "}{";
+        _ = s;
+    }
+};
+
+extern "c" fn write(fd: c_int, buf: [*]const u8, n: usize) isize;
+
+comptime {
+    std.debug.assert(max_len > 0);
+}
+
+test "append grows the list" {
+    const text =
+        \\ multi { line
+    ;
+    _ = text;
+}
+"#;
+        let expected = "\
+[2-]   imports: std(2)
+[5]          const max_len
+[8]          enum Error
+           pub const Error = error  // Errors the list can return.
+[10-17]      enum Color
+           pub const Color = enum(u8)
+  [14-16]      fn isWarm
+             pub fn isWarm(self: Color) bool
+[20-35]      struct List
+           pub const List = struct  // A list of bytes.
+  [21]         prop items
+  [22]         prop on_grow
+  [24]         const Self
+  [27-29]      fn init
+             pub fn init() Self  // Create an empty list.
+  [31-34]      fn grow
+             fn grow(self: *Self) error{OutOfMemory}!void
+[37]         fn write
+           extern \"c\" fn write(fd: c_int, buf: [*]const u8, n: usize) isize
+[43-48]      test append grows the list";
+        assert_eq!(outline(code, Lang::Zig, usize::MAX), expected);
+    }
+
+    #[test]
+    fn non_ascii_names_outline() {
+        // Non-ASCII identifiers and text must not be split mid-character.
+        let code = "const café = 1;\nconst π = 3;\n\npub fn naïve() void {}\n";
+        let expected = "[1]          const café
+[2]          const π
+[4]          fn naïve
+           pub fn naïve() void";
+        assert_eq!(outline(code, Lang::Zig, usize::MAX), expected);
+    }
+}
@@ -13,9 +13,12 @@
     clippy::missing_panics_doc,        // same
 )]
 
+#[cfg(feature = "archive")]
+pub mod archive;
 pub(crate) mod budget;
 pub mod cache;
 pub(crate) mod classify;
+pub mod config;
 pub mod diff;
 pub(crate) mod edit;
 pub mod error;
@@ -38,6 +41,27 @@ use classify::classify;
 use error::TilthError;
 use types::QueryType;
 
+pub use lang::detection::{is_test_path, TEST_DIRS, TEST_FILE_INFIXES, TEST_FILE_SUFFIXES};
+pub use lang::registry::register_language;
+pub use read::outline::code::outline_entries;
+pub use read::outline::{outline_named, FormatVersion, LineRanges, OutlineOptions, OutlineStyle};
+pub use search::{KindFilter, MatchRole, SearchOptions, DEFAULT_PAGE_SIZE};
+pub use types::{Lang, OutlineEntry, OutlineKind};
+
+/// Per-call settings that don't warrant their own `run*` variant.
+/// `Default` reproduces plain `run`.
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Outline rendering for file-path queries.
+    pub outline: OutlineOptions,
+    /// Append outlines of locally-imported files after a file-path read.
+    pub inline_imports: bool,
+    /// Return only this symbol's declaration card from a file-path read.
+    pub card: Option<String>,
+    /// Paging and filters for search queries.
+    pub search: search::SearchOptions,
+}
+
 /// Holds expanded search dependencies, allocated once.
 /// Avoids scattered `Option<T>` + `unwrap()` throughout dispatch.
 struct ExpandedCtx {
@@ -57,7 +81,17 @@ pub fn run(
     glob: Option<&str>,
     cache: &OutlineCache,
 ) -> Result<String, TilthError> {
-    run_inner(query, scope, section, budget_tokens, false, 0, glob, cache)
+    run_inner(
+        query,
+        scope,
+        section,
+        budget_tokens,
+        false,
+        0,
+        glob,
+        &RunOptions::default(),
+        cache,
+    )
 }
 
 /// Full variant — forces full file output, bypassing smart views.
@@ -69,7 +103,17 @@ pub fn run_full(
     glob: Option<&str>,
     cache: &OutlineCache,
 ) -> Result<String, TilthError> {
-    run_inner(query, scope, section, budget_tokens, true, 0, glob, cache)
+    run_inner(
+        query,
+        scope,
+        section,
+        budget_tokens,
+        true,
+        0,
+        glob,
+        &RunOptions::default(),
+        cache,
+    )
 }
 
 /// Run with expanded search — inline source for top N matches.
@@ -91,10 +135,91 @@ pub fn run_expanded(
         full,
         expand,
         glob,
+        &RunOptions::default(),
         cache,
     )
 }
 
+/// Most general variant — `run_expanded` plus [`RunOptions`].
+pub fn run_with_options(
+    query: &str,
+    scope: &Path,
+    section: Option<&str>,
+    budget_tokens: Option<u64>,
+    full: bool,
+    expand: usize,
+    glob: Option<&str>,
+    options: &RunOptions,
+    cache: &OutlineCache,
+) -> Result<String, TilthError> {
+    run_inner(
+        query,
+        scope,
+        section,
+        budget_tokens,
+        full,
+        expand,
+        glob,
+        options,
+        cache,
+    )
+}
+
+/// [`run`] for tools that parse the result: a JSON string instead of text.
+/// A file path gives its outline entry tree, a glob the matching files, and
+/// any other query the ranked search matches: symbol definitions and usages,
+/// falling back to content matches when there are none. Paging and kind
+/// filters come from `opts`.
+pub fn run_json(
+    query: &str,
+    scope: &Path,
+    glob: Option<&str>,
+    opts: &SearchOptions,
+) -> Result<String, TilthError> {
+    let scope = &*lang::expand_home(scope);
+    require_search_scope(scope)?;
+    let value = match classify_with(query, scope, opts) {
+        QueryType::FilePath(path) => {
+            let content = std::fs::read(&path).map_err(|e| TilthError::IoError {
+                path: path.clone(),
+                source: e,
+            })?;
+            let content = String::from_utf8_lossy(&content);
+            let lang = match lang::detect_file_type(&path) {
+                types::FileType::Code(lang) => Some(lang),
+                _ => None,
+            };
+            let entries = lang
+                .map(|l| outline_entries(&content, l))
+                .unwrap_or_default();
+            format::outline_json(&path, scope, lang, &entries)
+        }
+        QueryType::Glob(pattern) => {
+            format::glob_json(&search::glob::search(&pattern, scope)?, scope)
+        }
+        QueryType::Regex(pattern) => format::search_json(
+            &search::search_regex_raw(&pattern, scope, glob, opts)?,
+            scope,
+        ),
+        QueryType::Content(text) => format::search_json(
+            &search::search_content_raw(&text, scope, glob, opts)?,
+            scope,
+        ),
+        QueryType::Concept(text) if text.contains(' ') => format::search_json(
+            &search::search_content_raw(&text, scope, glob, opts)?,
+            scope,
+        ),
+        QueryType::Symbol(text) | QueryType::Concept(text) | QueryType::Fallthrough(text) => {
+            let mut result = search::search_symbol_raw(&text, scope, glob, opts)?;
+            if result.total_found == 0 && !opts.regex {
+                result = search::search_content_raw(&text, scope, glob, opts)?;
+            }
+            format::search_json(&result, scope)
+        }
+    };
+    Ok(value.to_string())
+}
+
 /// Find all callers of a symbol.
 pub fn run_callers(
     target: &str,
@@ -104,6 +229,8 @@ pub fn run_callers(
     glob: Option<&str>,
     cache: &OutlineCache,
 ) -> Result<String, TilthError> {
+    let scope = &*lang::expand_home(scope);
+    error::require_dir(scope)?;
     let session = session::Session::new();
     let bloom = index::bloom::BloomFilterCache::new();
     let expand = if expand > 0 { expand } else { 2 };
@@ -123,6 +250,10 @@ pub fn run_deps(
     budget_tokens: Option<u64>,
     cache: &OutlineCache,
 ) -> Result<String, TilthError> {
+    let scope = &*lang::expand_home(scope);
+    let path = &*lang::expand_home(path);
+    error::require_dir(scope)?;
+    error::require_file(path)?;
     let bloom = index::bloom::BloomFilterCache::new();
     let result = search::deps::analyze_deps(path, scope, cache, &bloom)?;
     let budget_usize = budget_tokens.map(|b| b as usize);
@@ -137,9 +268,13 @@ fn run_inner(
     full: bool,
     expand: usize,
     glob: Option<&str>,
+    options: &RunOptions,
     cache: &OutlineCache,
 ) -> Result<String, TilthError> {
-    let query_type = classify(query, scope);
+    let scope = &*lang::expand_home(scope);
+    require_search_scope(scope)?;
+    let opts = &options.search;
+    let query_type = classify_with(query, scope, opts);
 
     let use_expanded =
         expand > 0 && !matches!(query_type, QueryType::FilePath(_) | QueryType::Glob(_));
@@ -148,6 +283,7 @@ fn run_inner(
     // Check before main dispatch. Only activate when all parts look like identifiers
     // to avoid hijacking regex (/foo,bar/) or glob (*.{rs,ts}) queries.
     if query.contains(',')
+        && !opts.regex
         && !matches!(
             query_type,
             QueryType::Regex(_) | QueryType::Glob(_) | QueryType::FilePath(_)
@@ -171,7 +307,7 @@ fn run_inner(
             let bloom = index::bloom::BloomFilterCache::new();
             let expand = if expand > 0 { expand } else { 2 };
             let output = search::search_multi_symbol_expanded(
-                &parts, scope, cache, &session, &sym_index, &bloom, expand, None, glob,
+                &parts, scope, cache, &session, &sym_index, &bloom, expand, None, glob, opts,
             )?;
             return match budget_tokens {
                 Some(b) => Ok(budget::apply(&output, b)),
@@ -182,9 +318,18 @@ fn run_inner(
 
     // FilePath and Glob are read operations, not search — handle before expanded dispatch
     let output = match query_type {
+        QueryType::FilePath(path) if options.card.is_some() => {
+            read::card::card(&path, options.card.as_deref().unwrap_or_default())?
+        }
         QueryType::FilePath(path) => {
-            let mut out = read::read_file(&path, section, full, cache, false)?;
-            if section.is_none() && !full && read::would_outline(&path) {
+            let mut out = read::read_file(&path, section, full, cache, false, &options.outline)?;
+            if section.is_none() && options.inline_imports {
+                out.push_str(&read::imports::inline_imports(&path, cache));
+            } else if section.is_none()
+                && !full
+                && options.outline.is_default()
+                && read::would_outline(&path)
+            {
                 let related = read::imports::resolve_related_files(&path);
                 if !related.is_empty() {
                     let hints: Vec<String> = related
@@ -206,9 +351,9 @@ fn run_inner(
                 bloom: index::bloom::BloomFilterCache::new(),
                 expand,
             };
-            run_query_expanded(&query_type, scope, cache, &ctx, glob)?
+            run_query_expanded(&query_type, scope, cache, &ctx, glob, opts)?
         }
-        _ => run_query_basic(&query_type, scope, cache, glob)?,
+        _ => run_query_basic(&query_type, scope, cache, glob, opts)?,
     };
 
     match budget_tokens {
@@ -217,6 +362,26 @@ fn run_inner(
     }
 }
 
+/// Fail unless `scope` is a directory or, with the `archive` feature, a source
+/// archive, whose entries symbol, content and regex searches read in memory.
+fn require_search_scope(scope: &Path) -> Result<(), TilthError> {
+    #[cfg(feature = "archive")]
+    if archive::is_archive(scope) {
+        return Ok(());
+    }
+    error::require_dir(scope)
+}
+
+/// [`classify`], except that a regex symbol search ([`SearchOptions::regex`])
+/// always takes the query as a name pattern.
+fn classify_with(query: &str, scope: &Path, opts: &SearchOptions) -> QueryType {
+    if opts.regex {
+        QueryType::Symbol(query.into())
+    } else {
+        classify(query, scope)
+    }
+}
+
 /// Dispatch search queries in expanded mode (inline source for top N matches).
 /// Only called for search query types — FilePath/Glob are handled before this.
 fn run_query_expanded(
@@ -225,6 +390,7 @@ fn run_query_expanded(
     cache: &OutlineCache,
     ctx: &ExpandedCtx,
     glob: Option<&str>,
+    opts: &search::SearchOptions,
 ) -> Result<String, TilthError> {
     match query_type {
         QueryType::Symbol(name) => search::search_symbol_expanded(
@@ -237,6 +403,7 @@ fn run_query_expanded(
             ctx.expand,
             None,
             glob,
+            opts,
         ),
         QueryType::Concept(text) if text.contains(' ') => search::search_content_expanded(
             text,
@@ -246,6 +413,7 @@ fn run_query_expanded(
             ctx.expand,
             None,
             glob,
+            opts,
         ),
         // Single-word Concept and Fallthrough share the same expanded path:
         // both go straight to symbol_expanded, intentionally bypassing the
@@ -261,6 +429,7 @@ fn run_query_expanded(
             ctx.expand,
             None,
             glob,
+            opts,
         ),
         QueryType::Content(text) => search::search_content_expanded(
             text,
@@ -270,6 +439,7 @@ fn run_query_expanded(
             ctx.expand,
             None,
             glob,
+            opts,
         ),
         QueryType::Regex(pattern) => search::search_regex_expanded(
             pattern,
@@ -279,6 +449,7 @@ fn run_query_expanded(
             ctx.expand,
             None,
             glob,
+            opts,
         ),
         // FilePath/Glob never reach here (gated by use_expanded)
         QueryType::FilePath(_) | QueryType::Glob(_) => {
@@ -294,21 +465,22 @@ fn run_query_basic(
     scope: &Path,
     cache: &OutlineCache,
     glob: Option<&str>,
+    opts: &search::SearchOptions,
 ) -> Result<String, TilthError> {
     match query_type {
-        QueryType::Symbol(name) => search::search_symbol(name, scope, cache, glob),
+        QueryType::Symbol(name) => search::search_symbol(name, scope, cache, glob, opts),
         QueryType::Concept(text) if text.contains(' ') => {
-            multi_word_concept_search(text, scope, cache, glob)
+            multi_word_concept_search(text, scope, cache, glob, opts)
         }
         QueryType::Concept(text) => {
             // Single-word concept: prefer definitions, then content, then any match.
-            single_query_search(text, scope, cache, true, glob)
+            single_query_search(text, scope, cache, true, glob, opts)
         }
-        QueryType::Content(text) => search::search_content(text, scope, cache, glob),
-        QueryType::Regex(pattern) => search::search_regex(pattern, scope, cache, glob),
+        QueryType::Content(text) => search::search_content(text, scope, cache, glob, opts),
+        QueryType::Regex(pattern) => search::search_regex(pattern, scope, cache, glob, opts),
         QueryType::Fallthrough(text) => {
             // Accept any symbol match immediately (no definitions preference).
-            single_query_search(text, scope, cache, false, glob)
+            single_query_search(text, scope, cache, false, glob, opts)
         }
         // FilePath/Glob never reach here
         QueryType::FilePath(_) | QueryType::Glob(_) => {
@@ -328,8 +500,9 @@ fn single_query_search(
     cache: &cache::OutlineCache,
     prefer_definitions: bool,
     glob: Option<&str>,
+    opts: &search::SearchOptions,
 ) -> Result<String, error::TilthError> {
-    let sym_result = search::search_symbol_raw(text, scope, glob)?;
+    let sym_result = search::search_symbol_raw(text, scope, glob, opts)?;
     let accept_sym = if prefer_definitions {
         sym_result.definitions > 0
     } else {
@@ -340,7 +513,7 @@ fn single_query_search(
         return search::format_raw_result(&sym_result, cache);
     }
 
-    let content_result = search::search_content_raw(text, scope, glob)?;
+    let content_result = search::search_content_raw(text, scope, glob, opts)?;
     if content_result.total_found > 0 {
         return search::format_raw_result(&content_result, cache);
     }
@@ -362,9 +535,10 @@ fn multi_word_concept_search(
     scope: &Path,
     cache: &cache::OutlineCache,
     glob: Option<&str>,
+    opts: &search::SearchOptions,
 ) -> Result<String, error::TilthError> {
     // Try exact phrase match first
-    let mut content_result = search::search_content_raw(text, scope, glob)?;
+    let mut content_result = search::search_content_raw(text, scope, glob, opts)?;
     content_result.query = text.to_string();
     if content_result.total_found > 0 {
         return search::format_raw_result(&content_result, cache);
@@ -389,7 +563,7 @@ fn multi_word_concept_search(
             .join("|")
     };
 
-    let mut relaxed_result = search::search_regex_raw(&relaxed, scope, glob)?;
+    let mut relaxed_result = search::search_regex_raw(&relaxed, scope, glob, opts)?;
     relaxed_result.query = text.to_string();
     if relaxed_result.total_found > 0 {
         return search::format_raw_result(&relaxed_result, cache);
@@ -401,3 +575,72 @@ fn multi_word_concept_search(
         suggestion: read::suggest_similar_file(scope, first_word),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    #[test]
+    fn run_json_covers_reads_and_searches() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("area.rs");
+        std::fs::write(
+            &file,
+            "/// Square area.\npub fn area(side: f64) -> f64 {\n    side * side\n}\n\nfn total() -> f64 {\n    area(2.0)\n}\n",
+        )
+        .unwrap();
+        let opts = SearchOptions::default();
+        let parse = |s: String| serde_json::from_str::<Value>(&s).unwrap();
+
+        let read = run_json(file.to_str().unwrap(), tmp.path(), None, &opts).unwrap();
+        let function = |name: &str, lines: (u32, u32), bytes: (usize, usize), sig: &str| {
+            json!({
+                "kind": "function",
+                "name": name,
+                "start_line": lines.0,
+                "end_line": lines.1,
+                "byte_range": {"start": bytes.0, "end": bytes.1},
+                "signature": sig,
+                "children": [],
+                "doc": null,
+                "deprecated": false,
+                "visibility": null,
+                "modifiers": [],
+                "type_params": null,
+                "decorators": [],
+            })
+        };
+        let mut area = function("area", (2, 4), (17, 66), "pub fn area(side: f64) -> f64");
+        area["doc"] = json!("Square area.");
+        area["visibility"] = json!("pub");
+        let total = function("total", (6, 8), (68, 103), "fn total() -> f64");
+        assert_eq!(
+            parse(read),
+            json!({"path": "area.rs", "lang": "rust", "entries": [area, total]})
+        );
+
+        let search = run_json("area", tmp.path(), None, &opts).unwrap();
+        let usage = |line: u32, text: &str| {
+            json!({"path": "area.rs", "line": line, "text": text, "kind": "usage",
+                   "name": null, "range": null, "implements": null})
+        };
+        assert_eq!(
+            parse(search),
+            json!({
+                "query": "area",
+                "total_found": 3,
+                "definitions": 1,
+                "usages": 2,
+                "offset": 0,
+                "matches": [
+                    {"path": "area.rs", "line": 2, "text": "pub fn area(side: f64) -> f64 {",
+                     "kind": "definition", "name": "area", "range": [2, 4], "implements": null},
+                    usage(1, "/// Square area."),
+                    usage(7, "    area(2.0)"),
+                ],
+            })
+        );
+    }
+}
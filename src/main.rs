@@ -17,6 +17,8 @@ struct Cli {
     query: Option<String>,
 
     /// Directory to search within or resolve relative paths against.
+    /// With the `archive` feature, maps and symbol, content and regex searches
+    /// may also take a .tar.gz/.tgz/.tar/.zip.
     #[arg(long, default_value = ".")]
     scope: PathBuf,
 
@@ -32,6 +34,55 @@ struct Cli {
     #[arg(long)]
     full: bool,
 
+    /// Outline style for code files, forced at any file size:
+    /// names (symbol names only), stub (public declarations without bodies),
+    /// grouped (sectioned by kind) or json (the entry tree as JSON).
+    #[arg(long, value_name = "STYLE", conflicts_with_all = ["section", "full"])]
+    outline: Option<tilth::OutlineStyle>,
+
+    /// Show the first N lines of each function body under its outline entry.
+    #[arg(long, value_name = "N", conflicts_with_all = ["section", "full"])]
+    body_lines: Option<usize>,
+
+    /// Cut outline symbol names longer than N bytes (at least 3), ending them with "...".
+    #[arg(long, value_name = "N", conflicts_with_all = ["section", "full"])]
+    max_name_len: Option<usize>,
+
+    /// Add byte offsets to outline entry ranges: [10-20 b340-890], end exclusive.
+    #[arg(long, conflicts_with_all = ["section", "full"])]
+    byte_ranges: bool,
+
+    /// Collapse getter/setter pairs in outlines into one "X { get; set }" entry.
+    #[arg(long, conflicts_with_all = ["section", "full"])]
+    collapse_accessors: bool,
+
+    /// List imported names the file never mentions under the outline's
+    /// import summary. A textual guess; trait imports can look unused.
+    #[arg(long, conflicts_with_all = ["section", "full"])]
+    unused_imports: bool,
+
+    /// Pin the outline text layout to version N so scripts parsing it keep
+    /// working across upgrades. Defaults to the latest.
+    #[arg(long, value_name = "N", conflicts_with_all = ["section", "full"])]
+    format_version: Option<tilth::FormatVersion>,
+
+    /// Outline only the symbols overlapping these lines, e.g. "12-30,88".
+    #[arg(long, value_name = "LINES", conflicts_with_all = ["section", "full", "changed_diff"])]
+    changed: Option<tilth::LineRanges>,
+
+    /// Outline only the symbols a unified diff (e.g. `git diff > pr.patch`)
+    /// changes in the file being read.
+    #[arg(long, value_name = "PATCH", conflicts_with_all = ["section", "full"])]
+    changed_diff: Option<PathBuf>,
+
+    /// Show one symbol's signature, doc comment and location instead of the file.
+    #[arg(long, value_name = "SYMBOL", conflicts_with_all = ["section", "full", "outline", "body_lines"])]
+    card: Option<String>,
+
+    /// Append outlines of locally-imported files (one level deep) after a file read.
+    #[arg(long, conflicts_with = "section")]
+    inline_imports: bool,
+
     /// Machine-readable JSON output.
     #[arg(long)]
     json: bool,
@@ -56,6 +107,94 @@ struct Cli {
     #[arg(long)]
     glob: Option<String>,
 
+    /// Skip this many ranked search matches (paging).
+    #[arg(long, default_value_t = 0)]
+    offset: usize,
+
+    /// Max search matches per page [default: 10].
+    #[arg(long)]
+    limit: Option<usize>,
+
+    /// Leave test files out of search results and the map (see `tilth::is_test_path`).
+    #[arg(long)]
+    exclude_tests: bool,
+
+    /// Definition kinds a symbol search keeps, comma-separated; `!` drops a kind
+    /// (e.g. "type", "fn,const", "!var"). `type` covers class/struct/interface/alias/enum.
+    #[arg(long, value_name = "KINDS")]
+    kinds: Option<tilth::KindFilter>,
+
+    /// Return only symbol definitions or only usages: definitions, usages or all [default: all].
+    #[arg(long, value_name = "ROLE")]
+    only: Option<tilth::MatchRole>,
+
+    /// Match symbols across naming conventions: user_name, userName and user-name
+    /// all match "username".
+    #[arg(long)]
+    ignore_case_style: bool,
+
+    /// Match symbols regardless of letter case: "color" finds Color.
+    #[arg(long)]
+    ignore_case: bool,
+
+    /// Match symbols anywhere inside identifiers: "set" also finds settings and reset.
+    #[arg(long)]
+    substring: bool,
+
+    /// Take the query as a regex over symbol names, e.g. 'handle.*Request' or '^parse'.
+    #[arg(long)]
+    regex: bool,
+
+    /// Max bytes of map output; cut at a line boundary with a truncation notice.
+    #[arg(long, value_name = "N", requires = "map")]
+    max_bytes: Option<usize>,
+
+    /// Print per-phase map timing (walk, parse, format) to stderr.
+    #[arg(long, requires = "map")]
+    timings: bool,
+
+    /// Map files that .gitignore excludes too.
+    #[arg(long, requires = "map")]
+    include_ignored: bool,
+
+    /// Order of the files in each map directory: name, size, mtime or lines [default: name].
+    #[arg(long, value_name = "ORDER", requires = "map")]
+    sort: Option<tilth::map::SortOrder>,
+
+    /// Note each map file's line count.
+    #[arg(long, requires = "map")]
+    show_lines: bool,
+
+    /// Note each map file's size.
+    #[arg(long, requires = "map")]
+    show_size: bool,
+
+    /// Show map directories even when every file under them was filtered out.
+    #[arg(long, requires = "map")]
+    keep_empty_dirs: bool,
+
+    /// Print the map as a Mermaid graph of its directories.
+    #[arg(long, requires = "map")]
+    mermaid: bool,
+
+    /// With --mermaid, add each file as a leaf under its directory.
+    #[arg(long, requires = "mermaid")]
+    mermaid_files: bool,
+
+    /// Write the map to FILE instead of stdout, streamed as it is formatted.
+    #[arg(long, value_name = "FILE", requires = "map")]
+    output: Option<PathBuf>,
+
+    /// Write a ctags `tags` file for `--scope` (default: `tags` in the scope),
+    /// for Vim/Emacs tag navigation. Tag paths are relative to the scope.
+    #[arg(
+        long,
+        value_name = "FILE",
+        num_args = 0..=1,
+        conflicts_with_all = ["callers", "deps", "map"]
+    )]
+    tags: Option<Option<PathBuf>>,
+
     /// Find all callers of a symbol.
     #[arg(long, conflicts_with_all = ["deps", "map", "edit"])]
     callers: bool,
@@ -231,12 +370,61 @@ fn main() {
 
     let is_tty = io::stdout().is_terminal();
 
+    // Tags mode
+    if let Some(path) = cli.tags {
+        let scope = cli.scope.canonicalize().unwrap_or(cli.scope);
+        let path = path.unwrap_or_else(|| scope.join("tags"));
+        if let Err(e) = tilth::map::write_tags(&scope, &path) {
+            emit_result(Err(e), &scope.to_string_lossy(), false, is_tty);
+        }
+        return;
+    }
+
     // Map mode
     if cli.map {
         let cache = tilth::cache::OutlineCache::new();
         let scope = cli.scope.canonicalize().unwrap_or(cli.scope);
-        let output = tilth::map::generate(&scope, 3, cli.budget, &cache);
-        emit_output(&output, is_tty);
+        let config = match tilth::config::ProjectConfig::load(&scope) {
+            Ok(config) => config,
+            Err(e) => {
+                emit_result(Err(e), &scope.to_string_lossy(), false, is_tty);
+                return;
+            }
+        };
+        let opts = tilth::map::MapOptions {
+            exclude_tests: cli.exclude_tests || config.options.search.exclude_tests,
+            max_bytes: cli.max_bytes,
+            timings: cli.timings,
+            include_ignored: cli.include_ignored,
+            sort: cli.sort.unwrap_or_default(),
+            show_lines: cli.show_lines,
+            show_size: cli.show_size,
+            languages: Vec::new(),
+            keep_empty_dirs: cli.keep_empty_dirs,
+            format: if cli.mermaid {
+                tilth::map::MapFormat::Mermaid {
+                    files: cli.mermaid_files,
+                }
+            } else {
+                tilth::map::MapFormat::Text
+            },
+            exclude: config.options.search.exclude.clone(),
+        };
+        let budget = cli.budget.or(config.budget);
+        if let Some(path) = cli.output {
+            let written = std::fs::File::create(&path)
+                .map_err(|source| tilth::error::TilthError::IoError { path, source })
+                .and_then(|file| {
+                    let mut out = io::BufWriter::new(file);
+                    tilth::map::generate_to(&scope, 3, budget, &cache, &opts, &mut out)
+                });
+            if let Err(e) = written {
+                emit_result(Err(e), &scope.to_string_lossy(), false, is_tty);
+            }
+            return;
+        }
+        let result = tilth::map::generate_with(&scope, 3, budget, &cache, &opts);
+        emit_result(result, &scope.to_string_lossy(), false, is_tty);
         return;
     }
 
@@ -250,6 +438,15 @@ fn main() {
 
     let cache = tilth::cache::OutlineCache::new();
     let scope = cli.scope.canonicalize().unwrap_or(cli.scope);
+    // `.tilth.toml` supplies defaults; explicit flags below override them.
+    let config = match tilth::config::ProjectConfig::load(&scope) {
+        Ok(config) => config,
+        Err(e) => {
+            emit_result(Err(e), &query, cli.json, is_tty);
+            return;
+        }
+    };
+    let budget = cli.budget.or(config.budget);
 
     // When piped (not a TTY), force full output — scripts expect raw content
     let full = cli.full || !is_tty;
@@ -257,14 +454,8 @@ fn main() {
 
     // Callers mode
     if cli.callers {
-        let result = tilth::run_callers(
-            &query,
-            &scope,
-            expand,
-            cli.budget,
-            cli.glob.as_deref(),
-            &cache,
-        );
+        let result =
+            tilth::run_callers(&query, &scope, expand, budget, cli.glob.as_deref(), &cache);
         emit_result(result, &query, cli.json, is_tty);
         return;
     }
@@ -286,41 +477,75 @@ fn main() {
                 }
             }
         };
-        let result = tilth::run_deps(&path, &scope, cli.budget, &cache);
+        let result = tilth::run_deps(&path, &scope, budget, &cache);
         emit_result(result, &query, cli.json, is_tty);
         return;
     }
 
-    let result = if expand > 0 {
-        tilth::run_expanded(
-            &query,
-            &scope,
-            cli.section.as_deref(),
-            cli.budget,
-            full,
-            expand,
-            cli.glob.as_deref(),
-            &cache,
-        )
-    } else if full {
-        tilth::run_full(
-            &query,
-            &scope,
-            cli.section.as_deref(),
-            cli.budget,
-            cli.glob.as_deref(),
-            &cache,
-        )
-    } else {
-        tilth::run(
-            &query,
-            &scope,
-            cli.section.as_deref(),
-            cli.budget,
-            cli.glob.as_deref(),
-            &cache,
-        )
-    };
+    let mut options = config.options;
+    if let Some(style) = cli.outline {
+        options.outline.style = style;
+    }
+    if let Some(n) = cli.body_lines {
+        options.outline.body_lines = n;
+    }
+    if let Some(n) = cli.max_name_len {
+        options.outline.max_name_len = Some(n);
+    }
+    options.outline.byte_ranges |= cli.byte_ranges;
+    options.outline.collapse_accessors |= cli.collapse_accessors;
+    options.outline.unused_imports |= cli.unused_imports;
+    if let Some(version) = cli.format_version {
+        options.outline.format_version = version;
+    }
+    if let Some(ranges) = cli.changed {
+        options.outline.changed_lines = Some(ranges);
+    }
+    if let Some(patch) = cli.changed_diff {
+        match std::fs::read_to_string(&patch) {
+            Ok(raw) => {
+                let ranges = tilth::diff::changed_lines(&raw, Path::new(&query));
+                options.outline.changed_lines = Some(ranges.unwrap_or_default());
+            }
+            Err(source) => {
+                let e = tilth::error::TilthError::IoError {
+                    path: patch,
+                    source,
+                };
+                emit_result(Err(e), &query, cli.json, is_tty);
+                return;
+            }
+        }
+    }
+    options.inline_imports = cli.inline_imports;
+    options.card = cli.card;
+    options.search.offset = cli.offset;
+    if let Some(limit) = cli.limit {
+        options.search.limit = limit.max(1);
+    }
+    options.search.exclude_tests |= cli.exclude_tests;
+    if let Some(kinds) = cli.kinds {
+        options.search.kinds = kinds;
+    }
+    if let Some(role) = cli.only {
+        options.search.role = role;
+    }
+    options.search.ignore_case_style |= cli.ignore_case_style;
+    options.search.ignore_case |= cli.ignore_case;
+    options.search.substring = cli.substring;
+    options.search.regex = cli.regex;
+
+    let result = tilth::run_with_options(
+        &query,
+        &scope,
+        cli.section.as_deref(),
+        budget,
+        full,
+        expand,
+        cli.glob.as_deref(),
+        &options,
+        &cache,
+    );
 
     emit_result(result, &query, cli.json, is_tty);
 }
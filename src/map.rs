@@ -1,41 +1,263 @@
-use std::collections::BTreeMap;
-use std::fmt::Write;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Write};
+use std::io;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
 use ignore::WalkBuilder;
 
 use crate::cache::OutlineCache;
+use crate::error::TilthError;
+use crate::index::SymbolIndex;
 use crate::lang::detect_file_type;
+use crate::lang::detection::{is_minified, is_minified_file, is_test_path, MinifiedThreshold};
 use crate::read::outline;
-use crate::types::{estimate_tokens, FileType};
+use crate::search::SearchOptions;
+use crate::types::{estimate_tokens, FileType, Lang, OutlineEntry, OutlineKind};
+
+/// Map settings beyond scope/depth/budget. `Default` maps every file.
+#[derive(Debug, Clone, Default)]
+pub struct MapOptions {
+    /// Drop test files (see [`crate::lang::detection::is_test_path`]).
+    pub exclude_tests: bool,
+    /// Hard cap on output size in bytes, cut at a line boundary. Applied after
+    /// the token budget, for limits that long lines would otherwise slip past.
+    pub max_bytes: Option<usize>,
+    /// Report per-phase timing (walk, parse, format) on stderr.
+    pub timings: bool,
+    /// Map files that `.gitignore` excludes too. By default ignore rules are
+    /// honored — nested `.gitignore` files and `!` negations included — whether
+    /// or not `scope` is inside a git repository.
+    pub include_ignored: bool,
+    /// Glob patterns, relative to the scope, of files and directories left out
+    /// of a directory map (`generated/**`, `*.generated.ts`).
+    pub exclude: Vec<String>,
+    /// Order of the files within each directory.
+    pub sort: SortOrder,
+    /// Note each file's line count: `server.go (312 lines): Server, listen`.
+    pub show_lines: bool,
+    /// Note each file's size: `server.go (8KB): Server, listen`.
+    pub show_size: bool,
+    /// Keep only code files in these languages; empty keeps every file.
+    pub languages: Vec<Lang>,
+    /// Show directories the walk enters even when no file under them is
+    /// mapped. By default a directory whose files were all filtered out
+    /// (language, tests, `exclude`) is left out.
+    pub keep_empty_dirs: bool,
+    /// Text tree or Mermaid diagram.
+    pub format: MapFormat,
+}
+
+/// Output of a map: see [`MapOptions::format`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MapFormat {
+    /// The indented tree with symbols and token estimates.
+    #[default]
+    Text,
+    /// A Mermaid `graph TD` of directory containment, for markdown docs. With
+    /// `files`, each file is a leaf under its directory. Node IDs come from the
+    /// path, so they stay the same as the tree grows. The token budget and
+    /// byte cap don't apply: a cut diagram wouldn't render.
+    Mermaid { files: bool },
+}
+
+impl MapOptions {
+    /// True when a file of `file_type` passes [`MapOptions::languages`].
+    fn keeps(&self, file_type: FileType) -> bool {
+        self.languages.is_empty()
+            || matches!(file_type, FileType::Code(lang) if self.languages.contains(&lang))
+    }
+}
+
+/// How [`MapOptions::sort`] orders the files in a directory. Ties, and
+/// subdirectories, go by name, so the map is the same on every platform.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    /// By file name, in byte order.
+    #[default]
+    Name,
+    /// Largest first.
+    Size,
+    /// Most recently modified first.
+    Mtime,
+    /// Most lines first.
+    LineCount,
+}
+
+impl std::str::FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "name" => Ok(Self::Name),
+            "size" => Ok(Self::Size),
+            "mtime" => Ok(Self::Mtime),
+            "lines" => Ok(Self::LineCount),
+            _ => Err(format!(
+                "unknown sort order \"{s}\" (supported: name, size, mtime, lines)"
+            )),
+        }
+    }
+}
 
 /// Generate a structural codebase map.
 /// Code files show symbol names from outline cache.
 /// Non-code files show name + token estimate.
-#[must_use]
-pub fn generate(scope: &Path, depth: usize, budget: Option<u64>, cache: &OutlineCache) -> String {
+/// Minified bundles are noted but never parsed.
+/// Fails when `scope` is missing or not a directory.
+pub fn generate(
+    scope: &Path,
+    depth: usize,
+    budget: Option<u64>,
+    cache: &OutlineCache,
+) -> Result<String, TilthError> {
+    generate_with(scope, depth, budget, cache, &MapOptions::default())
+}
+
+/// [`generate`] with explicit [`MapOptions`].
+pub fn generate_with(
+    scope: &Path,
+    depth: usize,
+    budget: Option<u64>,
+    cache: &OutlineCache,
+    opts: &MapOptions,
+) -> Result<String, TilthError> {
+    generate_logged(scope, depth, budget, cache, opts, &mut std::io::stderr())
+}
+
+/// [`generate_with`], streaming the map into `out` instead of returning it, so a
+/// huge repo's map never sits in memory whole. A token budget or byte cap needs
+/// the complete text to cut it, so with either set the map is rendered first.
+/// Fails on a bad `scope` or when writing to `out` fails.
+pub fn generate_to(
+    scope: &Path,
+    depth: usize,
+    budget: Option<u64>,
+    cache: &OutlineCache,
+    opts: &MapOptions,
+    out: &mut dyn io::Write,
+) -> Result<(), TilthError> {
+    let scope = &*crate::lang::expand_home(scope);
+    let mut timings = Timings::default();
+    let tree = collect(scope, depth, cache, opts, &mut timings)?;
+
+    let start = Instant::now();
+    let written = if let MapFormat::Mermaid { files } = opts.format {
+        write_mermaid(scope, &tree, files, out)
+    } else if budget.is_none() && opts.max_bytes.is_none() {
+        write_map(scope, depth, &tree, usize::MAX, out)
+    } else {
+        out.write_all(render(scope, depth, &tree, budget, opts).as_bytes())
+    };
+    written
+        .and_then(|()| out.flush())
+        .map_err(|e| TilthError::from_io(Path::new("<output>"), e))?;
+    timings.format = start.elapsed();
+
+    if opts.timings {
+        eprintln!("{timings}");
+    }
+    Ok(())
+}
+
+/// [`generate_with`], writing the [`MapOptions::timings`] line to `log`.
+fn generate_logged(
+    scope: &Path,
+    depth: usize,
+    budget: Option<u64>,
+    cache: &OutlineCache,
+    opts: &MapOptions,
+    log: &mut dyn io::Write,
+) -> Result<String, TilthError> {
+    let scope = &*crate::lang::expand_home(scope);
+    let mut timings = Timings::default();
+    let tree = collect(scope, depth, cache, opts, &mut timings)?;
+
+    let start = Instant::now();
+    let out = render(scope, depth, &tree, budget, opts);
+    timings.format = start.elapsed();
+
+    if opts.timings {
+        let _ = writeln!(log, "{timings}");
+    }
+    Ok(out)
+}
+
+/// Walk `scope` (a directory, or an archive with the `archive` feature) into
+/// the per-directory file tree, recording walk and parse time.
+fn collect(
+    scope: &Path,
+    depth: usize,
+    cache: &OutlineCache,
+    opts: &MapOptions,
+    timings: &mut Timings,
+) -> Result<BTreeMap<PathBuf, Vec<FileEntry>>, TilthError> {
+    let start = Instant::now();
+
+    #[cfg(feature = "archive")]
+    let mut tree = if crate::archive::is_archive(scope) {
+        walk_archive(scope, depth, opts, timings)?
+    } else {
+        crate::error::require_dir(scope)?;
+        walk_dir(scope, depth, cache, opts, timings)?
+    };
+    #[cfg(not(feature = "archive"))]
+    let mut tree = {
+        crate::error::require_dir(scope)?;
+        walk_dir(scope, depth, cache, opts, timings)?
+    };
+    for files in tree.values_mut() {
+        sort_files(files, opts.sort);
+    }
+    timings.walk = start.elapsed().saturating_sub(timings.parse);
+    Ok(tree)
+}
+
+/// Where the time of one map run went. Walk excludes the parse time spent
+/// inside it; `parsed` counts outlines computed, not cache hits.
+#[derive(Debug, Default)]
+struct Timings {
+    parsed: usize,
+    walk: Duration,
+    parse: Duration,
+    format: Duration,
+}
+
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "parsed {} files in {:.1}s (walk {:.1}s, parse {:.1}s, format {:.1}s)",
+            self.parsed,
+            (self.walk + self.parse + self.format).as_secs_f64(),
+            self.walk.as_secs_f64(),
+            self.parse.as_secs_f64(),
+            self.format.as_secs_f64(),
+        )
+    }
+}
+
+fn walk_dir(
+    scope: &Path,
+    depth: usize,
+    cache: &OutlineCache,
+    opts: &MapOptions,
+    timings: &mut Timings,
+) -> Result<BTreeMap<PathBuf, Vec<FileEntry>>, TilthError> {
     let mut tree: BTreeMap<PathBuf, Vec<FileEntry>> = BTreeMap::new();
+    let minified_threshold = MinifiedThreshold::from_env();
 
-    let walker = WalkBuilder::new(scope)
-        .follow_links(true)
-        .hidden(false)
-        .git_ignore(false)
-        .git_global(false)
-        .git_exclude(false)
-        .ignore(false)
-        .parents(false)
-        .filter_entry(|entry| {
-            if entry.file_type().is_some_and(|ft| ft.is_dir()) {
-                if let Some(name) = entry.file_name().to_str() {
-                    return !crate::search::SKIP_DIRS.contains(&name);
-                }
+    for entry in walker(scope, Some(depth + 1), opts)?.flatten() {
+        if opts.keep_empty_dirs && entry.file_type().is_some_and(|ft| ft.is_dir()) {
+            let rel = entry.path().strip_prefix(scope).unwrap_or(entry.path());
+            if rel.components().count() <= depth {
+                insert_dir(&mut tree, rel);
             }
-            true
-        })
-        .max_depth(Some(depth + 1))
-        .build();
-
-    for entry in walker.flatten() {
+            continue;
+        }
         if !entry.file_type().is_some_and(|ft| ft.is_file()) {
             continue;
         }
@@ -48,29 +270,32 @@ pub fn generate(scope: &Path, depth: usize, budget: Option<u64>, cache: &Outline
         if file_depth > depth {
             continue;
         }
-
-        let parent = rel.parent().unwrap_or(Path::new("")).to_path_buf();
-        let name = rel
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("")
-            .to_string();
+        let file_type = detect_file_type(path);
+        if (opts.exclude_tests && is_test_path(rel)) || !opts.keeps(file_type) {
+            continue;
+        }
 
         let meta = std::fs::metadata(path).ok();
         let byte_len = meta.as_ref().map_or(0, std::fs::Metadata::len);
-        let tokens = estimate_tokens(byte_len);
+        let mtime = meta
+            .and_then(|m| m.modified().ok())
+            .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
 
-        let file_type = detect_file_type(path);
+        let minified =
+            matches!(file_type, FileType::Code(_)) && is_minified_file(path, minified_threshold);
+        // A file outlined now is read anyway; count its lines from that read.
+        let mut read_lines = None;
         let symbols = match file_type {
-            FileType::Code(_) => {
-                let mtime = meta
-                    .and_then(|m| m.modified().ok())
-                    .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-
+            FileType::Code(_) if !minified => {
                 let outline_str = cache.get_or_compute(path, mtime, || {
+                    let start = Instant::now();
                     let content = std::fs::read_to_string(path).unwrap_or_default();
                     let buf = content.as_bytes();
-                    outline::generate(path, file_type, &content, buf, true)
+                    read_lines = Some(line_count(buf));
+                    let outline = outline::generate(path, file_type, &content, buf, true);
+                    timings.parsed += 1;
+                    timings.parse += start.elapsed();
+                    outline
                 });
 
                 Some(extract_symbol_names(&outline_str))
@@ -78,36 +303,711 @@ pub fn generate(scope: &Path, depth: usize, budget: Option<u64>, cache: &Outline
             _ => None,
         };
 
-        tree.entry(parent.clone()).or_default().push(FileEntry {
-            name,
-            symbols,
-            tokens,
-        });
+        // Counting lines may mean reading the file, so only when they are used.
+        let lines = if opts.sort == SortOrder::LineCount || opts.show_lines {
+            read_lines.unwrap_or_else(|| std::fs::read(path).map_or(0, |b| line_count(&b)))
+        } else {
+            0
+        };
+        insert_file(
+            &mut tree,
+            rel,
+            FileEntry {
+                name: String::new(),
+                symbols,
+                tokens: estimate_tokens(byte_len),
+                minified,
+                bytes: byte_len,
+                mtime,
+                lines,
+                note: note(opts, lines, byte_len),
+            },
+        );
+    }
 
-        // Ensure all ancestor directories exist in the tree so format_tree can find them.
-        let mut ancestor = parent.parent();
-        while let Some(a) = ancestor {
-            tree.entry(a.to_path_buf()).or_default();
-            if a == Path::new("") {
-                break;
+    Ok(tree)
+}
+
+/// Sequential walk of `scope` as the map sees it: [`SKIP_DIRS`](crate::search::SKIP_DIRS)
+/// and [`MapOptions::exclude`] matches are left out, and so are `.gitignore`d
+/// paths unless [`MapOptions::include_ignored`] is set. Fails on a bad pattern.
+fn walker(
+    scope: &Path,
+    max_depth: Option<usize>,
+    opts: &MapOptions,
+) -> Result<ignore::Walk, TilthError> {
+    let gitignore = !opts.include_ignored;
+    let mut builder = WalkBuilder::new(scope);
+    builder
+        .follow_links(true)
+        .hidden(false)
+        .git_ignore(gitignore)
+        .git_global(false)
+        .git_exclude(gitignore)
+        .require_git(false)
+        .ignore(false)
+        .parents(gitignore)
+        .filter_entry(|entry| {
+            if entry.file_type().is_some_and(|ft| ft.is_dir()) {
+                if let Some(name) = entry.file_name().to_str() {
+                    return !crate::search::SKIP_DIRS.contains(&name);
+                }
             }
-            ancestor = a.parent();
+            true
+        })
+        .max_depth(max_depth);
+    if let Some(overrides) = crate::search::overrides(scope, None, &opts.exclude)? {
+        builder.overrides(overrides);
+    }
+    Ok(builder.build())
+}
+
+/// Map a source archive (`.tar.gz` / `.tgz` / `.tar` / `.zip`) from its entries
+/// in memory, without extracting. Same layout and filters as a directory map;
+/// outlines aren't cached since entries have no on-disk identity.
+#[cfg(feature = "archive")]
+fn walk_archive(
+    archive: &Path,
+    depth: usize,
+    opts: &MapOptions,
+    timings: &mut Timings,
+) -> Result<BTreeMap<PathBuf, Vec<FileEntry>>, TilthError> {
+    let mut tree: BTreeMap<PathBuf, Vec<FileEntry>> = BTreeMap::new();
+    let minified_threshold = MinifiedThreshold::from_env();
+
+    for entry in crate::archive::read_entries(archive)? {
+        let rel = entry.path.as_path();
+        if rel.components().count().saturating_sub(1) > depth {
+            continue;
+        }
+        let skipped_dir = rel.parent().is_some_and(|p| {
+            p.iter().any(|c| {
+                c.to_str()
+                    .is_some_and(|c| crate::search::SKIP_DIRS.contains(&c))
+            })
+        });
+        let file_type = detect_file_type(rel);
+        if skipped_dir || (opts.exclude_tests && is_test_path(rel)) || !opts.keeps(file_type) {
+            continue;
         }
+
+        let minified = matches!(file_type, FileType::Code(_))
+            && crate::lang::detection::is_minified(&entry.data, minified_threshold);
+        let symbols = match file_type {
+            FileType::Code(_) if !minified => {
+                let start = Instant::now();
+                let content = String::from_utf8_lossy(&entry.data);
+                let outline_str = outline::generate(rel, file_type, &content, &entry.data, true);
+                timings.parsed += 1;
+                timings.parse += start.elapsed();
+                Some(extract_symbol_names(&outline_str))
+            }
+            _ => None,
+        };
+
+        let lines = line_count(&entry.data);
+        insert_file(
+            &mut tree,
+            rel,
+            FileEntry {
+                name: String::new(),
+                symbols,
+                tokens: estimate_tokens(entry.size),
+                minified,
+                bytes: entry.size,
+                mtime: std::time::SystemTime::UNIX_EPOCH,
+                lines,
+                note: note(opts, lines, entry.size),
+            },
+        );
     }
 
-    let mut out = format!("# Map: {} (depth {})\n", scope.display(), depth);
-    format_tree(&tree, Path::new(""), 0, &mut out);
+    Ok(tree)
+}
+
+/// File the entry for `rel` under its parent directory, named after `rel`.
+fn insert_file(tree: &mut BTreeMap<PathBuf, Vec<FileEntry>>, rel: &Path, mut file: FileEntry) {
+    let parent = rel.parent().unwrap_or(Path::new("")).to_path_buf();
+    file.name = rel
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_string();
+
+    tree.entry(parent.clone()).or_default().push(file);
+    insert_dir(tree, &parent);
+}
 
-    match budget {
-        Some(b) => crate::budget::apply(&out, b),
+/// Make sure `dir` and all its ancestors exist in the tree so `format_tree` can find them.
+fn insert_dir(tree: &mut BTreeMap<PathBuf, Vec<FileEntry>>, dir: &Path) {
+    let mut ancestor = Some(dir);
+    while let Some(a) = ancestor {
+        tree.entry(a.to_path_buf()).or_default();
+        if a == Path::new("") {
+            break;
+        }
+        ancestor = a.parent();
+    }
+}
+
+/// Header + tree, then the token budget and byte cap.
+fn render(
+    scope: &Path,
+    depth: usize,
+    tree: &BTreeMap<PathBuf, Vec<FileEntry>>,
+    budget: Option<u64>,
+    opts: &MapOptions,
+) -> String {
+    if let MapFormat::Mermaid { files } = opts.format {
+        let mut buf = Vec::new();
+        let _ = write_mermaid(scope, tree, files, &mut buf);
+        return String::from_utf8(buf).expect("map is built from str");
+    }
+    let out = match budget {
+        Some(b) => fit_budget(scope, depth, tree, b),
+        None => map_text(scope, depth, tree, usize::MAX),
+    };
+    match opts.max_bytes {
+        Some(max) => crate::budget::cap_bytes(&out, max),
         None => out,
     }
 }
 
+/// The map within `budget` tokens. Files are dropped deepest level first, so
+/// a tight budget keeps the top of the tree, directory names included; when
+/// even the top-level files don't fit, the text is cut like any other output.
+fn fit_budget(
+    scope: &Path,
+    depth: usize,
+    tree: &BTreeMap<PathBuf, Vec<FileEntry>>,
+    budget: u64,
+) -> String {
+    let full = map_text(scope, depth, tree, usize::MAX);
+    if estimate_tokens(full.len() as u64) <= budget {
+        return full;
+    }
+    let level = |dir: &Path| dir.components().count();
+    let deepest = tree
+        .iter()
+        .filter(|(_, files)| !files.is_empty())
+        .map(|(dir, _)| level(dir))
+        .max()
+        .unwrap_or(0);
+    for max_level in (0..deepest).rev() {
+        let omitted: usize = tree
+            .iter()
+            .filter(|(dir, _)| level(dir) > max_level)
+            .map(|(_, files)| files.len())
+            .sum();
+        let out = format!(
+            "{}... {omitted} deeper files omitted (budget: {budget})",
+            map_text(scope, depth, tree, max_level)
+        );
+        if estimate_tokens(out.len() as u64) <= budget {
+            return out;
+        }
+    }
+    crate::budget::apply(&full, budget)
+}
+
+/// Header + tree as a string, with files down to `max_level` directories deep.
+fn map_text(
+    scope: &Path,
+    depth: usize,
+    tree: &BTreeMap<PathBuf, Vec<FileEntry>>,
+    max_level: usize,
+) -> String {
+    let mut buf = Vec::new();
+    let _ = write_map(scope, depth, tree, max_level, &mut buf);
+    String::from_utf8(buf).expect("map is built from str")
+}
+
+/// Header + tree, with files down to `max_level` directories deep.
+fn write_map(
+    scope: &Path,
+    depth: usize,
+    tree: &BTreeMap<PathBuf, Vec<FileEntry>>,
+    max_level: usize,
+    out: &mut dyn io::Write,
+) -> io::Result<()> {
+    writeln!(out, "# Map: {} (depth {})", scope.display(), depth)?;
+    format_tree(tree, Path::new(""), 0, max_level, out)
+}
+
+/// Parsed outlines of every code file in a scope, built once so repeated
+/// [`search_in`] calls never touch the filesystem.
+pub struct OutlineSet {
+    scope: PathBuf,
+    files: Vec<(PathBuf, Vec<OutlineEntry>)>,
+}
+
+impl OutlineSet {
+    /// Outline every code file under `scope` (any depth). Minified bundles and
+    /// files with no entries are left out; `exclude_tests` and `languages` are honored.
+    pub fn build(scope: &Path, opts: &MapOptions) -> Result<Self, TilthError> {
+        let scope = &*crate::lang::expand_home(scope);
+        crate::error::require_dir(scope)?;
+        let minified_threshold = MinifiedThreshold::from_env();
+        let mut files = Vec::new();
+        for entry in walker(scope, None, opts)?.flatten() {
+            let path = entry.path();
+            let file_type = detect_file_type(path);
+            let FileType::Code(lang) = file_type else {
+                continue;
+            };
+            let rel = path.strip_prefix(scope).unwrap_or(path);
+            if (opts.exclude_tests && is_test_path(rel)) || !opts.keeps(file_type) {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            if is_minified(content.as_bytes(), minified_threshold) {
+                continue;
+            }
+            let entries = crate::lang::outline::get_outline_entries(&content, lang);
+            if !entries.is_empty() {
+                files.push((rel.to_path_buf(), entries));
+            }
+        }
+        Ok(Self {
+            scope: scope.to_path_buf(),
+            files,
+        })
+    }
+
+    /// Root the relative paths in this set are under.
+    #[must_use]
+    pub fn scope(&self) -> &Path {
+        &self.scope
+    }
+
+    /// Number of files with at least one outline entry.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// How [`search_in`] matches entry names against the query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NameMatch {
+    /// Exact names, then prefixes, then substrings.
+    #[default]
+    Tiered,
+    /// Every name the query fuzzily matches, best score first, so near misses
+    /// like `tokeniz` still find `tokenize`.
+    Fuzzy,
+}
+
+/// One outline entry matched by [`search_in`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlineHit {
+    /// Relative to [`OutlineSet::scope`].
+    pub path: PathBuf,
+    pub name: String,
+    pub kind: OutlineKind,
+    pub start_line: u32,
+    pub end_line: u32,
+}
+
+/// Search entry names in an [`OutlineSet`] — definitions only, no usages.
+/// Case-insensitive and ranked by `matching`, then path and line. Honors
+/// `opts.kinds`, `exclude_tests`, `ignore_case_style` and paging.
+#[must_use]
+pub fn search_in(
+    set: &OutlineSet,
+    query: &str,
+    opts: &SearchOptions,
+    matching: NameMatch,
+) -> Vec<OutlineHit> {
+    fn visit(
+        path: &Path,
+        entries: &[OutlineEntry],
+        needle: &str,
+        fuzzy: Option<&SkimMatcherV2>,
+        opts: &SearchOptions,
+        hits: &mut Vec<(i64, OutlineHit)>,
+    ) {
+        for entry in entries {
+            let name = normalize(&entry.name, opts);
+            let rank = if let Some(matcher) = fuzzy {
+                matcher.fuzzy_match(&name, needle).map(|score| -score)
+            } else if name == needle {
+                Some(0)
+            } else if name.starts_with(needle) {
+                Some(1)
+            } else if name.contains(needle) {
+                Some(2)
+            } else {
+                None
+            };
+            if let Some(rank) =
+                rank.filter(|_| entry.kind != OutlineKind::Import && opts.kinds.allows(entry.kind))
+            {
+                hits.push((
+                    rank,
+                    OutlineHit {
+                        path: path.to_path_buf(),
+                        name: entry.name.clone(),
+                        kind: entry.kind,
+                        start_line: entry.start_line,
+                        end_line: entry.end_line,
+                    },
+                ));
+            }
+            visit(path, &entry.children, needle, fuzzy, opts, hits);
+        }
+    }
+
+    fn normalize(name: &str, opts: &SearchOptions) -> String {
+        if opts.ignore_case_style {
+            crate::search::fold_ident(name)
+        } else {
+            name.to_lowercase()
+        }
+    }
+
+    let needle = normalize(query, opts);
+    let fuzzy = (matching == NameMatch::Fuzzy).then(SkimMatcherV2::default);
+    let mut hits = Vec::new();
+    for (path, entries) in &set.files {
+        if opts.exclude_tests && is_test_path(path) {
+            continue;
+        }
+        visit(path, entries, &needle, fuzzy.as_ref(), opts, &mut hits);
+    }
+    hits.sort_by(|(ra, a), (rb, b)| (ra, &a.path, a.start_line).cmp(&(rb, &b.path, b.start_line)));
+    hits.into_iter()
+        .map(|(_, hit)| hit)
+        .skip(opts.offset)
+        .take(opts.limit)
+        .collect()
+}
+
+/// A line in a file, 1-based.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct XrefSite {
+    pub path: PathBuf,
+    pub line: u32,
+}
+
+/// One definition and every place its name is used.
+#[derive(Debug, Clone)]
+pub struct XrefEntry {
+    pub name: String,
+    pub definition: XrefSite,
+    pub usages: Vec<XrefSite>,
+}
+
+/// Cross-reference index: each symbol defined in `scope` with all its usages.
+/// Definitions come from the symbol index (built here if needed); usages come
+/// from the symbol search's usage walk, minus each name's own definition lines.
+///
+/// Same-named definitions stay separate entries. A usage in a file that defines
+/// the name goes to that file's first definition; elsewhere it can't be told
+/// apart, so it is listed under every definition of the name.
+/// Sorted by name, then definition location.
+pub fn xref(scope: &Path, index: &SymbolIndex) -> Result<Vec<XrefEntry>, TilthError> {
+    crate::error::require_dir(scope)?;
+    if !index.is_built(scope) {
+        index.build(scope);
+    }
+
+    let mut defs: HashMap<Arc<str>, Vec<XrefSite>> = HashMap::new();
+    for (name, loc) in index.definitions(scope) {
+        defs.entry(name).or_default().push(XrefSite {
+            path: loc.path,
+            line: loc.line,
+        });
+    }
+    for sites in defs.values_mut() {
+        sites.sort();
+        sites.dedup();
+    }
+
+    // (name, usage site) pairs from the symbol usage search.
+    let mut names: Vec<&str> = defs.keys().map(|name| &**name).collect();
+    names.sort_unstable();
+    let opts = SearchOptions::default();
+    let mut usages: Vec<(Arc<str>, XrefSite)> = Vec::new();
+    for m in crate::search::symbol::usages_of("xref", &names, scope, None, &opts)? {
+        let mut seen: Vec<&str> = Vec::new();
+        for word in m
+            .text
+            .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        {
+            if word.is_empty() || seen.contains(&word) {
+                continue;
+            }
+            seen.push(word);
+            let Some((name, sites)) = defs.get_key_value(word) else {
+                continue;
+            };
+            if sites.iter().any(|s| s.line == m.line && s.path == m.path) {
+                continue;
+            }
+            usages.push((
+                name.clone(),
+                XrefSite {
+                    path: m.path.clone(),
+                    line: m.line,
+                },
+            ));
+        }
+    }
+
+    let mut entries: Vec<XrefEntry> = defs
+        .iter()
+        .flat_map(|(name, sites)| {
+            sites.iter().map(|site| XrefEntry {
+                name: name.to_string(),
+                definition: site.clone(),
+                usages: Vec::new(),
+            })
+        })
+        .collect();
+    entries.sort_by(|a, b| (&a.name, &a.definition).cmp(&(&b.name, &b.definition)));
+
+    let mut owners: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, entry) in entries.iter().enumerate() {
+        owners.entry(entry.name.clone()).or_default().push(i);
+    }
+    for (name, site) in usages {
+        let Some(candidates) = owners.get(&*name) else {
+            continue;
+        };
+        let local = candidates
+            .iter()
+            .find(|&&i| entries[i].definition.path == site.path);
+        match local {
+            Some(&i) => entries[i].usages.push(site),
+            None => {
+                for &i in candidates {
+                    entries[i].usages.push(site.clone());
+                }
+            }
+        }
+    }
+    for entry in &mut entries {
+        entry.usages.sort();
+    }
+
+    Ok(entries)
+}
+
+/// Render an xref as a plain-text index, paths relative to `scope`:
+/// `name  def.rs:12` followed by one indented `file:line` per usage.
+#[must_use]
+pub fn format_xref(entries: &[XrefEntry], scope: &Path) -> String {
+    let rel = |site: &XrefSite| {
+        let path = site.path.strip_prefix(scope).unwrap_or(&site.path);
+        format!("{}:{}", path.display(), site.line)
+    };
+    let mut out = format!(
+        "# Xref: {} ({} definitions)\n",
+        scope.display(),
+        entries.len()
+    );
+    for entry in entries {
+        let _ = writeln!(
+            out,
+            "{}  {} ({} usages)",
+            entry.name,
+            rel(&entry.definition),
+            entry.usages.len()
+        );
+        for usage in &entry.usages {
+            let _ = writeln!(out, "  {}", rel(usage));
+        }
+    }
+    out
+}
+
+/// Output format of [`symbol_index_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IndexFormat {
+    /// `name  kind  path:line`, one symbol per line under a `# Symbols` header.
+    #[default]
+    Plain,
+    /// Exuberant/Universal ctags (`name<TAB>path<TAB>line;"<TAB>kind`), kinds as
+    /// ctags letters (see [`ctags_kind`]), usable as a Vim/Emacs `tags` file.
+    Ctags,
+}
+
+/// Flat index of every symbol defined under `scope`, sorted by name (byte
+/// order), then path and line. Every outline entry counts, nested ones
+/// included; imports and `impl` blocks are left out. Paths are relative to `scope`.
+/// Fails when `scope` is missing or not a directory.
+pub fn symbol_index(scope: &Path) -> Result<String, TilthError> {
+    symbol_index_with(scope, IndexFormat::Plain)
+}
+
+/// [`symbol_index`] in the given [`IndexFormat`].
+pub fn symbol_index_with(scope: &Path, format: IndexFormat) -> Result<String, TilthError> {
+    fn collect(
+        entries: &[OutlineEntry],
+        rel: &Path,
+        lang: Lang,
+        symbols: &mut Vec<(String, PathBuf, u32, OutlineKind, &'static str)>,
+    ) {
+        for entry in entries {
+            // An export entry is named by its statement; generic parameters
+            // follow some names: `Box<T>`, Go's `Map[T any]`.
+            let name = if entry.kind == OutlineKind::Export {
+                outline::code::export_name(&entry.name).unwrap_or_default()
+            } else {
+                let name = entry.name.split(['<', '[']).next().unwrap_or_default();
+                name.to_string()
+            };
+            // Impl blocks and Go's per-receiver method groups define nothing.
+            let skip = entry.kind == OutlineKind::Import
+                || name.is_empty()
+                || name == "impl"
+                || name.starts_with("impl ")
+                || (lang == Lang::Go && entry.kind == OutlineKind::Module);
+            if !skip {
+                symbols.push((
+                    name,
+                    rel.to_path_buf(),
+                    entry.start_line,
+                    entry.kind,
+                    outline::code::kind_label(entry.kind, lang),
+                ));
+            }
+            collect(&entry.children, rel, lang, symbols);
+        }
+    }
+
+    let scope = &*crate::lang::expand_home(scope);
+    crate::error::require_dir(scope)?;
+    let minified_threshold = MinifiedThreshold::from_env();
+
+    let mut symbols = Vec::new();
+    for entry in walker(scope, None, &MapOptions::default())?.flatten() {
+        let path = entry.path();
+        let FileType::Code(lang) = detect_file_type(path) else {
+            continue;
+        };
+        let Ok(content) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        if is_minified(content.as_bytes(), minified_threshold) {
+            continue;
+        }
+        let entries = crate::lang::outline::get_outline_entries(&content, lang);
+        let rel = path.strip_prefix(scope).unwrap_or(path);
+        collect(&entries, rel, lang, &mut symbols);
+    }
+    symbols.sort_by(|a, b| (&a.0, &a.1, a.2, a.4).cmp(&(&b.0, &b.1, b.2, b.4)));
+
+    let mut out = String::new();
+    match format {
+        IndexFormat::Plain => {
+            let _ = writeln!(
+                out,
+                "# Symbols: {} ({} symbols)",
+                scope.display(),
+                symbols.len()
+            );
+            for (name, path, line, _, label) in &symbols {
+                let _ = writeln!(out, "{name}  {label}  {}:{line}", path.display());
+            }
+        }
+        IndexFormat::Ctags => {
+            out.push_str("!_TAG_FILE_FORMAT\t2\t/extended format/\n");
+            out.push_str("!_TAG_FILE_SORTED\t1\t/0=unsorted, 1=sorted, 2=foldcase/\n");
+            out.push_str("!_TAG_PROGRAM_NAME\ttilth\t//\n");
+            for (name, path, line, kind, _) in &symbols {
+                let kind = ctags_kind(*kind);
+                let _ = writeln!(out, "{name}\t{}\t{line};\"\t{kind}", path.display());
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Write a ctags `tags` file for `scope` to `path` — [`symbol_index_with`] in
+/// [`IndexFormat::Ctags`]. Tag paths are relative to `scope`, so editors find
+/// them when the file sits at the scope root.
+pub fn write_tags(scope: &Path, path: &Path) -> Result<(), TilthError> {
+    let tags = symbol_index_with(scope, IndexFormat::Ctags)?;
+    std::fs::write(path, tags).map_err(|e| TilthError::from_io(path, e))
+}
+
+/// The ctags kind letter for an outline kind, following Universal ctags where
+/// it has one: `f` function, `c` class, `s` struct, `i` interface/trait,
+/// `t` type alias, `g` enum, `C` constant, `v` variable, `n` module/namespace,
+/// `m` property (member), `e` export, `M` macro, `S`/`T` test suite/case.
+#[must_use]
+pub fn ctags_kind(kind: OutlineKind) -> char {
+    match kind {
+        OutlineKind::Function => 'f',
+        OutlineKind::Class => 'c',
+        OutlineKind::Struct => 's',
+        OutlineKind::Interface => 'i',
+        OutlineKind::TypeAlias => 't',
+        OutlineKind::Enum => 'g',
+        OutlineKind::Constant => 'C',
+        OutlineKind::Variable | OutlineKind::ImmutableVariable => 'v',
+        OutlineKind::Module => 'n',
+        OutlineKind::Property => 'm',
+        OutlineKind::Export => 'e',
+        OutlineKind::Macro => 'M',
+        OutlineKind::Import => 'I',
+        OutlineKind::TestSuite => 'S',
+        OutlineKind::TestCase => 'T',
+    }
+}
+
 struct FileEntry {
     name: String,
     symbols: Option<Vec<String>>,
     tokens: u64,
+    minified: bool,
+    bytes: u64,
+    mtime: std::time::SystemTime,
+    /// Counted only for [`SortOrder::LineCount`] or [`MapOptions::show_lines`];
+    /// 0 otherwise.
+    lines: usize,
+    /// `312 lines, 8KB` as [`MapOptions`] asks, shown after the name.
+    note: Option<String>,
+}
+
+/// The line count and size annotation `opts` asks for, if any.
+fn note(opts: &MapOptions, lines: usize, bytes: u64) -> Option<String> {
+    let mut parts = Vec::new();
+    if opts.show_lines {
+        parts.push(format!("{lines} lines"));
+    }
+    if opts.show_size {
+        parts.push(crate::format::format_size(bytes));
+    }
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+/// Order one directory's files by `order`, ties by name.
+fn sort_files(files: &mut [FileEntry], order: SortOrder) {
+    files.sort_by(|a, b| {
+        let by_order = match order {
+            SortOrder::Name => std::cmp::Ordering::Equal,
+            SortOrder::Size => b.bytes.cmp(&a.bytes),
+            SortOrder::Mtime => b.mtime.cmp(&a.mtime),
+            SortOrder::LineCount => b.lines.cmp(&a.lines),
+        };
+        by_order.then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+/// Lines in `data`, a last line without a newline included.
+fn line_count(data: &[u8]) -> usize {
+    let newlines = memchr::memchr_iter(b'\n', data).count();
+    newlines + usize::from(data.last().is_some_and(|&b| b != b'\n'))
 }
 
 /// Extract symbol names from an outline string.
@@ -169,8 +1069,14 @@ fn format_tree(
     tree: &BTreeMap<PathBuf, Vec<FileEntry>>,
     dir: &Path,
     indent: usize,
-    out: &mut String,
-) {
+    max_level: usize,
+    out: &mut dyn io::Write,
+) -> io::Result<()> {
+    // Past the deepest level shown, only the directory's own name remains.
+    if indent > max_level {
+        return Ok(());
+    }
+
     // Collect subdirectories that have entries
     let mut subdirs: Vec<&PathBuf> = tree
         .keys()
@@ -183,9 +1089,20 @@ fn format_tree(
     // Show files in this directory
     if let Some(files) = tree.get(dir) {
         for f in files {
-            if let Some(ref symbols) = f.symbols {
+            let note = f
+                .note
+                .as_deref()
+                .map(|n| format!(", {n}"))
+                .unwrap_or_default();
+            if f.minified {
+                writeln!(
+                    out,
+                    "{prefix}{} (minified, ~{} tokens{note})",
+                    f.name, f.tokens
+                )?;
+            } else if let Some(ref symbols) = f.symbols {
                 if symbols.is_empty() {
-                    let _ = writeln!(out, "{prefix}{} (~{} tokens)", f.name, f.tokens);
+                    writeln!(out, "{prefix}{} (~{} tokens{note})", f.name, f.tokens)?;
                 } else {
                     let syms = symbols.join(", ");
                     let truncated = if syms.len() > 80 {
@@ -193,10 +1110,13 @@ fn format_tree(
                     } else {
                         syms
                     };
-                    let _ = writeln!(out, "{prefix}{}: {truncated}", f.name);
+                    match &f.note {
+                        Some(n) => writeln!(out, "{prefix}{} ({n}): {truncated}", f.name)?,
+                        None => writeln!(out, "{prefix}{}: {truncated}", f.name)?,
+                    }
                 }
             } else {
-                let _ = writeln!(out, "{prefix}{} (~{} tokens)", f.name, f.tokens);
+                writeln!(out, "{prefix}{} (~{} tokens{note})", f.name, f.tokens)?;
             }
         }
     }
@@ -204,7 +1124,775 @@ fn format_tree(
     // Recurse into subdirectories
     for subdir in subdirs {
         let dir_name = subdir.file_name().and_then(|n| n.to_str()).unwrap_or("?");
-        let _ = writeln!(out, "{prefix}{dir_name}/");
-        format_tree(tree, subdir, indent + 1, out);
+        writeln!(out, "{prefix}{dir_name}/")?;
+        format_tree(tree, subdir, indent + 1, max_level, out)?;
+    }
+    Ok(())
+}
+
+/// The tree as a Mermaid `graph TD`: the scope at the root, an edge from each
+/// directory to its subdirectories and, with `files`, to its files.
+fn write_mermaid(
+    scope: &Path,
+    tree: &BTreeMap<PathBuf, Vec<FileEntry>>,
+    files: bool,
+    out: &mut dyn io::Write,
+) -> io::Result<()> {
+    let root = scope.file_name().map_or_else(
+        || scope.display().to_string(),
+        |n| n.to_string_lossy().into_owned(),
+    );
+    writeln!(out, "graph TD")?;
+    writeln!(out, "  root[\"{}/\"]", mermaid_label(&root))?;
+    for (dir, entries) in tree {
+        let id = mermaid_id('d', dir);
+        if let Some(parent) = dir.parent() {
+            let name = dir
+                .file_name()
+                .map(|n| n.to_string_lossy())
+                .unwrap_or_default();
+            writeln!(out, "  {id}[\"{}/\"]", mermaid_label(&name))?;
+            writeln!(out, "  {} --> {id}", mermaid_id('d', parent))?;
+        }
+        if !files {
+            continue;
+        }
+        for f in entries {
+            let file_id = mermaid_id('f', &dir.join(&f.name));
+            writeln!(out, "  {file_id}[\"{}\"]", mermaid_label(&f.name))?;
+            writeln!(out, "  {id} --> {file_id}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Node ID for `rel`, prefixed `d` (directory) or `f` (file): letters and
+/// digits kept, every other byte as `_xx` hex, so distinct paths never collide.
+/// The scope itself is `root`.
+fn mermaid_id(prefix: char, rel: &Path) -> String {
+    let rel = rel.to_string_lossy();
+    if rel.is_empty() {
+        return "root".to_string();
+    }
+    let mut id = format!("{prefix}_");
+    for b in rel.bytes() {
+        if b.is_ascii_alphanumeric() {
+            id.push(char::from(b));
+        } else {
+            let _ = write!(id, "_{b:02x}");
+        }
+    }
+    id
+}
+
+/// `name` safe inside a quoted Mermaid label.
+fn mermaid_label(name: &str) -> String {
+    name.replace('#', "#35;")
+        .replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The Rust, TypeScript, Python, Go and Java tree the benches run on.
+    fn polyglot() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("benches/fixtures/polyglot")
+    }
+
+    #[test]
+    fn minified_files_are_noted_not_outlined() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = format!("function bundled(){{{}}}", "var a=1;".repeat(1000));
+        std::fs::write(dir.path().join("app.min.js"), bundle).unwrap();
+        std::fs::write(dir.path().join("lib.js"), "function helper() {}\n").unwrap();
+
+        let out = generate(dir.path(), 3, None, &OutlineCache::new()).unwrap();
+        assert!(out.contains("app.min.js (minified, ~"), "got:\n{out}");
+        assert!(!out.contains("bundled"), "got:\n{out}");
+        assert!(out.contains("lib.js: helper"), "got:\n{out}");
+    }
+
+    #[test]
+    fn timings_line_only_when_enabled() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "fn a() {}\n").unwrap();
+        std::fs::write(dir.path().join("b.py"), "def b(): pass\n").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "hi\n").unwrap();
+
+        let run = |timings: bool| {
+            let opts = MapOptions {
+                timings,
+                ..MapOptions::default()
+            };
+            let mut log = Vec::new();
+            let out = generate_logged(dir.path(), 3, None, &OutlineCache::new(), &opts, &mut log)
+                .unwrap();
+            (out, String::from_utf8(log).unwrap())
+        };
+
+        let (plain, log) = run(false);
+        assert_eq!(log, "");
+        let (timed, log) = run(true);
+        assert_eq!(timed, plain, "timing must not change the map");
+        assert!(
+            log.starts_with("parsed 2 files in ")
+                && log.contains("(walk ")
+                && log.ends_with("s)\n"),
+            "got: {log}"
+        );
+    }
+
+    #[test]
+    fn streamed_map_matches_string_map() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/net")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "pub fn run() {}\n").unwrap();
+        std::fs::write(dir.path().join("src/net/http.rs"), "pub struct Client;\n").unwrap();
+        std::fs::write(dir.path().join("README.md"), "# Demo\n").unwrap();
+
+        let cache = OutlineCache::new();
+        for (budget, max_bytes) in [(None, None), (Some(20), None), (None, Some(40))] {
+            let opts = MapOptions {
+                max_bytes,
+                ..MapOptions::default()
+            };
+            let expected = generate_with(dir.path(), 3, budget, &cache, &opts).unwrap();
+            let mut buf = Vec::new();
+            generate_to(dir.path(), 3, budget, &cache, &opts, &mut buf).unwrap();
+            assert_eq!(String::from_utf8(buf).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn token_budget_drops_deepest_files_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/net/proto")).unwrap();
+        std::fs::write(root.join("main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(root.join("src/lib.rs"), "pub fn run() {}\n").unwrap();
+        std::fs::write(root.join("src/net/http.rs"), "pub struct Client;\n").unwrap();
+        for name in ["a", "b", "c", "d"] {
+            let body =
+                format!("pub struct Message{name}Request;\npub struct Message{name}Reply;\n");
+            std::fs::write(root.join(format!("src/net/proto/{name}.rs")), body).unwrap();
+        }
+
+        let cache = OutlineCache::new();
+        let full = generate(root, 3, None, &cache).unwrap();
+        let budget = 60;
+        assert!(estimate_tokens(full.len() as u64) > budget, "got:\n{full}");
+
+        let out = generate(root, 3, Some(budget), &cache).unwrap();
+        assert!(estimate_tokens(out.len() as u64) <= budget, "got:\n{out}");
+        let expected = format!(
+            "# Map: {} (depth 3)\n\
+main.rs: main\n\
+src/\n  \
+lib.rs: run\n  \
+net/\n    \
+http.rs: Client\n    \
+proto/\n\
+... 4 deeper files omitted (budget: 60)",
+            root.display()
+        );
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn files_sort_by_name_or_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("b.txt"), "x".repeat(400)).unwrap();
+        std::fs::write(root.join("c.txt"), "x".repeat(40)).unwrap();
+        std::fs::write(root.join("a.txt"), "x".repeat(4)).unwrap();
+
+        let cache = OutlineCache::new();
+        let files = |sort| {
+            let opts = MapOptions {
+                sort,
+                ..MapOptions::default()
+            };
+            let out = generate_with(root, 3, None, &cache, &opts).unwrap();
+            out.lines()
+                .skip(1)
+                .map(|l| l.split(' ').next().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(files(SortOrder::Name), ["a.txt", "b.txt", "c.txt"]);
+        assert_eq!(files(SortOrder::Size), ["b.txt", "c.txt", "a.txt"]);
+        assert_eq!("lines".parse(), Ok(SortOrder::LineCount));
+    }
+
+    #[test]
+    fn files_note_line_counts_and_sizes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("server.go"), "package main\n\nfunc Serve() {}\n").unwrap();
+        std::fs::write(root.join("notes.txt"), "one\ntwo").unwrap();
+        std::fs::write(root.join("big.txt"), "x\n".repeat(1024)).unwrap();
+
+        let opts = MapOptions {
+            show_lines: true,
+            show_size: true,
+            ..MapOptions::default()
+        };
+        let out = generate_with(root, 3, None, &OutlineCache::new(), &opts).unwrap();
+        let expected = format!(
+            "# Map: {} (depth 3)\n\
+big.txt (~512 tokens, 1024 lines, 2KB)\n\
+notes.txt (~2 tokens, 2 lines, 7B)\n\
+server.go (3 lines, 30B): Serve\n",
+            root.display()
+        );
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn languages_keep_only_matching_files() {
+        let root = &polyglot();
+        let opts = MapOptions {
+            languages: vec![Lang::Rust, Lang::Python],
+            ..MapOptions::default()
+        };
+        let out = generate_with(root, 3, None, &OutlineCache::new(), &opts).unwrap();
+        let expected = format!(
+            "# Map: {} (depth 3)\n\
+             scripts/\n  \
+             report.py: Config, StockLevel, is_low, Report, __init__, add, extend, write, load_levels...\n\
+             src/\n  \
+             lib.rs: ItemId, InventoryError, UnknownItem, Insufficient, InventoryError, fmt, Confi...\n",
+            root.display()
+        );
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn directories_emptied_by_filters_are_pruned() {
+        let root = &polyglot();
+        let mut opts = MapOptions {
+            languages: vec![Lang::Go],
+            ..MapOptions::default()
+        };
+        let cache = OutlineCache::new();
+        let out = generate_with(root, 3, None, &cache, &opts).unwrap();
+        let header = format!("# Map: {} (depth 3)\n", root.display());
+        let server = "server/\n  main.go: Config, StockLevel, Store, NewStore, Store, Level, Reserve, stockHandler, main\n";
+        assert_eq!(out, format!("{header}{server}"));
+
+        opts.keep_empty_dirs = true;
+        let out = generate_with(root, 3, None, &cache, &opts).unwrap();
+        assert_eq!(out, format!("{header}scripts/\n{server}src/\nweb/\n"));
+    }
+
+    #[test]
+    fn mermaid_diagram_of_a_polyglot_tree() {
+        let root = &polyglot();
+        let cache = OutlineCache::new();
+        let dirs = MapOptions {
+            format: MapFormat::Mermaid { files: false },
+            ..MapOptions::default()
+        };
+        let out = generate_with(root, 3, None, &cache, &dirs).unwrap();
+        let expected = r#"graph TD
+  root["polyglot/"]
+  d_scripts["scripts/"]
+  root --> d_scripts
+  d_server["server/"]
+  root --> d_server
+  d_src["src/"]
+  root --> d_src
+  d_web["web/"]
+  root --> d_web
+"#;
+        assert_eq!(out, expected);
+
+        let files = MapOptions {
+            format: MapFormat::Mermaid { files: true },
+            ..MapOptions::default()
+        };
+        // The budget doesn't cut a diagram.
+        let out = generate_with(root, 3, Some(10), &cache, &files).unwrap();
+        let expected = r#"graph TD
+  root["polyglot/"]
+  f_README_2emd["README.md"]
+  root --> f_README_2emd
+  d_scripts["scripts/"]
+  root --> d_scripts
+  f_scripts_2freport_2epy["report.py"]
+  d_scripts --> f_scripts_2freport_2epy
+  d_server["server/"]
+  root --> d_server
+  f_server_2fReservations_2ejava["Reservations.java"]
+  d_server --> f_server_2fReservations_2ejava
+  f_server_2fmain_2ego["main.go"]
+  d_server --> f_server_2fmain_2ego
+  d_src["src/"]
+  root --> d_src
+  f_src_2flib_2ers["lib.rs"]
+  d_src --> f_src_2flib_2ers
+  d_web["web/"]
+  root --> d_web
+  f_web_2fclient_2ets["client.ts"]
+  d_web --> f_web_2fclient_2ets
+"#;
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn mermaid_ids_and_labels_are_escaped() {
+        assert_eq!(mermaid_id('d', Path::new("api-v1")), "d_api_2dv1");
+        assert_eq!(
+            mermaid_id('f', Path::new("web/\"quoted\".rs")),
+            "f_web_2f_22quoted_22_2ers"
+        );
+        assert_eq!(
+            mermaid_label("\"a\" <b> #c"),
+            "#quot;a#quot; #lt;b#gt; #35;c"
+        );
+    }
+
+    #[test]
+    fn search_in_reuses_one_outline_set() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/order.rs"),
+            "pub struct Order;\n\nimpl Order {\n    pub fn total(&self) -> u32 {\n        0\n    }\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("src/cart.py"),
+            "def order_total(items):\n    return 0\n",
+        )
+        .unwrap();
+
+        let set = OutlineSet::build(dir.path(), &MapOptions::default()).unwrap();
+        assert_eq!(set.len(), 2);
+        // The set is self-contained: later searches don't read the files.
+        std::fs::remove_dir_all(dir.path().join("src")).unwrap();
+
+        let found = |query: &str, opts: &SearchOptions| -> Vec<String> {
+            search_in(&set, query, opts, NameMatch::Tiered)
+                .iter()
+                .map(|h| format!("{}:{} {}", h.path.display(), h.start_line, h.name))
+                .collect()
+        };
+        let opts = SearchOptions::default();
+        assert_eq!(
+            found("order", &opts),
+            [
+                "src/order.rs:1 Order",
+                "src/cart.py:1 order_total",
+                "src/order.rs:3 impl Order",
+            ]
+        );
+        assert_eq!(
+            found("total", &opts),
+            ["src/order.rs:4 total", "src/cart.py:1 order_total"]
+        );
+
+        let types = SearchOptions {
+            kinds: "type".parse().unwrap(),
+            ..SearchOptions::default()
+        };
+        assert_eq!(found("order", &types), ["src/order.rs:1 Order"]);
+    }
+
+    #[test]
+    fn fuzzy_search_ranks_near_misses() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("lexer.rs"),
+            "pub fn tokenize() {}\n\npub fn retokenize_all() {}\n\npub fn parse() {}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("lex.py"),
+            "def tokenize(s):\n    pass\n\ndef token_size(s):\n    pass\n",
+        )
+        .unwrap();
+        let set = OutlineSet::build(dir.path(), &MapOptions::default()).unwrap();
+
+        let found = |opts: &SearchOptions, matching| -> Vec<String> {
+            search_in(&set, "tokeniz", opts, matching)
+                .iter()
+                .map(|h| format!("{}:{} {}", h.path.display(), h.start_line, h.name))
+                .collect()
+        };
+        let opts = SearchOptions::default();
+        assert_eq!(
+            found(&opts, NameMatch::Tiered),
+            [
+                "lex.py:1 tokenize",
+                "lexer.rs:1 tokenize",
+                "lexer.rs:3 retokenize_all",
+            ]
+        );
+
+        assert_eq!(
+            found(&opts, NameMatch::Fuzzy),
+            [
+                "lex.py:1 tokenize",
+                "lexer.rs:1 tokenize",
+                "lex.py:4 token_size",
+                "lexer.rs:3 retokenize_all",
+            ]
+        );
+        let top = SearchOptions {
+            limit: 1,
+            ..SearchOptions::default()
+        };
+        assert_eq!(found(&top, NameMatch::Fuzzy), ["lex.py:1 tokenize"]);
+    }
+
+    #[test]
+    fn exclude_tests_drops_test_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("tests")).unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "pub fn api() {}\n").unwrap();
+        std::fs::write(dir.path().join("lib_test.go"), "package lib\n").unwrap();
+        std::fs::write(dir.path().join("tests/it.rs"), "fn it() {}\n").unwrap();
+
+        let cache = OutlineCache::new();
+        let all = generate(dir.path(), 3, None, &cache).unwrap();
+        assert!(
+            all.contains("lib_test.go") && all.contains("it.rs"),
+            "got:\n{all}"
+        );
+
+        let opts = MapOptions {
+            exclude_tests: true,
+            ..MapOptions::default()
+        };
+        let out = generate_with(dir.path(), 3, None, &cache, &opts).unwrap();
+        assert!(out.contains("lib.rs: api"), "got:\n{out}");
+        assert!(!out.contains("lib_test.go"), "got:\n{out}");
+        assert!(!out.contains("it.rs"), "got:\n{out}");
+    }
+
+    #[test]
+    fn gitignored_paths_are_left_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("artifacts/debug")).unwrap();
+        std::fs::create_dir(root.join("gen")).unwrap();
+        std::fs::write(root.join(".gitignore"), "artifacts/\n*.log\n!keep.log\n").unwrap();
+        std::fs::write(root.join("gen/.gitignore"), "schema.rs\n").unwrap();
+        std::fs::write(root.join("lib.rs"), "pub fn api() {}\n").unwrap();
+        std::fs::write(root.join("artifacts/debug/build.rs"), "fn built() {}\n").unwrap();
+        std::fs::write(root.join("debug.log"), "noise\n").unwrap();
+        std::fs::write(root.join("keep.log"), "kept\n").unwrap();
+        std::fs::write(root.join("gen/mod.rs"), "pub fn generated() {}\n").unwrap();
+        std::fs::write(root.join("gen/schema.rs"), "pub struct Schema;\n").unwrap();
+
+        let cache = OutlineCache::new();
+        let out = generate(root, 3, None, &cache).unwrap();
+        assert!(out.contains("lib.rs: api"), "got:\n{out}");
+        assert!(out.contains("keep.log"), "got:\n{out}");
+        assert!(out.contains("mod.rs: generated"), "got:\n{out}");
+        assert!(!out.contains("artifacts"), "got:\n{out}");
+        assert!(!out.contains("debug.log"), "got:\n{out}");
+        assert!(!out.contains("schema.rs"), "got:\n{out}");
+
+        let opts = MapOptions {
+            include_ignored: true,
+            ..MapOptions::default()
+        };
+        let all = generate_with(root, 3, None, &cache, &opts).unwrap();
+        assert!(
+            all.contains("build.rs: built")
+                && all.contains("debug.log")
+                && all.contains("schema.rs"),
+            "got:\n{all}"
+        );
+    }
+
+    #[test]
+    fn exclude_globs_drop_files_and_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("proto/v1")).unwrap();
+        std::fs::create_dir(root.join("src")).unwrap();
+        std::fs::write(root.join("proto/v1/api.ts"), "export function call() {}\n").unwrap();
+        std::fs::write(root.join("src/app.ts"), "export function start() {}\n").unwrap();
+        std::fs::write(
+            root.join("src/client.generated.ts"),
+            "export function rpc() {}\n",
+        )
+        .unwrap();
+
+        let opts = MapOptions {
+            exclude: vec!["proto".into(), "*.generated.ts".into()],
+            ..MapOptions::default()
+        };
+        let out = generate_with(root, 3, None, &OutlineCache::new(), &opts).unwrap();
+        assert!(out.contains("app.ts: start"), "got:\n{out}");
+        assert!(!out.contains("proto"), "got:\n{out}");
+        assert!(!out.contains("client.generated.ts"), "got:\n{out}");
+
+        let bad = MapOptions {
+            exclude: vec!["[unclosed".into()],
+            ..MapOptions::default()
+        };
+        let err = generate_with(root, 3, None, &OutlineCache::new(), &bad).unwrap_err();
+        assert!(matches!(err, TilthError::InvalidQuery { .. }), "got: {err}");
+    }
+
+    #[test]
+    fn max_bytes_truncates_at_line_boundary() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["a.rs", "b.rs", "c.rs"] {
+            std::fs::write(dir.path().join(name), "fn one() {}\nfn two() {}\n").unwrap();
+        }
+        let cache = OutlineCache::new();
+        let full = generate(dir.path(), 3, None, &cache).unwrap();
+
+        let opts = MapOptions {
+            max_bytes: Some(full.len() - 1),
+            ..MapOptions::default()
+        };
+        let out = generate_with(dir.path(), 3, None, &cache, &opts).unwrap();
+        assert!(out.len() < full.len(), "got:\n{out}");
+        let (kept, notice) = out.rsplit_once('\n').unwrap();
+        assert!(full.starts_with(&format!("{kept}\n")), "got:\n{out}");
+        assert!(notice.starts_with("... truncated ("), "got:\n{out}");
+    }
+
+    #[test]
+    fn xref_collects_cross_file_usages() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("config.rs"),
+            "pub struct Config {}\n\npub fn load() -> Config {\n    Config {}\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("main.rs"),
+            "fn main() {\n    let c: Config = load();\n}\n",
+        )
+        .unwrap();
+        // Same name defined twice: each definition keeps its own entry.
+        std::fs::write(root.join("a.py"), "def helper():\n    pass\n\nhelper()\n").unwrap();
+        std::fs::write(root.join("b.ts"), "function helper() {}\nhelper();\n").unwrap();
+
+        let entries = xref(root, &SymbolIndex::new()).unwrap();
+        let find = |name: &str, file: &str| {
+            entries
+                .iter()
+                .find(|e| e.name == name && e.definition.path.ends_with(file))
+                .unwrap_or_else(|| panic!("no {name} in {file}: {entries:?}"))
+        };
+        let sites = |e: &XrefEntry| {
+            e.usages
+                .iter()
+                .map(|u| {
+                    format!(
+                        "{}:{}",
+                        u.path.file_name().unwrap().to_str().unwrap(),
+                        u.line
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let config = find("Config", "config.rs");
+        assert_eq!(config.definition.line, 1);
+        assert_eq!(sites(config), ["config.rs:3", "config.rs:4", "main.rs:2"]);
+        assert_eq!(sites(find("load", "config.rs")), ["main.rs:2"]);
+        assert_eq!(sites(find("helper", "a.py")), ["a.py:4"]);
+        assert_eq!(sites(find("helper", "b.ts")), ["b.ts:2"]);
+
+        let index = format_xref(&entries, root);
+        assert!(
+            index.contains("Config  config.rs:1 (3 usages)\n  config.rs:3\n"),
+            "got:\n{index}"
+        );
+    }
+
+    #[test]
+    fn symbol_index_is_sorted_across_languages() {
+        let root = &polyglot();
+        let plain = symbol_index(root).unwrap();
+        let (header, body) = plain.split_once('\n').unwrap();
+        assert!(header.ends_with("(75 symbols)"), "got:\n{plain}");
+        let lines: Vec<&str> = body.lines().collect();
+        for line in [
+            "Config  class  scripts/report.py:11",
+            "Config  export  web/client.ts:4",
+            "Config  struct  server/main.go:13",
+            "Config  struct  src/lib.rs:29",
+            "InventoryClient  export  web/client.ts:25",
+            "Level  fn  server/main.go:38",
+            "Notifier  interface  src/lib.rs:101",
+            "Reservations  class  server/Reservations.java:9",
+            "lowStock  export  web/client.ts:62",
+            "report_low_stock  fn  src/lib.rs:105",
+            "reserve  fn  src/lib.rs:72",
+        ] {
+            assert!(lines.contains(&line), "missing {line}:\n{plain}");
+        }
+        // Go methods are indexed, their receiver group is not.
+        assert!(!body.contains("Store  mod"), "got:\n{plain}");
+        // Exports are named by what they declare, not by their statement.
+        assert!(!body.contains("export interface"), "got:\n{plain}");
+        let mut sorted = lines.clone();
+        sorted.sort_by_key(|l| l.split("  ").next().unwrap());
+        assert_eq!(lines, sorted);
+
+        let tags = symbol_index_with(root, IndexFormat::Ctags).unwrap();
+        let names: Vec<&str> = tags
+            .lines()
+            .filter(|l| !l.starts_with("!_TAG_"))
+            .map(|l| l.split('\t').next().unwrap())
+            .collect();
+        let mut sorted = names.clone();
+        sorted.sort_unstable();
+        assert_eq!(names, sorted);
+    }
+
+    #[test]
+    fn symbol_index_lists_every_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        // Far past the rendered outline's 100-line cap.
+        let code = (0..300)
+            .map(|i| format!("fn f{i:03}() {{}}\n"))
+            .collect::<Vec<_>>()
+            .concat();
+        std::fs::write(dir.path().join("many.rs"), code).unwrap();
+
+        let plain = symbol_index(dir.path()).unwrap();
+        assert!(plain.starts_with(&format!(
+            "# Symbols: {} (300 symbols)\n",
+            dir.path().display()
+        )));
+        assert!(plain.ends_with("f299  fn  many.rs:300\n"), "got:\n{plain}");
+    }
+
+    #[test]
+    fn tags_file_uses_ctags_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("order.rs"),
+            "pub struct Order;\n\npub const LIMIT: u32 = 3;\n\npub fn apply() {}\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("shapes.py"), "class Shape:\n    pass\n").unwrap();
+        // Same walk as the map: SKIP_DIRS are left out.
+        std::fs::create_dir(root.join("node_modules")).unwrap();
+        std::fs::write(root.join("node_modules/dep.js"), "function dep() {}\n").unwrap();
+
+        let tags_path = root.join("tags");
+        write_tags(root, &tags_path).unwrap();
+        let tags = std::fs::read_to_string(&tags_path).unwrap();
+        assert!(
+            tags.starts_with("!_TAG_FILE_FORMAT\t2\t/extended format/\n"),
+            "got:\n{tags}"
+        );
+        assert!(
+            tags.contains("\nOrder\torder.rs\t1;\"\ts\n"),
+            "got:\n{tags}"
+        );
+        assert!(
+            tags.contains("\nLIMIT\torder.rs\t3;\"\tC\n"),
+            "got:\n{tags}"
+        );
+        assert!(
+            tags.contains("\napply\torder.rs\t5;\"\tf\n"),
+            "got:\n{tags}"
+        );
+        assert!(
+            tags.contains("\nShape\tshapes.py\t1;\"\tc\n"),
+            "got:\n{tags}"
+        );
+        assert!(!tags.contains("dep"), "got:\n{tags}");
+    }
+
+    #[cfg(feature = "archive")]
+    const ARCHIVE_FILES: &[(&str, &str)] = &[
+        (
+            "pkg-1.0/src/lib.rs",
+            "pub fn parse() {}\npub struct Token;\n",
+        ),
+        ("pkg-1.0/util.py", "def helper():\n    pass\n"),
+        ("pkg-1.0/node_modules/dep/index.js", "function dep() {}\n"),
+    ];
+
+    #[cfg(feature = "archive")]
+    fn assert_archive_map(out: &str) {
+        assert!(out.contains("pkg-1.0/"), "got:\n{out}");
+        assert!(out.contains("lib.rs: parse, Token"), "got:\n{out}");
+        assert!(out.contains("util.py: helper"), "got:\n{out}");
+        assert!(!out.contains("index.js"), "got:\n{out}");
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn maps_tar_gz_without_extracting() {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        for (path, body) in ARCHIVE_FILES {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(body.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, body.as_bytes())
+                .unwrap();
+        }
+        let bytes = builder.into_inner().unwrap().finish().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("pkg-1.0.tar.gz");
+        std::fs::write(&archive, bytes).unwrap();
+
+        let out = generate(&archive, 3, None, &OutlineCache::new()).unwrap();
+        assert_archive_map(&out);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn maps_zip_without_extracting() {
+        use std::io::Write as _;
+
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (path, body) in ARCHIVE_FILES {
+            writer
+                .start_file(*path, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(body.as_bytes()).unwrap();
+        }
+        let bytes = writer.finish().unwrap().into_inner();
+
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("pkg-1.0.zip");
+        std::fs::write(&archive, bytes).unwrap();
+
+        let out = generate(&archive, 3, None, &OutlineCache::new()).unwrap();
+        assert_archive_map(&out);
+    }
+
+    #[test]
+    fn missing_or_file_scope_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        std::fs::write(&file, "").unwrap();
+        let missing = dir.path().join("nope");
+        let cache = OutlineCache::new();
+
+        let err = generate(&missing, 3, None, &cache).unwrap_err();
+        assert_eq!(err.to_string(), format!("not found: {}", missing.display()));
+        let err = generate(&file, 3, None, &cache).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("{} [not a directory]", file.display())
+        );
     }
 }
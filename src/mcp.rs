@@ -395,6 +395,7 @@ fn tool_read(
     edit_mode: bool,
 ) -> Result<String, String> {
     let budget = args.get("budget").and_then(serde_json::Value::as_u64);
+    let outline = outline_options(args)?;
 
     // Multi-file batch read (capped at 20 to bound I/O)
     if let Some(paths_arr) = args.get("paths").and_then(|v| v.as_array()) {
@@ -430,7 +431,7 @@ fn tool_read(
             let path_str = p.as_str().ok_or("paths must be an array of strings")?;
             let path = PathBuf::from(path_str);
             session.record_read(&path);
-            match crate::read::read_file(&path, None, false, cache, edit_mode) {
+            match crate::read::read_file(&path, None, false, cache, edit_mode, &outline) {
                 Ok(output) => results.push(output),
                 Err(e) => results.push(format!("# {} — error: {}", path.display(), e)),
             }
@@ -452,11 +453,23 @@ fn tool_read(
         .unwrap_or(false);
 
     session.record_read(&path);
-    let mut output = crate::read::read_file(&path, section, full, cache, edit_mode)
+    if let Some(name) = args.get("card").and_then(|v| v.as_str()) {
+        let output = crate::read::card::card(&path, name).map_err(|e| e.to_string())?;
+        return Ok(apply_budget(output, budget));
+    }
+    let mut output = crate::read::read_file(&path, section, full, cache, edit_mode, &outline)
         .map_err(|e| e.to_string())?;
 
-    // Append related-file hint for outlined code files (not section reads, not batch).
-    if section.is_none() && crate::read::would_outline(&path) {
+    let inline_imports = args
+        .get("inline_imports")
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false);
+
+    // Inlined imports replace the related-file hint, which would only repeat their paths.
+    if section.is_none() && inline_imports {
+        output.push_str(&crate::read::imports::inline_imports(&path, cache));
+    } else if section.is_none() && outline.is_default() && crate::read::would_outline(&path) {
+        // Append related-file hint for outlined code files (not section reads, not batch).
         let related = crate::read::imports::resolve_related_files(&path);
         if !related.is_empty() {
             output.push_str("\n\n> Related: ");
@@ -472,6 +485,47 @@ fn tool_read(
     Ok(apply_budget(output, budget))
 }
 
+/// Parse the optional `outline` style, `body_lines`, `max_name_len`,
+/// `byte_ranges`, `changed_lines`, `collapse_accessors`, `unused_imports` and
+/// `format_version` arguments shared by read calls.
+fn outline_options(args: &Value) -> Result<crate::read::outline::OutlineOptions, String> {
+    let mut opts = crate::read::outline::OutlineOptions::default();
+    if let Some(style) = args.get("outline").and_then(|v| v.as_str()) {
+        opts.style = style.parse()?;
+    }
+    if let Some(n) = args.get("body_lines").and_then(serde_json::Value::as_u64) {
+        opts.body_lines = n as usize;
+    }
+    if let Some(n) = args.get("max_name_len").and_then(serde_json::Value::as_u64) {
+        opts.max_name_len = Some(n as usize);
+    }
+    if let Some(b) = args.get("byte_ranges").and_then(serde_json::Value::as_bool) {
+        opts.byte_ranges = b;
+    }
+    if let Some(lines) = args.get("changed_lines").and_then(|v| v.as_str()) {
+        opts.changed_lines = Some(lines.parse()?);
+    }
+    if let Some(b) = args
+        .get("collapse_accessors")
+        .and_then(serde_json::Value::as_bool)
+    {
+        opts.collapse_accessors = b;
+    }
+    if let Some(b) = args
+        .get("unused_imports")
+        .and_then(serde_json::Value::as_bool)
+    {
+        opts.unused_imports = b;
+    }
+    if let Some(n) = args
+        .get("format_version")
+        .and_then(serde_json::Value::as_u64)
+    {
+        opts.format_version = n.to_string().parse()?;
+    }
+    Ok(opts)
+}
+
 fn tool_search(
     args: &Value,
     cache: &OutlineCache,
@@ -498,7 +552,12 @@ fn tool_search(
         .map(PathBuf::from);
     let context = context_path.as_deref();
     let glob = args.get("glob").and_then(|v| v.as_str());
-    let budget = args.get("budget").and_then(serde_json::Value::as_u64);
+    let config = crate::config::ProjectConfig::load(&scope).map_err(|e| e.to_string())?;
+    let budget = args
+        .get("budget")
+        .and_then(serde_json::Value::as_u64)
+        .or(config.budget);
+    let search_opts = search_options(args, config.options.search)?;
 
     let output = match kind {
         "symbol" => {
@@ -512,7 +571,16 @@ fn tool_search(
                 1 => {
                     session.record_search(queries[0]);
                     crate::search::search_symbol_expanded(
-                        queries[0], &scope, cache, session, index, bloom, expand, context, glob,
+                        queries[0],
+                        &scope,
+                        cache,
+                        session,
+                        index,
+                        bloom,
+                        expand,
+                        context,
+                        glob,
+                        &search_opts,
                     )
                 }
                 2..=5 => {
@@ -520,7 +588,16 @@ fn tool_search(
                         session.record_search(q);
                     }
                     crate::search::search_multi_symbol_expanded(
-                        &queries, &scope, cache, session, index, bloom, expand, context, glob,
+                        &queries,
+                        &scope,
+                        cache,
+                        session,
+                        index,
+                        bloom,
+                        expand,
+                        context,
+                        glob,
+                        &search_opts,
                     )
                 }
                 _ => {
@@ -534,15 +611,33 @@ fn tool_search(
         "content" => {
             session.record_search(query);
             crate::search::search_content_expanded(
-                query, &scope, cache, session, expand, context, glob,
+                query,
+                &scope,
+                cache,
+                session,
+                expand,
+                context,
+                glob,
+                &search_opts,
             )
         }
         "regex" => {
             session.record_search(query);
-            let result = crate::search::content::search(query, &scope, true, context, glob)
-                .map_err(|e| e.to_string())?;
+            let result =
+                crate::search::content::search(query, &scope, true, context, glob, &search_opts)
+                    .map_err(|e| e.to_string())?;
             crate::search::format_raw_result(&result, cache)
         }
+        "symbol_regex" => {
+            session.record_search(query);
+            let opts = crate::search::SearchOptions {
+                regex: true,
+                ..search_opts
+            };
+            crate::search::search_symbol_expanded(
+                query, &scope, cache, session, index, bloom, expand, context, glob, &opts,
+            )
+        }
         "callers" => {
             session.record_search(query);
             crate::search::callers::search_callers_expanded(
@@ -551,7 +646,7 @@ fn tool_search(
         }
         _ => {
             return Err(format!(
-                "unknown search kind: {kind}. Use: symbol, content, regex, callers"
+                "unknown search kind: {kind}. Use: symbol, content, regex, symbol_regex, callers"
             ))
         }
     }
@@ -562,6 +657,39 @@ fn tool_search(
     Ok(result)
 }
 
+/// Parse the optional paging and filter arguments of search calls over `opts`,
+/// the project defaults from `.tilth.toml`.
+fn search_options(
+    args: &Value,
+    mut opts: crate::search::SearchOptions,
+) -> Result<crate::search::SearchOptions, String> {
+    if let Some(offset) = args.get("offset").and_then(Value::as_u64) {
+        opts.offset = offset as usize;
+    }
+    if let Some(limit) = args.get("limit").and_then(Value::as_u64) {
+        opts.limit = (limit as usize).max(1);
+    }
+    if let Some(exclude_tests) = args.get("exclude_tests").and_then(Value::as_bool) {
+        opts.exclude_tests = exclude_tests;
+    }
+    if let Some(kinds) = args.get("kinds").and_then(Value::as_str) {
+        opts.kinds = kinds.parse()?;
+    }
+    if let Some(role) = args.get("only").and_then(Value::as_str) {
+        opts.role = role.parse()?;
+    }
+    if let Some(ignore) = args.get("ignore_case_style").and_then(Value::as_bool) {
+        opts.ignore_case_style = ignore;
+    }
+    if let Some(ignore) = args.get("ignore_case").and_then(Value::as_bool) {
+        opts.ignore_case = ignore;
+    }
+    if let Some(substring) = args.get("substring").and_then(Value::as_bool) {
+        opts.substring = substring;
+    }
+    Ok(opts)
+}
+
 fn tool_files(args: &Value, cache: &OutlineCache) -> Result<String, String> {
     let pattern = args
         .get("pattern")
@@ -724,7 +852,7 @@ fn tool_edit(
 /// Falls back to cwd when scope is invalid, with a warning message.
 fn resolve_scope(args: &Value) -> (PathBuf, Option<String>) {
     let raw_str = args.get("scope").and_then(|v| v.as_str()).unwrap_or(".");
-    let raw: PathBuf = raw_str.into();
+    let raw = crate::lang::expand_home(std::path::Path::new(raw_str)).into_owned();
     let resolved = raw.canonicalize().unwrap_or_else(|_| raw.clone());
     let cwd = std::env::current_dir().unwrap_or_else(|_| ".".into());
     if resolved == cwd {
@@ -885,9 +1013,9 @@ fn tool_definitions(edit_mode: bool) -> Vec<Value> {
                     },
                     "kind": {
                         "type": "string",
-                        "enum": ["symbol", "content", "regex", "callers"],
+                        "enum": ["symbol", "content", "regex", "symbol_regex", "callers"],
                         "default": "symbol",
-                        "description": "Search type. symbol: structural definitions + usages. content: literal text. regex: regex pattern. callers: find all call sites of a symbol."
+                        "description": "Search type. symbol: structural definitions + usages. content: literal text. regex: regex pattern. symbol_regex: regex over symbol names (e.g. 'handle.*Request'), with the usages of every matched name. callers: find all call sites of a symbol."
                     },
                     "expand": {
                         "type": "number",
@@ -905,6 +1033,46 @@ fn tool_definitions(edit_mode: bool) -> Vec<Value> {
                     "glob": {
                         "type": "string",
                         "description": "File pattern filter. Whitelist: \"*.rs\" (only Rust files). Exclude: \"!*.test.ts\" (skip test files). Brace expansion: \"*.{go,rs}\" (Go and Rust). Path patterns: \"src/**/*.ts\"."
+                    },
+                    "offset": {
+                        "type": "number",
+                        "default": 0,
+                        "description": "Skip this many ranked matches — for paging through large result sets."
+                    },
+                    "limit": {
+                        "type": "number",
+                        "default": 10,
+                        "description": "Max matches per page."
+                    },
+                    "exclude_tests": {
+                        "type": "boolean",
+                        "default": false,
+                        "description": "Skip test files: tests/ and __tests__/ dirs, *_test.go, test_*.py, *.test.ts, *.spec.js, *Test.java, etc."
+                    },
+                    "kinds": {
+                        "type": "string",
+                        "description": "Symbol search only: definition kinds to keep, comma-separated; prefix ! to drop. fn, class, struct, interface, alias, enum, type (all type kinds), const, var, prop, mod, macro, export. E.g. \"type\" or \"!var\"."
+                    },
+                    "only": {
+                        "type": "string",
+                        "enum": ["all", "definitions", "usages"],
+                        "default": "all",
+                        "description": "Symbol search only: return just definitions (go to definition) or just usages."
+                    },
+                    "ignore_case_style": {
+                        "type": "boolean",
+                        "default": false,
+                        "description": "Symbol search only: match across naming conventions — user_name, userName, UserName and user-name all match \"username\"."
+                    },
+                    "ignore_case": {
+                        "type": "boolean",
+                        "default": false,
+                        "description": "Symbol search only: match regardless of letter case — \"color\" finds Color."
+                    },
+                    "substring": {
+                        "type": "boolean",
+                        "default": false,
+                        "description": "Symbol search only: match inside identifiers too — \"set\" also finds settings and reset. By default the query must be a whole identifier."
                     }
                 }
             }
@@ -933,6 +1101,51 @@ fn tool_definitions(edit_mode: bool) -> Vec<Value> {
                         "default": false,
                         "description": "Force full content output, bypass smart outlining."
                     },
+                    "outline": {
+                        "type": "string",
+                        "enum": ["names", "stub", "grouped", "json"],
+                        "description": "Force an outline view at any file size. names: top-level symbol names only. stub: public declarations without bodies, like a .d.ts/.pyi. grouped: the standard outline sectioned by kind (types, functions, constants, ...). json: the outline entry tree as JSON."
+                    },
+                    "body_lines": {
+                        "type": "number",
+                        "description": "Show the first N lines of each function body under its outline entry, at any file size. Between a signature-only outline and full content."
+                    },
+                    "max_name_len": {
+                        "type": "number",
+                        "description": "Cut outline symbol names longer than this many bytes, ending them with '...'. Values below 3 count as 3. For generated code with very long identifiers."
+                    },
+                    "byte_ranges": {
+                        "type": "boolean",
+                        "default": false,
+                        "description": "Add byte offsets to outline entry ranges, e.g. [10-20 b340-890] (end exclusive), for slicing the file by bytes."
+                    },
+                    "changed_lines": {
+                        "type": "string",
+                        "description": "Outline only the symbols overlapping these lines, e.g. '12-30,88' — the functions and types a change touched."
+                    },
+                    "collapse_accessors": {
+                        "type": "boolean",
+                        "default": false,
+                        "description": "Collapse getter/setter pairs (Java getX/setX, TS get x/set x) into one 'X { get; set }' outline entry."
+                    },
+                    "unused_imports": {
+                        "type": "boolean",
+                        "default": false,
+                        "description": "List imported names the file never mentions under the outline's import summary. A textual guess: trait imports used only for their methods look unused."
+                    },
+                    "format_version": {
+                        "type": "number",
+                        "enum": [1, 2, 3, 4],
+                        "description": "Pin the outline text layout to this version so parsers keep working across upgrades. Defaults to the latest."
+                    },
+                    "card": {
+                        "type": "string",
+                        "description": "Symbol name. Returns only its signature, full doc comment and line range — no body. Single-file reads only."
+                    },
+                    "inline_imports": {
+                        "type": "boolean",
+                        "description": "Append a short outline of each locally-imported file (one level deep, max 5). Single-file reads only."
+                    },
                     "budget": {
                         "type": "number",
                         "description": "Max tokens in response."
@@ -1208,7 +1421,7 @@ mod tests {
         // With no arg, defaults to "." which is cwd
         let cwd = std::env::current_dir().unwrap();
         // The function returns "." when resolved == cwd
-        assert!(scope == PathBuf::from(".") || scope == cwd);
+        assert!(scope == Path::new(".") || scope == cwd);
         assert!(warning.is_none());
     }
 
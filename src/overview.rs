@@ -68,7 +68,7 @@ fn fingerprint_inner(root: &Path) -> String {
                 }
             })
             .collect();
-        mods.sort_by(|a, b| b.1.cmp(&a.1)); // most files first
+        mods.sort_by_key(|m| std::cmp::Reverse(m.1)); // most files first
 
         // If all modules (or at least most) share a common top-level prefix
         // (e.g., all are "src/..."), strip it so we display short names
@@ -135,7 +135,7 @@ fn fingerprint_inner(root: &Path) -> String {
             !lower.split('/').any(|part| non_source.contains(&part))
         });
         // Sort by file count descending, truncate to 10, extract names
-        mods.sort_by(|a, b| b.1.cmp(&a.1));
+        mods.sort_by_key(|m| std::cmp::Reverse(m.1));
         mods.truncate(10);
         mods.into_iter().map(|(name, _)| name).collect()
     };
@@ -252,6 +252,16 @@ fn lang_display_name(lang: Lang) -> &'static str {
         Lang::CSharp => "C#",
         Lang::Dockerfile => "Docker",
         Lang::Make => "Make",
+        Lang::Nix => "Nix",
+        Lang::R => "R",
+        Lang::Lua => "Lua",
+        Lang::Perl => "Perl",
+        Lang::Zig => "Zig",
+        Lang::OCaml => "OCaml",
+        Lang::Toml => "TOML",
+        Lang::Yaml => "YAML",
+        Lang::Vue => "Vue",
+        Lang::Registered(id) => crate::lang::registry::name(id),
     }
 }
 
@@ -369,7 +379,7 @@ fn walk_dir(
                 *lang_counts.entry(lang).or_insert(0) += 1;
 
                 // Track size for hot files
-                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                let size = entry.metadata().map_or(0, |m| m.len());
                 if let Ok(rel) = path.strip_prefix(root) {
                     let rel_str = rel.to_string_lossy().to_string();
 
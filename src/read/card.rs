@@ -0,0 +1,109 @@
+//! Declaration cards: one symbol's signature, full doc comment and location,
+//! without its body — the cheap answer to "what is X?".
+
+use std::fs;
+use std::path::Path;
+
+use crate::error::TilthError;
+use crate::lang::detect_file_type;
+use crate::lang::outline::get_outline_entries;
+use crate::read::outline::{code, stub};
+use crate::types::{FileType, Lang, OutlineEntry, OutlineKind};
+
+/// Render a card for every top-level or member symbol in `path` named `name`.
+/// Overloads and same-named members each get their own card.
+pub fn card(path: &Path, name: &str) -> Result<String, TilthError> {
+    crate::error::require_file(path)?;
+    let content = fs::read_to_string(path).map_err(|e| TilthError::from_io(path, e))?;
+    let FileType::Code(lang) = detect_file_type(path) else {
+        return Err(TilthError::InvalidQuery {
+            query: name.to_string(),
+            reason: "declaration cards need a code file".into(),
+        });
+    };
+    render(path, &content, lang, name).ok_or_else(|| TilthError::InvalidQuery {
+        query: name.to_string(),
+        reason: format!("no symbol named \"{name}\" in {}", path.display()),
+    })
+}
+
+fn render(path: &Path, content: &str, lang: Lang, name: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let entries = get_outline_entries(content, lang);
+
+    let mut found = Vec::new();
+    collect_named(&entries, name, &mut found);
+    if found.is_empty() {
+        return None;
+    }
+
+    let cards: Vec<String> = found
+        .iter()
+        .map(|entry| {
+            let mut card = format!(
+                "# {}:{}-{} [card]\n\n{}",
+                path.display(),
+                entry.start_line,
+                entry.end_line,
+                stub::full_signature(entry, &lines, lang)
+            );
+            if let Some(doc) = &entry.doc {
+                card.push_str("\n\n");
+                card.push_str(doc);
+            }
+            card
+        })
+        .collect();
+    Some(cards.join("\n\n"))
+}
+
+fn collect_named<'a>(entries: &'a [OutlineEntry], name: &str, out: &mut Vec<&'a OutlineEntry>) {
+    for entry in entries {
+        let matches = match entry.kind {
+            OutlineKind::Import => false,
+            OutlineKind::Export => code::export_name(&entry.name).as_deref() == Some(name),
+            _ => entry.name == name,
+        };
+        if matches {
+            out.push(entry);
+        }
+        collect_named(&entry.children, name, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAIN_RS: &str = "\
+use std::fmt;
+
+pub struct Config {
+    pub verbose: bool,
+}
+
+/// Build the default configuration.
+///
+/// Reads nothing from the environment; callers override fields afterwards.
+pub fn initialize() -> Config {
+    Config { verbose: false }
+}
+";
+
+    #[test]
+    fn card_has_signature_location_and_full_doc() {
+        let out = render(Path::new("src/main.rs"), MAIN_RS, Lang::Rust, "initialize").unwrap();
+        assert_eq!(
+            out,
+            "# src/main.rs:10-12 [card]\n\n\
+             pub fn initialize() -> Config\n\n\
+             Build the default configuration.\n\n\
+             Reads nothing from the environment; callers override fields afterwards."
+        );
+    }
+
+    #[test]
+    fn card_unknown_symbol() {
+        assert!(render(Path::new("src/main.rs"), MAIN_RS, Lang::Rust, "missing").is_none());
+    }
+}
@@ -1,13 +1,19 @@
 //! Resolve import statements to local file paths.
-//! Used by the MCP layer to hint related files after an outlined read.
+//! Used by the MCP layer to hint related files after an outlined read,
+//! and to inline imported outlines on request. Also flags imports that look
+//! unused, for the outline.
 
+use std::fmt::Write;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::cache::OutlineCache;
 use crate::lang::detect_file_type;
-use crate::types::{FileType, Lang};
+use crate::types::{FileType, Lang, OutlineEntry};
 
 const MAX_SUGGESTIONS: usize = 8;
+const MAX_INLINED: usize = 5;
+const INLINED_OUTLINE_LINES: usize = 15;
 
 /// Extract import sources from a code file and resolve them to existing local file paths.
 /// Returns empty Vec for non-code files, files with no imports, or when all imports are external.
@@ -49,6 +55,51 @@ pub fn resolve_related_files_with_content(file_path: &Path, content: &str) -> Ve
     results
 }
 
+/// Outline each local import of `file_path`, one level deep, for appending after
+/// the file's own view. The file itself is never inlined, so self and cyclic
+/// imports stop here. Returns an empty string when nothing resolves.
+pub fn inline_imports(file_path: &Path, cache: &OutlineCache) -> String {
+    let root = fs::canonicalize(file_path).unwrap_or_else(|_| file_path.to_path_buf());
+    let mut out = String::new();
+    let mut inlined = 0;
+
+    for path in resolve_related_files(file_path) {
+        if inlined >= MAX_INLINED {
+            break;
+        }
+        if fs::canonicalize(&path).is_ok_and(|p| p == root) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let mtime = fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+        let file_type = detect_file_type(&path);
+        let outline = cache.get_or_compute(&path, mtime, || {
+            super::outline::generate(&path, file_type, &content, content.as_bytes(), true)
+        });
+
+        let _ = write!(out, "\n\n## {}\n", path.display());
+        let mut lines = outline.lines();
+        for line in lines.by_ref().take(INLINED_OUTLINE_LINES) {
+            let _ = writeln!(out, "{line}");
+        }
+        let rest = lines.count();
+        if rest > 0 {
+            let _ = writeln!(out, "... {rest} more lines");
+        }
+        inlined += 1;
+    }
+
+    if !out.is_empty() {
+        out.insert_str(0, "\n\n# Imports");
+        out.truncate(out.trim_end().len());
+    }
+    out
+}
+
 pub(crate) fn is_import_line(line: &str, lang: Lang) -> bool {
     let trimmed = line.trim_start();
     match lang {
@@ -80,6 +131,156 @@ pub(crate) fn is_external(source: &str, lang: Lang) -> bool {
     }
 }
 
+/// Names from `imports` that never appear as a word outside the import
+/// statements themselves. A heuristic: macro-only, trait-method and re-export
+/// uses aren't seen, and same-named locals hide an unused import.
+pub(crate) fn unused_imports(imports: &[&OutlineEntry], lines: &[&str], lang: Lang) -> Vec<String> {
+    let in_import = |i: usize| {
+        imports
+            .iter()
+            .any(|e| (e.start_line as usize..=e.end_line as usize).contains(&(i + 1)))
+    };
+    let words: std::collections::HashSet<&str> = lines
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !in_import(*i))
+        .flat_map(|(_, l)| l.split(|c: char| !c.is_alphanumeric() && c != '_' && c != '$'))
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let mut unused = Vec::new();
+    for entry in imports {
+        let start = entry.start_line as usize - 1;
+        let end = (entry.end_line as usize).min(lines.len());
+        let text = lines.get(start..end).unwrap_or_default().join("\n");
+        for name in bound_names(&text, lang) {
+            if !words.contains(name.as_str()) && !unused.contains(&name) {
+                unused.push(name);
+            }
+        }
+    }
+    unused
+}
+
+/// Names an import statement brings into scope. Globs, side-effect imports,
+/// `_` aliases and Rust `pub use` re-exports bind nothing checkable.
+fn bound_names(text: &str, lang: Lang) -> Vec<String> {
+    let text = text.trim().trim_end_matches(';');
+    let last_word = |s: &str| s.split_whitespace().last().unwrap_or_default().to_string();
+    // `a as b` → `b`; a path → its last segment.
+    let binding = |item: &str, sep: &str| {
+        let item = item.trim();
+        match item.split_once(" as ") {
+            Some((_, alias)) => alias.trim().to_string(),
+            None => item.rsplit(sep).next().unwrap_or(item).trim().to_string(),
+        }
+    };
+    let names: Vec<String> = match lang {
+        Lang::Rust => {
+            if text.starts_with("pub") {
+                return Vec::new();
+            }
+            let path = text
+                .trim_start_matches("use ")
+                .replace(char::is_whitespace, " ");
+            match path.split_once('{') {
+                Some((prefix, group)) => group
+                    .trim_end_matches('}')
+                    .split(',')
+                    .map(|item| {
+                        if item.trim() == "self" {
+                            binding(prefix.trim_end_matches("::"), "::")
+                        } else {
+                            binding(item, "::")
+                        }
+                    })
+                    .collect(),
+                None => vec![binding(&path, "::")],
+            }
+        }
+        Lang::TypeScript | Lang::Tsx | Lang::JavaScript => {
+            let Some((clause, _)) = text.split_once(" from ") else {
+                return Vec::new();
+            };
+            let clause = clause
+                .trim_start_matches("import")
+                .trim_start()
+                .trim_start_matches("type ");
+            let mut names = Vec::new();
+            let (default, named) = match clause.split_once('{') {
+                Some((default, named)) => (default, named.trim_end().trim_end_matches('}')),
+                None => (clause, ""),
+            };
+            for item in default.split(',') {
+                if let Some(ns) = item.trim().strip_prefix("* as ") {
+                    names.push(ns.trim().to_string());
+                } else if !item.trim().is_empty() {
+                    names.push(item.trim().to_string());
+                }
+            }
+            names.extend(
+                named
+                    .split(',')
+                    .map(|item| binding(item.trim().trim_start_matches("type "), " ")),
+            );
+            names
+        }
+        Lang::Python => {
+            if let Some(rest) = text.strip_prefix("from ") {
+                let Some((module, items)) = rest.split_once(" import ") else {
+                    return Vec::new();
+                };
+                if module.trim() == "__future__" {
+                    return Vec::new();
+                }
+                items
+                    .trim_matches(|c: char| c == '(' || c == ')' || c.is_whitespace())
+                    .split(',')
+                    .map(|item| binding(item, " "))
+                    .collect()
+            } else {
+                text.trim_start_matches("import ")
+                    .split(',')
+                    .map(|item| match item.split_once(" as ") {
+                        Some((_, alias)) => alias.trim().to_string(),
+                        None => item
+                            .trim()
+                            .split('.')
+                            .next()
+                            .unwrap_or_default()
+                            .to_string(),
+                    })
+                    .collect()
+            }
+        }
+        Lang::Go => text
+            .trim_start_matches("import")
+            .lines()
+            .map(|l| l.trim().trim_matches(|c| c == '(' || c == ')').trim())
+            .filter(|l| l.ends_with('"'))
+            .map(|l| {
+                if let Some((alias, _)) = l.split_once(' ') {
+                    alias.to_string()
+                } else {
+                    let path = l.trim_matches('"');
+                    path.rsplit('/').next().unwrap_or(path).to_string()
+                }
+            })
+            .collect(),
+        Lang::Java | Lang::Kotlin | Lang::Scala => vec![binding(&last_word(text), ".")],
+        // `const std = @import("std")` binds `std`.
+        Lang::Zig => text
+            .split_once(" = ")
+            .map(|(lhs, _)| vec![last_word(lhs.split(':').next().unwrap_or(lhs))])
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+    names
+        .into_iter()
+        .filter(|n| !n.is_empty() && n != "_" && n != "*" && n != ".")
+        .collect()
+}
+
 fn resolve(dir: &Path, source: &str, lang: Lang) -> Option<PathBuf> {
     match lang {
         Lang::Rust => resolve_rust(dir, source),
@@ -207,3 +408,40 @@ fn resolve_c_include(dir: &Path, source: &str) -> Option<PathBuf> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inline_imports_appends_imported_outline() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("main.ts");
+        fs::write(
+            &main,
+            "import { helper } from './utils';\nimport { main } from './main';\n\nhelper();\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("utils.ts"),
+            "export function helper() {}\nexport const LIMIT = 10;\n",
+        )
+        .unwrap();
+
+        let out = inline_imports(&main, &OutlineCache::new());
+        assert!(out.starts_with("\n\n# Imports"), "got:\n{out}");
+        assert!(out.contains("utils.ts"), "got:\n{out}");
+        assert!(out.contains("helper"), "got:\n{out}");
+        assert!(out.contains("LIMIT"), "got:\n{out}");
+        // Self-import is not inlined.
+        assert!(!out.contains("main.ts"), "got:\n{out}");
+    }
+
+    #[test]
+    fn inline_imports_empty_without_local_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("main.ts");
+        fs::write(&main, "import React from 'react';\n").unwrap();
+        assert!(inline_imports(&main, &OutlineCache::new()).is_empty());
+    }
+}
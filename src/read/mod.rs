@@ -1,3 +1,4 @@
+pub mod card;
 pub mod imports;
 pub mod outline;
 
@@ -10,6 +11,7 @@ use crate::cache::OutlineCache;
 use crate::error::TilthError;
 use crate::format;
 use crate::lang::detect_file_type;
+use crate::read::outline::{OutlineOptions, OutlineStyle};
 use crate::types::{estimate_tokens, FileType, ViewMode};
 
 pub(crate) const TOKEN_THRESHOLD: u64 = 6_000;
@@ -27,12 +29,15 @@ fn full_read_size_cap() -> u64 {
 }
 
 /// Main entry point for read mode. Routes through the decision tree.
+/// A non-default `outline` is an explicit request for that view — it bypasses
+/// the full-vs-outline decision for any file size.
 pub fn read_file(
     path: &Path,
     section: Option<&str>,
     full: bool,
     cache: &OutlineCache,
     edit_mode: bool,
+    outline: &OutlineOptions,
 ) -> Result<String, TilthError> {
     let meta = match fs::metadata(path) {
         Ok(m) => m,
@@ -55,8 +60,13 @@ pub fn read_file(
         }
     };
 
-    // Directory → list contents
+    // Directory → list contents. A line range only makes sense for a file.
     if meta.is_dir() {
+        if section.is_some() {
+            return Err(TilthError::IsADirectory {
+                path: path.to_path_buf(),
+            });
+        }
         return list_directory(path);
     }
 
@@ -88,6 +98,7 @@ pub fn read_file(
         return Ok(format::binary_header(path, byte_len, mime));
     }
 
+    let buf = buf.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(buf);
     let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
 
     // Generated
@@ -107,6 +118,27 @@ pub fn read_file(
     let content = String::from_utf8_lossy(buf);
     let line_count = memchr::memchr_iter(b'\n', buf).count() as u32 + 1;
 
+    if !outline.is_default() {
+        let file_type = detect_file_type(path);
+        let view = outline::generate_with(
+            path,
+            file_type,
+            &content,
+            buf,
+            byte_len > FILE_SIZE_CAP,
+            outline,
+        );
+        let mode = match (file_type, outline.style) {
+            (FileType::Code(_), OutlineStyle::Names) => ViewMode::Names,
+            (FileType::Code(_), OutlineStyle::Stub) => ViewMode::Stub,
+            (FileType::Code(_), OutlineStyle::Json) => ViewMode::Json,
+            (FileType::StructuredData, _) => ViewMode::Keys,
+            _ => ViewMode::Outline,
+        };
+        let header = format::file_header(path, byte_len, line_count, mode);
+        return Ok(format!("{header}\n\n{view}"));
+    }
+
     // Guard: full=true on very large files. Return outline + warning instead of
     // dumping megabytes that would blow up the MCP client's timeout/memory.
     let cap = full_read_size_cap();
@@ -160,9 +192,7 @@ pub fn read_file(
 /// Would this file produce an outline (rather than full content) in default read mode?
 /// Used by the MCP layer to decide whether to append related-file hints.
 pub fn would_outline(path: &Path) -> bool {
-    std::fs::metadata(path)
-        .map(|m| !m.is_dir() && estimate_tokens(m.len()) > TOKEN_THRESHOLD)
-        .unwrap_or(false)
+    std::fs::metadata(path).is_ok_and(|m| !m.is_dir() && estimate_tokens(m.len()) > TOKEN_THRESHOLD)
 }
 
 /// Resolve a heading address to a line range in a markdown file.
@@ -443,6 +473,49 @@ fn mime_from_ext(path: &Path) -> &'static str {
 mod tests {
     use super::*;
 
+    #[test]
+    fn section_on_directory_is_an_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache = OutlineCache::new();
+        let opts = OutlineOptions::default();
+        let err = read_file(tmp.path(), Some("1-5"), false, &cache, false, &opts).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("{} [is a directory, expected a file]", tmp.path().display())
+        );
+        // Without a section, a directory is listed rather than rejected.
+        assert!(read_file(tmp.path(), None, false, &cache, false, &opts).is_ok());
+    }
+
+    #[test]
+    fn leading_bom_is_stripped() {
+        let code = "\u{feff}pub fn first() {}\npub fn second() {}\n";
+        let entries = crate::lang::outline::get_outline_entries(code, crate::types::Lang::Rust);
+        let names: Vec<(&str, u32)> = entries
+            .iter()
+            .map(|e| (e.name.as_str(), e.start_line))
+            .collect();
+        assert_eq!(names, [("first", 1), ("second", 2)]);
+
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("lib.rs");
+        fs::write(&path, code).unwrap();
+        let cache = OutlineCache::new();
+        let out = read_file(
+            &path,
+            None,
+            false,
+            &cache,
+            false,
+            &OutlineOptions::default(),
+        )
+        .unwrap();
+        assert!(
+            out.ends_with("\n\npub fn first() {}\npub fn second() {}\n"),
+            "{out}"
+        );
+    }
+
     #[test]
     fn heading_found() {
         let input = b"# Title\nSome content\n## Section\nSection content\n";
@@ -524,7 +597,8 @@ mod tests {
         std::env::set_var("TILTH_FULL_SIZE_CAP", "100");
 
         let cache = OutlineCache::new();
-        let result = read_file(&path, None, true, &cache, false).unwrap();
+        let result =
+            read_file(&path, None, true, &cache, false, &OutlineOptions::default()).unwrap();
 
         // Should contain the warning, not the full file content
         assert!(
@@ -0,0 +1,110 @@
+//! Cargo build scripts: the outline of a `build.rs` is usually just `fn main`,
+//! while what it configures lives in `println!("cargo:...")` directives.
+//! Those are listed under the regular outline.
+
+use std::path::Path;
+
+use crate::lang::treesitter::node_text_simple;
+use crate::types::Lang;
+
+/// `build.rs` anywhere in the tree — Cargo's default build script name.
+pub fn is_build_script(path: &Path) -> bool {
+    path.file_name().is_some_and(|n| n == "build.rs")
+}
+
+/// `[line] cargo:...` for every `println!`/`print!` whose format string is a
+/// cargo directive, in source order. Format placeholders are left as written.
+pub fn directives(content: &str, max_lines: usize) -> Vec<String> {
+    let Some(language) = crate::lang::outline::outline_language(Lang::Rust) else {
+        return Vec::new();
+    };
+    let mut parser = tree_sitter::Parser::new();
+    if parser.set_language(&language).is_err() {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(content, None) else {
+        return Vec::new();
+    };
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut out = Vec::new();
+    collect(tree.root_node(), &lines, max_lines, &mut out);
+    out
+}
+
+fn collect(node: tree_sitter::Node, lines: &[&str], max_lines: usize, out: &mut Vec<String>) {
+    if out.len() >= max_lines {
+        return;
+    }
+    if node.kind() == "macro_invocation" {
+        if let Some(directive) = directive(node, lines) {
+            let line = node.start_position().row + 1;
+            out.push(format!("[{line}] {directive}"));
+        }
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect(child, lines, max_lines, out);
+    }
+}
+
+/// The directive string of `println!("cargo:...", ...)`, without quotes.
+fn directive(node: tree_sitter::Node, lines: &[&str]) -> Option<String> {
+    let name = node_text_simple(node.child_by_field_name("macro")?, lines);
+    if name != "println" && name != "print" {
+        return None;
+    }
+    let mut cursor = node.walk();
+    let args = node
+        .children(&mut cursor)
+        .find(|c| c.kind() == "token_tree")?;
+    let mut cursor = args.walk();
+    let first = args
+        .children(&mut cursor)
+        .find(|c| c.kind() == "string_literal")?;
+    let text = node_text_simple(first, lines);
+    let text = text.trim_matches('"');
+    text.starts_with("cargo:").then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cargo_directives_are_listed() {
+        let build_rs = r#"use std::env;
+
+fn main() {
+    // println!("cargo:rustc-cfg=commented_out");
+    println!("cargo:rerun-if-changed=build.rs");
+    let target = env::var("TARGET").unwrap();
+    if target.contains("linux") {
+        println!("cargo::rustc-link-lib=ssl");
+    }
+    println!("building for {target}");
+    println!("cargo:rustc-env=TARGET={}", target);
+}
+"#;
+        assert!(is_build_script(Path::new("crates/sys/build.rs")));
+        assert!(!is_build_script(Path::new("src/build.rs.in")));
+        assert_eq!(
+            directives(build_rs, usize::MAX),
+            [
+                "[5] cargo:rerun-if-changed=build.rs",
+                "[8] cargo::rustc-link-lib=ssl",
+                "[11] cargo:rustc-env=TARGET={}",
+            ]
+        );
+
+        let out = crate::read::outline::outline_named(build_rs, Path::new("build.rs"));
+        assert!(
+            out.ends_with(
+                "\n\ncargo directives:\n  [5] cargo:rerun-if-changed=build.rs\n  \
+                 [8] cargo::rustc-link-lib=ssl\n  [11] cargo:rustc-env=TARGET={}"
+            ),
+            "{out}"
+        );
+    }
+}
@@ -1,38 +1,486 @@
-use crate::lang::outline::{extract_import_source, outline_language, walk_top_level};
+use std::fmt::Write;
+
+use super::{FormatVersion, LineRanges, OutlineOptions};
+use crate::lang::outline::{
+    extract_import_source, get_outline_entries, outline_language, walk_top_level,
+};
 use crate::types::{Lang, OutlineEntry, OutlineKind};
 
 /// Generate a code outline using tree-sitter. Walks top-level AST nodes,
 /// emitting signatures without bodies.
 pub fn outline(content: &str, lang: Lang, max_lines: usize) -> String {
-    let Some(language) = outline_language(lang) else {
+    outline_with(content, lang, max_lines, &OutlineOptions::default())
+}
+
+/// The outline as structured entries, before any formatting: the same tree
+/// [`outline`] renders, overloads grouped. Empty when the language has no
+/// outline support.
+///
+/// ```
+/// use tilth::{outline_entries, Lang, OutlineKind};
+///
+/// let src = "struct Point { x: f64 }\n\nimpl Point {\n    fn norm(&self) -> f64 { self.x }\n}\n";
+/// let entries = outline_entries(src, Lang::Rust);
+/// let methods: Vec<&str> = entries
+///     .iter()
+///     .flat_map(|e| &e.children)
+///     .filter(|c| c.kind == OutlineKind::Function)
+///     .map(|c| c.name.as_str())
+///     .collect();
+/// assert_eq!(methods, ["norm"]);
+/// ```
+#[must_use]
+pub fn outline_entries(content: &str, lang: Lang) -> Vec<OutlineEntry> {
+    let lines: Vec<&str> = content.lines().collect();
+    top_level_entries(content, lang, &lines, &OutlineOptions::default()).unwrap_or_default()
+}
+
+/// [`outline_entries`] as compact JSON: an array of entries, children nested.
+#[must_use]
+pub fn json(content: &str, lang: Lang) -> String {
+    let entries = outline_entries(content, lang);
+    let entries: Vec<crate::format::EntryJson> = entries.iter().map(Into::into).collect();
+    serde_json::to_string(&entries).unwrap_or_default()
+}
+
+/// [`outline`] honoring [`OutlineOptions`]: `body_lines` shows the first lines
+/// of each function body under its entry — a glimpse of what it does without
+/// the implementation — and the name and range options shape each entry line.
+pub fn outline_with(content: &str, lang: Lang, max_lines: usize, opts: &OutlineOptions) -> String {
+    let (bom, content) = split_bom(content);
+    let lines: Vec<&str> = content.lines().collect();
+    match top_level_entries(content, lang, &lines, opts) {
+        Some(mut entries) => {
+            shift_byte_ranges(&mut entries, bom);
+            let entries = touched(entries, opts.changed_lines.as_ref());
+            format_entries(&entries, &lines, max_lines, lang, opts)
+        }
+        None => fallback_outline(content, max_lines),
+    }
+}
+
+/// The length of a leading BOM, and the content after it.
+fn split_bom(content: &str) -> (usize, &str) {
+    let stripped = crate::lang::strip_bom(content);
+    (content.len() - stripped.len(), stripped)
+}
+
+/// Move byte ranges, which index the BOM-less text the parser saw, `by` bytes
+/// on so printed offsets slice the file as it is on disk.
+fn shift_byte_ranges(entries: &mut [OutlineEntry], by: usize) {
+    for entry in entries {
+        if !entry.byte_range.is_empty() {
+            entry.byte_range = entry.byte_range.start + by..entry.byte_range.end + by;
+        }
+        shift_byte_ranges(&mut entry.children, by);
+    }
+}
+
+/// Entries overlapping `changed`, children filtered the same way. All of them
+/// when there is no filter.
+fn touched(entries: Vec<OutlineEntry>, changed: Option<&LineRanges>) -> Vec<OutlineEntry> {
+    let Some(changed) = changed else {
+        return entries;
+    };
+    entries
+        .into_iter()
+        .filter(|e| changed.overlaps(e.start_line, e.end_line))
+        .map(|mut e| {
+            e.children = touched(std::mem::take(&mut e.children), Some(changed));
+            e
+        })
+        .collect()
+}
+
+/// Outline with top-level entries bucketed by kind under section headers
+/// (imports, types, impls & modules, functions, ...). Source order is kept
+/// within each section; empty sections are left out. Body previews are never
+/// shown; [`OutlineOptions::max_name_len`] applies.
+pub fn grouped(content: &str, lang: Lang, max_lines: usize, opts: &OutlineOptions) -> String {
+    const SECTIONS: &[(&str, &[OutlineKind])] = &[
+        ("Imports", &[OutlineKind::Import]),
+        (
+            "Types",
+            &[
+                OutlineKind::Class,
+                OutlineKind::Struct,
+                OutlineKind::Interface,
+                OutlineKind::TypeAlias,
+                OutlineKind::Enum,
+            ],
+        ),
+        ("Impls & modules", &[OutlineKind::Module]),
+        ("Functions", &[OutlineKind::Function, OutlineKind::Macro]),
+        ("Constants", &[OutlineKind::Constant]),
+        (
+            "Variables",
+            &[
+                OutlineKind::Variable,
+                OutlineKind::ImmutableVariable,
+                OutlineKind::Property,
+            ],
+        ),
+        ("Exports", &[OutlineKind::Export]),
+        ("Tests", &[OutlineKind::TestSuite, OutlineKind::TestCase]),
+    ];
+
+    let (bom, content) = split_bom(content);
+    let lines: Vec<&str> = content.lines().collect();
+    let Some(mut entries) = top_level_entries(content, lang, &lines, opts) else {
         return fallback_outline(content, max_lines);
     };
+    shift_byte_ranges(&mut entries, bom);
+    let entries = touched(entries, opts.changed_lines.as_ref());
+    let mut buckets: Vec<Vec<OutlineEntry>> = SECTIONS.iter().map(|_| Vec::new()).collect();
+    for entry in entries {
+        if let Some(i) = SECTIONS
+            .iter()
+            .position(|(_, kinds)| kinds.contains(&entry.kind))
+        {
+            buckets[i].push(entry);
+        }
+    }
+
+    let opts = OutlineOptions {
+        body_lines: 0,
+        ..opts.clone()
+    };
+    let mut sections = Vec::new();
+    let mut remaining = max_lines;
+    for ((title, _), bucket) in SECTIONS.iter().zip(&buckets) {
+        if bucket.is_empty() || remaining == 0 {
+            continue;
+        }
+        let body = format_entries(bucket, &lines, remaining, lang, &opts);
+        remaining = remaining.saturating_sub(body.lines().count());
+        sections.push(format!("{title}:\n{body}"));
+    }
+    sections.join("\n\n")
+}
 
+/// Top-level entries as the outline shows them in `opts.format_version`,
+/// overloads grouped and, with `opts.collapse_accessors`, getter/setter pairs
+/// collapsed. `None` when the language has no parser.
+fn top_level_entries(
+    content: &str,
+    lang: Lang,
+    lines: &[&str],
+    opts: &OutlineOptions,
+) -> Option<Vec<OutlineEntry>> {
+    if let Some(entries) = crate::lang::scanner_entries(lang, content) {
+        return Some(entries);
+    }
+    let language = outline_language(lang)?;
     let mut parser = tree_sitter::Parser::new();
-    if parser.set_language(&language).is_err() {
-        return fallback_outline(content, max_lines);
+    parser.set_language(&language).ok()?;
+    let tree = parser.parse(content, None)?;
+    let entries = walk_top_level(tree.root_node(), lines, lang, opts.format_version);
+    // Before overload grouping, which would merge a TS `get x`/`set x` pair.
+    let entries = if opts.collapse_accessors {
+        collapse_accessors(entries)
+    } else {
+        entries
+    };
+    Some(group_overloads(entries))
+}
+
+/// Names-only outline: one top-level symbol name per line, imports omitted.
+/// No ranges, signatures, or docs — a minimal index to decide what to fetch.
+pub fn names(content: &str, lang: Lang, max_lines: usize) -> String {
+    get_outline_entries(content, lang)
+        .iter()
+        .filter(|e| e.kind != OutlineKind::Import)
+        .filter_map(|e| match e.kind {
+            OutlineKind::Export => export_name(&e.name),
+            _ => Some(e.name.clone()),
+        })
+        .take(max_lines)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Pull the declared name out of an export statement's text:
+/// `export default async function foo(` → `foo`.
+pub(crate) fn export_name(text: &str) -> Option<String> {
+    const KEYWORDS: &[&str] = &[
+        "export",
+        "default",
+        "declare",
+        "abstract",
+        "async",
+        "function",
+        "function*",
+        "class",
+        "const",
+        "let",
+        "var",
+        "interface",
+        "type",
+        "enum",
+        "namespace",
+    ];
+    let name = text
+        .split_whitespace()
+        .find(|w| !KEYWORDS.contains(w))?
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '$')
+        .collect::<String>();
+    if name.is_empty() {
+        None
+    } else {
+        Some(name)
     }
+}
 
-    let Some(tree) = parser.parse(content, None) else {
-        return fallback_outline(content, max_lines);
+/// Merge runs of same-named functions (TS overload signatures, Java/C#/Kotlin
+/// overloads) into one entry spanning them all, with every signature listed.
+fn group_overloads(entries: Vec<OutlineEntry>) -> Vec<OutlineEntry> {
+    let mut grouped: Vec<(OutlineEntry, usize)> = Vec::new();
+    for mut entry in entries {
+        entry.children = group_overloads(std::mem::take(&mut entry.children));
+        if let Some((last, count)) = grouped.last_mut() {
+            let key = overload_key(&entry);
+            if key.is_some() && last.kind == entry.kind && key == overload_key(last) {
+                let sig = overload_signature(&entry);
+                if *count == 1 {
+                    last.signature = Some(overload_signature(last));
+                }
+                if let Some(s) = last.signature.as_mut() {
+                    s.push('\n');
+                    s.push_str(&sig);
+                }
+                last.end_line = entry.end_line;
+                last.byte_range.end = entry.byte_range.end;
+                if last.doc.is_none() {
+                    last.doc = entry.doc;
+                }
+                *count += 1;
+                continue;
+            }
+        }
+        grouped.push((entry, 1));
+    }
+
+    grouped
+        .into_iter()
+        .map(|(mut entry, count)| {
+            if count > 1 {
+                if let Some(key) = overload_key(&entry) {
+                    entry.name = format!("{key} ({count} overloads)");
+                }
+            }
+            entry
+        })
+        .collect()
+}
+
+/// Merge each getter directly followed or preceded by its setter into one
+/// `X { get; set }` property spanning both: `getX`/`isX` with `setX` (Java,
+/// Kotlin, ...) and `get x`/`set x` accessors (TS/JS). The signature is the
+/// getter's with the call replaced by the property name.
+fn collapse_accessors(entries: Vec<OutlineEntry>) -> Vec<OutlineEntry> {
+    let mut out: Vec<OutlineEntry> = Vec::with_capacity(entries.len());
+    for mut entry in entries {
+        entry.children = collapse_accessors(std::mem::take(&mut entry.children));
+        let pair = out.last().and_then(|last| {
+            let (a, b) = (accessor(last)?, accessor(&entry)?);
+            (a.property == b.property && a.is_getter != b.is_getter).then_some((a, b))
+        });
+        let Some((a, b)) = pair else {
+            out.push(entry);
+            continue;
+        };
+        let last_is_getter = a.is_getter;
+        let getter = if last_is_getter { a } else { b };
+        let last = out.pop().expect("pair has a previous entry");
+        let (start, end) = (last.start_line, entry.end_line);
+        let bytes = last.byte_range.start..entry.byte_range.end;
+        let (mut merged, setter) = if last_is_getter {
+            (last, entry)
+        } else {
+            (entry, last)
+        };
+        merged.kind = OutlineKind::Property;
+        merged.name = format!("{} {{ get; set }}", getter.property);
+        merged.signature = merged.signature.as_deref().map(|sig| {
+            let sig = sig.split('{').next().unwrap_or(sig);
+            let sig = sig.split_whitespace().collect::<Vec<_>>().join(" ");
+            sig.replacen(&format!("{}()", getter.head), &getter.property, 1)
+        });
+        merged.start_line = start;
+        merged.end_line = end;
+        merged.byte_range = bytes;
+        merged.deprecated |= setter.deprecated;
+        merged.doc = merged.doc.or(setter.doc);
+        out.push(merged);
+    }
+    out
+}
+
+/// A function recognised as a property getter or setter.
+struct Accessor {
+    property: String,
+    is_getter: bool,
+    /// Text before `()` in the getter's declaration: `getName`, `get name`.
+    head: String,
+}
+
+fn accessor(entry: &OutlineEntry) -> Option<Accessor> {
+    if entry.kind != OutlineKind::Function {
+        return None;
+    }
+    let name = entry.name.as_str();
+    // TS/JS accessors keep the plain name; the keyword is in the signature.
+    if let Some(sig) = entry.signature.as_deref() {
+        let sig = sig.split_whitespace().collect::<Vec<_>>().join(" ");
+        for (keyword, is_getter) in [("get", true), ("set", false)] {
+            let head = format!("{keyword} {name}");
+            if sig.contains(&format!("{head}(")) {
+                return Some(Accessor {
+                    property: name.to_string(),
+                    is_getter,
+                    head,
+                });
+            }
+        }
+    }
+    for (prefix, is_getter) in [("get", true), ("is", true), ("set", false)] {
+        if let Some(property) = name.strip_prefix(prefix) {
+            if property.starts_with(|c: char| c.is_ascii_uppercase()) {
+                return Some(Accessor {
+                    property: property.to_string(),
+                    is_getter,
+                    head: name.to_string(),
+                });
+            }
+        }
+    }
+    None
+}
+
+/// The name overloads share: a function's name, or the declared name of an
+/// exported function. Everything else is never grouped.
+fn overload_key(entry: &OutlineEntry) -> Option<String> {
+    match entry.kind {
+        OutlineKind::Function => Some(entry.name.clone()),
+        OutlineKind::Export if entry.name.split_whitespace().any(|w| w == "function") => {
+            export_name(&entry.name)
+        }
+        _ => None,
+    }
+}
+
+/// One overload's declaration line, without body opener or terminator.
+fn overload_signature(entry: &OutlineEntry) -> String {
+    let text = match entry.kind {
+        OutlineKind::Export => &entry.name,
+        _ => entry.signature.as_ref().unwrap_or(&entry.name),
     };
+    let text = text.split('{').next().unwrap_or(text).trim_end();
+    text.strip_suffix(';').unwrap_or(text).to_string()
+}
 
-    let root = tree.root_node();
-    let lines: Vec<&str> = content.lines().collect();
-    let entries = walk_top_level(root, &lines, lang);
+/// Format outline entries in the layout of the requested
+/// [`FormatVersion`]. A format change adds a version rather than editing an
+/// existing one.
+fn format_entries(
+    entries: &[OutlineEntry],
+    lines: &[&str],
+    max_lines: usize,
+    lang: Lang,
+    opts: &OutlineOptions,
+) -> String {
+    match opts.format_version {
+        // Version 2 only adds the visibility marker `format_entry` renders.
+        FormatVersion::V1 | FormatVersion::V2 => {
+            let entries = pinned_entries(entries, lines, lang, opts.format_version);
+            format_entries_v1(&entries, lines, max_lines, lang, opts)
+        }
+        FormatVersion::V3 | FormatVersion::V4 => {
+            format_entries_v1(entries, lines, max_lines, lang, opts)
+        }
+    }
+}
+
+/// Entries as versions 1 and 2 showed them: no modifier tags, type parameters
+/// or decorators, and signatures cut to their first source line. Version 1
+/// also leaves out enum variants and struct fields.
+fn pinned_entries(
+    entries: &[OutlineEntry],
+    lines: &[&str],
+    lang: Lang,
+    version: FormatVersion,
+) -> Vec<OutlineEntry> {
+    // Rust, Go and TypeScript fields arrived with version 2.
+    let fields = matches!(lang, Lang::Rust | Lang::Go | Lang::TypeScript | Lang::Tsx);
+    entries
+        .iter()
+        .filter(|e| version >= FormatVersion::V2 || !fields || e.kind != OutlineKind::Property)
+        .map(|entry| {
+            let mut entry = entry.clone();
+            if entry.kind == OutlineKind::Function {
+                // Java annotations used to lead the signature.
+                let sig = entry.signature.as_ref().map(|sig| {
+                    if lang == Lang::Java && !entry.decorators.is_empty() {
+                        format!("{} {sig}", entry.decorators.join(" "))
+                    } else {
+                        sig.clone()
+                    }
+                });
+                entry.signature = sig.map(|sig| first_line_signature(sig, &entry, lines));
+            }
+            entry.modifiers.clear();
+            entry.type_params = None;
+            entry.decorators.clear();
+            if version == FormatVersion::V1 && entry.kind == OutlineKind::Enum {
+                entry
+                    .children
+                    .retain(|c| lang == Lang::Java && c.kind != OutlineKind::Constant);
+            }
+            entry.children = pinned_entries(&entry.children, lines, lang, version);
+            entry
+        })
+        .collect()
+}
 
-    format_entries(&entries, &lines, max_lines, lang)
+/// `sig` as it read before version 3: the first line of the declaration, cut
+/// at `{` or a trailing `:`. Falls back to `sig` when no line starts it.
+fn first_line_signature(sig: String, entry: &OutlineEntry, lines: &[&str]) -> String {
+    // Grouped overloads already hold one line per signature.
+    if sig.contains('\n') {
+        return sig;
+    }
+    let start = entry.start_line.saturating_sub(1) as usize;
+    let end = (entry.end_line as usize).min(lines.len());
+    for line in lines.get(start..end).unwrap_or_default() {
+        let line = line.trim();
+        let head = match line.find('{') {
+            Some(pos) => line[..pos].trim(),
+            None => line.strip_suffix(':').map_or(line, str::trim),
+        };
+        let collapsed = head.split_whitespace().collect::<Vec<_>>().join(" ");
+        if collapsed.is_empty() || !sig.starts_with(&collapsed) {
+            continue;
+        }
+        return if head.len() > 120 {
+            format!("{}...", crate::types::truncate_str(head, 117))
+        } else {
+            head.to_string()
+        };
+    }
+    sig
 }
 
 /// Format outline entries into the spec'd output format.
-fn format_entries(
+fn format_entries_v1(
     entries: &[OutlineEntry],
-    _lines: &[&str],
+    lines: &[&str],
     max_lines: usize,
     lang: Lang,
+    opts: &OutlineOptions,
 ) -> String {
     let mut out = Vec::new();
-    let mut import_groups: Vec<&str> = Vec::new();
+    let mut import_groups: Vec<&OutlineEntry> = Vec::new();
     // Track the start line of the first import in the current group.
     let mut import_group_start: u32 = 1;
 
@@ -46,13 +494,20 @@ fn format_entries(
                 if import_groups.is_empty() {
                     import_group_start = entry.start_line;
                 }
-                import_groups.push(&entry.name);
+                import_groups.push(entry);
                 continue;
             }
             _ => {
                 // Flush any accumulated imports
                 if !import_groups.is_empty() {
-                    out.push(format_imports(&import_groups, import_group_start));
+                    push_imports(
+                        &mut out,
+                        &import_groups,
+                        import_group_start,
+                        lines,
+                        lang,
+                        opts,
+                    );
                     import_groups.clear();
                 }
             }
@@ -61,38 +516,126 @@ fn format_entries(
         // Flatten namespace modules — hoist their children to top level
         // so classes inside namespaces show their methods at indent 1.
         if entry.kind == OutlineKind::Module && !entry.children.is_empty() {
-            out.push(format_entry(entry, 0, lang));
+            push_entry(&mut out, entry, 0, lines, lang, opts);
             for child in &entry.children {
                 if out.len() >= max_lines {
                     break;
                 }
-                out.push(format_entry(child, 1, lang));
+                push_entry(&mut out, child, 1, lines, lang, opts);
                 for grandchild in &child.children {
                     if out.len() >= max_lines {
                         break;
                     }
-                    out.push(format_entry(grandchild, 2, lang));
+                    push_entry(&mut out, grandchild, 2, lines, lang, opts);
                 }
             }
         } else {
-            out.push(format_entry(entry, 0, lang));
+            push_entry(&mut out, entry, 0, lines, lang, opts);
             for child in &entry.children {
                 if out.len() >= max_lines {
                     break;
                 }
-                out.push(format_entry(child, 1, lang));
+                push_entry(&mut out, child, 1, lines, lang, opts);
+                // Nested types (Java) carry their own members.
+                for grandchild in &child.children {
+                    if out.len() >= max_lines {
+                        break;
+                    }
+                    push_entry(&mut out, grandchild, 2, lines, lang, opts);
+                }
             }
         }
     }
 
     // Flush trailing imports
     if !import_groups.is_empty() {
-        out.push(format_imports(&import_groups, import_group_start));
+        push_imports(
+            &mut out,
+            &import_groups,
+            import_group_start,
+            lines,
+            lang,
+            opts,
+        );
     }
 
     out.join("\n")
 }
 
+/// Push an entry line, followed by its body preview when one was requested.
+fn push_entry(
+    out: &mut Vec<String>,
+    entry: &OutlineEntry,
+    indent: usize,
+    lines: &[&str],
+    lang: Lang,
+    opts: &OutlineOptions,
+) {
+    out.push(format_entry(entry, indent, lang, opts));
+    if opts.body_lines == 0 || entry.kind != OutlineKind::Function {
+        return;
+    }
+    let prefix = indent_prefix(indent, opts);
+    for line in body_preview(entry, lines, opts.body_lines) {
+        out.push(format!("{prefix}           {line}"));
+    }
+}
+
+/// The first `n` lines after a function's signature, de-indented relative to
+/// the declaration. The signature ends where the parentheses balance and the
+/// body opens (`{` or a trailing `:`); a closing `}` / `end` is never shown.
+fn body_preview<'a>(entry: &OutlineEntry, lines: &[&'a str], n: usize) -> Vec<&'a str> {
+    let start = entry.start_line as usize - 1;
+    let end = (entry.end_line as usize).min(lines.len());
+    if start >= end {
+        return Vec::new();
+    }
+    let base = lines[start].len() - lines[start].trim_start().len();
+
+    let mut depth = 0isize;
+    let mut body_start = end;
+    for (i, line) in lines.iter().enumerate().take(end).skip(start) {
+        depth += line.matches('(').count() as isize - line.matches(')').count() as isize;
+        let line = line.trim_end();
+        if depth <= 0 && (line.contains('{') || line.ends_with(':') || line.ends_with('=')) {
+            body_start = i + 1;
+            break;
+        }
+    }
+
+    let mut body = &lines[body_start.min(end)..end];
+    if let Some(last) = body.last() {
+        if matches!(last.trim(), "}" | "end" | "};") {
+            body = &body[..body.len() - 1];
+        }
+    }
+    body.iter()
+        .take(n)
+        .map(|l| l.get(base..).unwrap_or_else(|| l.trim_start()))
+        .collect()
+}
+
+/// Push the import summary, then any imported names that look unused when
+/// [`OutlineOptions::unused_imports`] asks for them.
+fn push_imports(
+    out: &mut Vec<String>,
+    imports: &[&OutlineEntry],
+    start: u32,
+    lines: &[&str],
+    lang: Lang,
+    opts: &OutlineOptions,
+) {
+    let names: Vec<&str> = imports.iter().map(|e| e.name.as_str()).collect();
+    out.push(format_imports(&names, start));
+    if !opts.unused_imports {
+        return;
+    }
+    let unused = crate::read::imports::unused_imports(imports, lines, lang);
+    if !unused.is_empty() {
+        out.push(format!("       unused (heuristic): {}", unused.join(", ")));
+    }
+}
+
 /// Format a collapsed import summary grouped by source with counts.
 /// Spec format: `imports: react(4), express(2), @/lib(3)`
 fn format_imports(imports: &[&str], start: u32) -> String {
@@ -130,16 +673,10 @@ fn format_imports(imports: &[&str], start: u32) -> String {
     format!("[{start}-]   imports: {condensed}{suffix}")
 }
 
-/// Format a single outline entry with optional indentation.
-fn format_entry(entry: &OutlineEntry, indent: usize, lang: Lang) -> String {
-    let prefix = "  ".repeat(indent);
-    let range = if entry.start_line == entry.end_line {
-        format!("[{}]", entry.start_line)
-    } else {
-        format!("[{}-{}]", entry.start_line, entry.end_line)
-    };
-
-    let kind_label = match entry.kind {
+/// The outline's label for `kind` in `lang`: `fn`, or Scala's `def` and
+/// Kotlin's `fun`, and so on.
+pub(crate) fn kind_label(kind: OutlineKind, lang: Lang) -> &'static str {
+    match kind {
         OutlineKind::Function => {
             if lang == Lang::Scala {
                 "def"
@@ -171,36 +708,111 @@ fn format_entry(entry: &OutlineEntry, indent: usize, lang: Lang) -> String {
         }
         OutlineKind::Export => "export",
         OutlineKind::Property => "prop",
-        OutlineKind::Module => {
-            if lang == Lang::Scala || lang == Lang::Kotlin {
-                "object"
-            } else {
-                "mod"
-            }
-        }
+        OutlineKind::Module => match lang {
+            Lang::Scala | Lang::Kotlin => "object",
+            Lang::Cpp => "namespace",
+            _ => "mod",
+        },
+        OutlineKind::Macro => "macro",
         OutlineKind::Import => "import",
         OutlineKind::TestSuite => "suite",
         OutlineKind::TestCase => "test",
+    }
+}
+
+/// Format a single outline entry with optional indentation. Names longer than
+/// [`OutlineOptions::max_name_len`] bytes (at least 3) are cut, with `...`
+/// counted in the limit.
+fn format_entry(entry: &OutlineEntry, indent: usize, lang: Lang, opts: &OutlineOptions) -> String {
+    let prefix = indent_prefix(indent, opts);
+    let mut range = if entry.start_line == entry.end_line {
+        format!("{}", entry.start_line)
+    } else {
+        format!("{}-{}", entry.start_line, entry.end_line)
     };
+    if opts.byte_ranges && !entry.byte_range.is_empty() {
+        let _ = write!(
+            range,
+            " b{}-{}",
+            entry.byte_range.start, entry.byte_range.end
+        );
+    }
+    let range = format!("[{range}]");
 
-    let sig = match &entry.signature {
-        Some(s) => format!("\n{prefix}           {s}"),
-        None => String::new(),
+    // C++ namespaces read `mod` before version 4.
+    let kind_label = if lang == Lang::Cpp && opts.format_version < FormatVersion::V4 {
+        kind_label(entry.kind, Lang::C)
+    } else {
+        kind_label(entry.kind, lang)
     };
 
-    let doc = match &entry.doc {
+    // Grouped overloads carry one signature per line.
+    let mut sig = String::new();
+    for line in entry.signature.iter().flat_map(|s| s.lines()) {
+        let line = match opts.max_sig_len {
+            Some(max) if line.len() > max => {
+                format!(
+                    "{}...",
+                    crate::types::truncate_str(line, max.saturating_sub(3))
+                )
+            }
+            _ => line.to_string(),
+        };
+        let _ = write!(sig, "\n{prefix}           {line}");
+    }
+
+    // JSDoc/Javadoc tags (`@param`, `@returns`) may come before the description.
+    let summary = entry
+        .doc
+        .as_deref()
+        .and_then(|d| d.lines().find(|l| !l.is_empty() && !l.starts_with('@')));
+    let doc = match summary {
         Some(d) => {
             let truncated = if d.len() > 60 {
                 format!("{}...", crate::types::truncate_str(d, 57))
             } else {
-                d.clone()
+                d.to_string()
             };
             format!("  // {truncated}")
         }
         None => String::new(),
     };
 
-    format!("{prefix}{range:<12} {kind_label} {}{sig}{doc}", entry.name)
+    let name = format!(
+        "{}{}",
+        entry.name,
+        entry.type_params.as_deref().unwrap_or("")
+    );
+    // Below 3 bytes the ellipsis alone would overrun the cap.
+    let name = match opts.max_name_len.map(|max| max.max(3)) {
+        Some(max) if name.len() > max => {
+            format!("{}...", crate::types::truncate_str(&name, max - 3))
+        }
+        _ => name,
+    };
+    let mut tags = String::new();
+    for modifier in &entry.modifiers {
+        let _ = write!(tags, " [{modifier}]");
+    }
+    if entry.deprecated {
+        tags.push_str(" [deprecated]");
+    }
+    let visibility = match entry.visibility.as_deref() {
+        Some(v) if opts.format_version >= FormatVersion::V2 => format!("{v} "),
+        _ => String::new(),
+    };
+    // Decorators and annotations (`@app.get("/")`, `@Override`) sit above the
+    // entry, as in source.
+    let mut decorators = String::new();
+    for decorator in &entry.decorators {
+        let _ = writeln!(decorators, "{prefix}           {decorator}");
+    }
+    format!("{decorators}{prefix}{range:<12} {visibility}{kind_label} {name}{tags}{sig}{doc}")
+}
+
+/// Leading spaces for an entry `indent` levels deep.
+fn indent_prefix(indent: usize, opts: &OutlineOptions) -> String {
+    " ".repeat(indent * opts.indent.unwrap_or(2))
 }
 
 /// Fallback when tree-sitter grammar isn't available.
@@ -213,69 +825,1480 @@ mod tests {
     use super::*;
 
     #[test]
-    fn scala_outline_constructs() {
-        let scala_code = r#"
-package example
+    fn doc_comments_use_language_delimiters() {
+        let doc_of = |code: &str, lang: Lang, name: &str| {
+            get_outline_entries(code, lang)
+                .into_iter()
+                .find(|e| e.name == name)
+                .and_then(|e| e.doc)
+        };
 
-import scala.util.Try
+        let rust_code = "\
+/// Runtime settings.
+/// Loaded once at startup.
+#[derive(Debug)]
+pub struct Config {}
 
-trait DataSource {
-  def load(): String
+// License header.
+
+fn bare() {}
+";
+        assert_eq!(
+            doc_of(rust_code, Lang::Rust, "Config").as_deref(),
+            Some("Runtime settings.\nLoaded once at startup.")
+        );
+        assert_eq!(doc_of(rust_code, Lang::Rust, "bare"), None);
+
+        let js_code = "/**\n * Adds two numbers.\n */\nfunction add(a, b) {}\n";
+        assert_eq!(
+            doc_of(js_code, Lang::JavaScript, "add").as_deref(),
+            Some("Adds two numbers.")
+        );
+
+        let py_code = "# Opens a connection.\ndef connect(url):\n    pass\n";
+        assert_eq!(
+            doc_of(py_code, Lang::Python, "connect").as_deref(),
+            Some("Opens a connection.")
+        );
+    }
+
+    #[test]
+    fn jsdoc_summary_is_the_description() {
+        let ts_code = "\
+/**
+ * @internal
+ * Adds two numbers.
+ *
+ * @param a first addend
+ * @returns the sum
+ */
+export function add(a: number, b: number): number {
+  return a + b;
 }
 
-class Database {
-  val connectionString = "jdbc:..."
-  var connected = false
-  
-  def connect(): Unit = {}
+/** Subtracts. */
+function sub(a: number, b: number): number {
+  return a - b;
 }
+";
+        let expected = "\
+[8-10]       export export function add(a: number, b: number): number {  // Adds two numbers.
+[13-15]      fn sub
+           function sub(a: number, b: number): number  // Subtracts.";
+        assert_eq!(outline(ts_code, Lang::TypeScript, usize::MAX), expected);
+    }
 
-object Database {
-  def create(): Database = new Database()
+    #[test]
+    fn python_decorators_show_above_entries() {
+        let py_code = r#"from dataclasses import dataclass
+
+@dataclass(frozen=True)
+class User:
+    name: str
+
+    @property
+    def label(self) -> str:
+        return self.name
+
+@app.route(
+    "/users/<int:id>",
+    methods=["GET"],
+)
+@login_required
+def show_user(id):
+    return User("x")
+"#;
+        let expected = r#"           @dataclass(frozen=True)
+[3-9]        class User
+             @property
+  [7-9]        fn label
+             def label(self) -> str
+           @app.route("/users/<int:id>", methods=["GET"])
+           @login_required
+[11-17]      fn show_user
+           def show_user(id)"#;
+        assert_eq!(outline(py_code, Lang::Python, usize::MAX), expected);
+    }
+
+    #[test]
+    fn java_annotations_show_above_entries() {
+        let java_code = r#"@RestController
+@RequestMapping("/users")
+public class UserController {
+    @GetMapping("/{id}")
+    public User show(@PathVariable long id) {
+        return repo.find(id);
+    }
+
+    @Override
+    public String toString() {
+        return "users";
+    }
 }
+"#;
+        let expected = r#"           @RestController
+           @RequestMapping("/users")
+[1-13]       class UserController
+             @GetMapping("/{id}")
+  [4-7]        fn show
+             public User show(@PathVariable long id)
+             @Override
+  [9-12]       fn toString
+             public String toString()"#;
+        assert_eq!(outline(java_code, Lang::Java, usize::MAX), expected);
+    }
 
-enum Color {
-  case Red, Green, Blue
+    #[test]
+    fn cpp_namespaces_collect_members() {
+        let cpp_code = r"#include <string>
+
+namespace geo {
+
+/// A 2D point.
+class Point {
+public:
+    double norm() const;
+};
+
+double distance(const Point& a, const Point& b) {
+    return 0.0;
 }
 
-type UserId = String
-"#;
+namespace detail {
+int helper(int x) { return x; }
+}
 
-        let outline = outline(scala_code, Lang::Scala, 1000);
+}  // namespace geo
 
-        assert!(outline.contains("trait DataSource"));
-        assert!(outline.contains("class Database"));
-        assert!(outline.contains("object Database"));
-        assert!(outline.contains("enum Color"));
-        assert!(outline.contains("type UserId"));
-        assert!(outline.contains("val connectionString"));
-        assert!(outline.contains("var connected"));
-        assert!(outline.contains("def load"));
-        assert!(outline.contains("def connect"));
-        assert!(outline.contains("def create"));
+namespace {
+void local() {}
+}
+";
+        let expected = "\
+[1-]   imports: <string>
+[3-19]       namespace geo
+  [6-9]        class Point  // A 2D point.
+  [11-13]      fn distance
+             double distance(const Point& a, const Point& b)
+  [15-17]      namespace detail
+    [16]         fn helper
+               int helper(int x)
+[21-23]      namespace <anonymous>
+  [22]         fn local
+             void local()";
+        assert_eq!(outline(cpp_code, Lang::Cpp, usize::MAX), expected);
     }
 
     #[test]
-    fn php_outline_constructs() {
-        let php_code = r#"<?php
-namespace App\Services;
+    fn cpp_templates_show_their_parameters() {
+        let cpp_code = "\
+/// Fixed-size buffer.
+template <typename T, int N>
+class Buffer {
+public:
+    T get(int i) const { return data[i]; }
+};
 
-use App\Support\Client;
+template <typename T>
+T max_of(T a, T b) {
+    return a > b ? a : b;
+}
+";
+        let expected = "\
+[2-6]        class Buffer
+           template <typename T, int N>  // Fixed-size buffer.
+  [5]          fn get
+             T get(int i) const
+[8-11]       fn max_of
+           template <typename T> T max_of(T a, T b)";
+        assert_eq!(outline(cpp_code, Lang::Cpp, usize::MAX), expected);
+    }
 
-trait LogsQueries {
-    public function log(string $query): void {}
+    #[test]
+    fn ruby_modules_and_class_methods() {
+        let ruby_code = "\
+require 'json'
+require_relative 'store/base'
+
+# Persistence helpers.
+module Store
+  VERSION = 2
+
+  class Record
+    def self.find(id)
+      new(id)
+    end
+
+    class << self
+      def all
+        []
+      end
+    end
+
+    def save
+      true
+    end
+  end
+end
+";
+        let expected = "\
+[1-]   imports: json, store/base
+[5-23]       mod Store  // Persistence helpers.
+  [6]          const VERSION
+  [8-22]       class Record
+    [9-11]       fn find [self]
+               def self.find(id)
+    [14-16]      fn all [self]
+               def all
+    [19-21]      fn save
+               def save";
+        assert_eq!(outline(ruby_code, Lang::Ruby, usize::MAX), expected);
+    }
+
+    #[test]
+    fn typescript_string_and_const_enums() {
+        let ts_code = "\
+/** Log levels. */
+export enum Level {
+  Debug = \"debug\",
+  Info = \"info\",
 }
 
-class UserService {
-    use LogsQueries;
+const enum Flag {
+  None,
+  Read = 1 << 0,
+}
+";
+        let expected = r#"[2-5]        export export enum Level {  // Log levels.
+  [3]          const Debug
+             Debug = "debug"
+  [4]          const Info
+             Info = "info"
+[7-10]       enum Flag [const]
+  [8]          const None
+  [9]          const Read
+             Read = 1 << 0"#;
+        assert_eq!(outline(ts_code, Lang::TypeScript, usize::MAX), expected);
+    }
 
-    public function __construct(private Client $client) {}
+    #[test]
+    fn json_outline_is_stable() {
+        let expected = r#"[{"kind":"function","name":"one","start_line":1,"end_line":1,"byte_range":{"start":0,"end":15},"signature":"pub fn one()","children":[],"doc":null,"deprecated":false,"visibility":"pub","modifiers":[],"type_params":null,"decorators":[]}]"#;
+        assert_eq!(json("pub fn one() {}\n", Lang::Rust), expected);
+    }
 
-    public function findUser(int $id): array {
-        return $this->client->loadUser($id);
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_outline_round_trips_through_serde() {
+        let rust_code = "pub struct Point {\n    x: f64,\n}\n\nimpl Point {\n    pub async fn norm(&self) -> f64 {\n        self.x\n    }\n}\n";
+        let out = json(rust_code, Lang::Rust);
+        let entries: Vec<OutlineEntry> = serde_json::from_str(&out).unwrap();
+        assert_eq!(entries[1].children[0].name, "norm");
+        assert_eq!(entries[1].children[0].modifiers, ["async"]);
+        assert_eq!(serde_json::to_string(&entries).unwrap(), out);
     }
+
+    #[test]
+    fn names_only_outline() {
+        let rust_code = r"
+use std::fmt;
+
+/// Runtime settings.
+pub struct Config {
+    pub verbose: bool,
+}
+
+pub enum Status {
+    Ready,
+}
+
+pub fn initialize() -> Config {
+    Config { verbose: false }
+}
+
+impl Config {
+    fn reset(&mut self) {}
 }
+";
+        assert_eq!(
+            names(rust_code, Lang::Rust, usize::MAX),
+            "Config\nStatus\ninitialize\nimpl Config"
+        );
+
+        let ts_code = r#"
+import { x } from "./utils";
+export default async function handler(req: Request) {}
+export const PI = 3.14;
+function local() {}
 "#;
+        assert_eq!(
+            names(ts_code, Lang::TypeScript, usize::MAX),
+            "handler\nPI\nlocal"
+        );
+        assert_eq!(names(ts_code, Lang::TypeScript, 1), "handler");
+    }
+
+    #[test]
+    fn body_preview_shows_first_lines() {
+        let body = OutlineOptions {
+            body_lines: 2,
+            ..OutlineOptions::default()
+        };
+        let rust_code = r"
+pub fn load(path: &str) -> String {
+    let raw = read(path);
+    let trimmed = raw.trim();
+    trimmed.to_string()
+}
+
+impl Config {
+    fn merge(
+        &mut self,
+        other: &Config,
+    ) {
+        self.a = other.a;
+        self.b = other.b;
+        self.c = other.c;
+    }
+
+    fn empty(&self) {}
+}
+";
+        let expected = "\
+[2-6]        pub fn load
+           pub fn load(path: &str) -> String
+               let raw = read(path);
+               let trimmed = raw.trim();
+[8-19]       mod impl Config
+  [9-16]       fn merge
+             fn merge(&mut self, other: &Config)
+                 self.a = other.a;
+                 self.b = other.b;
+  [18]         fn empty
+             fn empty(&self)";
+        assert_eq!(
+            outline_with(rust_code, Lang::Rust, usize::MAX, &body),
+            expected
+        );
+
+        let py_code = "def connect(url):\n    conn = open(url)\n    return conn\n";
+        assert!(
+            outline_with(py_code, Lang::Python, usize::MAX, &body).ends_with(
+                "def connect(url)\n               conn = open(url)\n               return conn"
+            )
+        );
+    }
+
+    #[test]
+    fn rust_trait_and_impl_outline() {
+        let rust_code = r#"/// Something drawable.
+pub trait Shape: Send {
+    /// Area in square units.
+    fn area(&self) -> f64;
+    fn name(&self) -> String {
+        "shape".into()
+    }
+    type Unit;
+    const SIDES: u8;
+}
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+    type Unit = f64;
+    const SIDES: u8 = 4;
+}
+"#;
+        let expected = "\
+[2-10]       pub interface Shape  // Something drawable.
+  [4]          fn area
+             fn area(&self) -> f64  // Area in square units.
+  [5-7]        fn name
+             fn name(&self) -> String
+  [8]          type Unit
+  [9]          const SIDES
+[12-18]      mod impl Square
+  [13-15]      fn area
+             fn area(&self) -> f64
+  [16]         type Unit
+  [17]         const SIDES";
+        assert_eq!(outline(rust_code, Lang::Rust, usize::MAX), expected);
+    }
+
+    #[test]
+    fn macro_rules_are_outlined() {
+        let rust_code = r"/// Builds a map from pairs.
+#[macro_export]
+macro_rules! hashmap {
+    ($($k:expr => $v:expr),* $(,)?) => {{
+        let mut m = ::std::collections::HashMap::new();
+        $(m.insert($k, $v);)*
+        m
+    }};
+}
+
+macro_rules! square {
+    ($x:expr) => {
+        $x * $x
+    };
+}
+";
+        let expected = "\
+[3-9]        macro hashmap!  // Builds a map from pairs.
+[11-15]      macro square!";
+        assert_eq!(outline(rust_code, Lang::Rust, usize::MAX), expected);
+    }
+
+    #[test]
+    fn c_header_defines_are_outlined() {
+        let header = "#ifndef GEOM_H
+#define GEOM_H
+
+#include <stdio.h>
+
+/* Larger of two values. */
+#define MAX(a, b) ((a) > (b) ? (a) : (b))
+#define BUF_SIZE 256
+
+struct point {
+    int x;
+    int y;
+};
+
+int add(int a, int b);
+
+#endif
+";
+        let expected = "\
+[4-]   imports: <stdio.h>
+[7]          macro MAX
+           #define MAX(a, b)  // Larger of two values.
+[8]          const BUF_SIZE
+           #define BUF_SIZE 256
+[10-13]      struct point";
+        assert_eq!(outline(header, Lang::C, usize::MAX), expected);
+    }
+
+    #[test]
+    fn generic_parameters_follow_the_name() {
+        let rust_code = "pub struct Pair<K, V: Clone> {\n    key: K,\n}\n\nfn first<T>(items: &[T]) -> &T {\n    &items[0]\n}\n";
+        let out = outline(rust_code, Lang::Rust, usize::MAX);
+        assert!(
+            out.starts_with("[1-3]        pub struct Pair<K, V: Clone>\n"),
+            "{out}"
+        );
+        assert!(out.contains("\n[5-7]        fn first<T>\n"), "{out}");
+
+        let ts_code =
+            "class Box<T> {\n  map<U>(f: (t: T) => U): Box<U> {\n    return new Box();\n  }\n}\n";
+        let out = outline(ts_code, Lang::TypeScript, usize::MAX);
+        assert!(
+            out.starts_with("[1-5]        class Box<T>\n  [2-4]        fn map<U>\n"),
+            "{out}"
+        );
+
+        let java_code = "class Registry<T extends Named> {\n    <R> R visit(Visitor<R> v) { return null; }\n}\n";
+        let out = outline(java_code, Lang::Java, usize::MAX);
+        assert!(
+            out.starts_with(
+                "[1-3]        class Registry<T extends Named>\n  [2]          fn visit<R>\n"
+            ),
+            "{out}"
+        );
+
+        let go_code = "package fp\n\nfunc Map[T, U any](xs []T, f func(T) U) []U {\n\treturn nil\n}\n\ntype Set[T comparable] struct {\n\titems map[T]bool\n}\n";
+        let out = outline(go_code, Lang::Go, usize::MAX);
+        assert!(out.starts_with("[3-5]        fn Map[T, U any]\n"), "{out}");
+        assert!(
+            out.contains("\n[7-9]        struct Set[T comparable]\n"),
+            "{out}"
+        );
+    }
+
+    #[test]
+    fn async_and_unsafe_functions_are_tagged() {
+        let rust_code =
+            "pub async fn fetch() {}\n\npub async unsafe fn raw() {}\n\nfn plain() {}\n";
+        let expected = "\
+[1]          pub fn fetch [async]
+           pub async fn fetch()
+[3]          pub fn raw [async] [unsafe]
+           pub async unsafe fn raw()
+[5]          fn plain
+           fn plain()";
+        assert_eq!(outline(rust_code, Lang::Rust, usize::MAX), expected);
+
+        let ts_code = "async function load() {}\nclass A {\n  async run() {}\n}\n";
+        let out = outline(ts_code, Lang::TypeScript, usize::MAX);
+        assert!(out.starts_with("[1]          fn load [async]\n"), "{out}");
+        assert!(out.contains("  [3]          fn run [async]\n"), "{out}");
+
+        let py_code = "async def serve(port):\n    pass\n";
+        let out = outline(py_code, Lang::Python, usize::MAX);
+        assert!(out.starts_with("[1-2]        fn serve [async]\n"), "{out}");
+    }
+
+    #[test]
+    fn multi_line_signatures_are_joined() {
+        let rust_code = "pub fn spawn<F, T>(
+    name: &str,
+    task: F,
+)
+    -> Handle<T>
+where
+    F: FnOnce() -> T + Send,
+{
+    todo!()
+}
+";
+        let expected = "\
+[1-10]       pub fn spawn<F, T>
+           pub fn spawn<F, T>(name: &str, task: F) -> Handle<T> where F: FnOnce() -> T + Send";
+        assert_eq!(outline(rust_code, Lang::Rust, usize::MAX), expected);
+
+        let ts_code = "class Api {
+  request(
+    method: string,
+    path: string,
+  ): Promise<Response> {
+    return fetch(path);
+  }
+}
+
+function total(items: number[])
+  : number {
+  return 0;
+}
+";
+        let expected = "\
+[1-8]        class Api
+  [2-7]        fn request
+             request(method: string, path: string): Promise<Response>
+[10-13]      fn total
+           function total(items: number[]) : number";
+        assert_eq!(outline(ts_code, Lang::TypeScript, usize::MAX), expected);
+    }
+
+    #[test]
+    fn long_names_are_cut_when_capped() {
+        let code =
+            "pub struct GeneratedProtocolBufferMessageForUserAccountSettings;\n\nfn short() {}\n";
+        let capped = OutlineOptions {
+            max_name_len: Some(24),
+            ..OutlineOptions::default()
+        };
+        assert_eq!(
+            outline_with(code, Lang::Rust, usize::MAX, &capped),
+            "[1]          pub struct GeneratedProtocolBuff...\n[3]          fn short\n           fn short()"
+        );
+        assert!(outline(code, Lang::Rust, usize::MAX)
+            .contains("struct GeneratedProtocolBufferMessageForUserAccountSettings"));
+
+        // Cut on a char boundary, never inside a multi-byte character.
+        let entry = OutlineEntry::new(OutlineKind::Function, "überlangerFunktionsname", 1, 1);
+        let truncated = OutlineOptions {
+            max_name_len: Some(5),
+            ..OutlineOptions::default()
+        };
+        assert_eq!(
+            format_entry(&entry, 0, Lang::Rust, &truncated),
+            "[1]          fn ü..."
+        );
+
+        // Caps below 3 are raised to 3 so the cut name never outgrows the cap.
+        for max in 0..=3 {
+            let tiny = OutlineOptions {
+                max_name_len: Some(max),
+                ..OutlineOptions::default()
+            };
+            assert_eq!(
+                format_entry(&entry, 0, Lang::Rust, &tiny),
+                "[1]          fn ..."
+            );
+        }
+    }
+
+    #[test]
+    fn indent_and_signature_length_are_configurable() {
+        let code =
+            "impl Order {\n    pub fn total(&self, discount: u32) -> u32 {\n        0\n    }\n}\n";
+        let opts = OutlineOptions {
+            max_sig_len: Some(20),
+            indent: Some(4),
+            ..OutlineOptions::default()
+        };
+        assert_eq!(
+            outline_with(code, Lang::Rust, usize::MAX, &opts),
+            "[1-5]        mod impl Order\n    [2-4]        pub fn total\n               pub fn total(&sel..."
+        );
+    }
+
+    #[test]
+    fn changed_lines_keep_only_enclosing_symbols() {
+        let code = "\
+use std::fmt;
+
+pub struct Order {
+    id: u32,
+}
+
+impl Order {
+    pub fn new(id: u32) -> Self {
+        Self { id }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.id * 2
+    }
+}
+
+pub fn untouched() {}
+
+pub fn apply() {
+    let _ = fmt::Error;
+}
+";
+        let changed = OutlineOptions {
+            changed_lines: Some("13,20-21".parse().unwrap()),
+            ..OutlineOptions::default()
+        };
+        assert_eq!(
+            outline_with(code, Lang::Rust, usize::MAX, &changed),
+            "\
+[7-15]       mod impl Order
+  [12-14]      pub fn total
+             pub fn total(&self) -> u32
+[19-21]      pub fn apply
+           pub fn apply()"
+        );
+        assert!("0-3".parse::<LineRanges>().is_err());
+        assert!("9-2".parse::<LineRanges>().is_err());
+    }
+
+    #[test]
+    fn unused_imports_are_flagged() {
+        let ts_code = "\
+import { readFile, writeFile } from 'fs';
+import * as path from 'path';
+import Legacy from './legacy';
+
+export function load(p: string) {
+  return readFile(path.join(p, 'x'));
+}
+";
+        let flagged = |code: &str, lang| {
+            let opts = OutlineOptions {
+                unused_imports: true,
+                ..OutlineOptions::default()
+            };
+            outline_with(code, lang, usize::MAX, &opts)
+        };
+        assert!(flagged(ts_code, Lang::TypeScript).starts_with(
+            "[1-]   imports: fs, path, ./legacy\n       unused (heuristic): writeFile, Legacy\n"
+        ));
+        // Off by default: the guess misfires on trait imports.
+        assert!(!outline(ts_code, Lang::TypeScript, usize::MAX).contains("unused"));
+
+        let py_code = "\
+import os
+import json
+import numpy as np
+
+def total(xs):
+    return np.sum(json.loads(xs))
+";
+        let out = flagged(py_code, Lang::Python);
+        assert!(out.contains("\n       unused (heuristic): os\n"), "{out}");
+
+        // Everything used: no extra line.
+        let rust_code = "use std::collections::{HashMap, BTreeMap as Tree};\n\nfn f(a: HashMap<u8, u8>, b: Tree<u8, u8>) {}\n";
+        assert!(!flagged(rust_code, Lang::Rust).contains("unused"));
+        let rust_code =
+            "use std::collections::{HashMap, BTreeMap as Tree};\n\nfn f(a: HashMap<u8, u8>) {}\n";
+        assert!(flagged(rust_code, Lang::Rust).contains("unused (heuristic): Tree\n"));
+    }
+
+    #[test]
+    fn byte_ranges_slice_the_source() {
+        let code = "use std::fmt;\n\n/// Greets.\npub fn greet(name: &str) -> String {\n    format!(\"hi {name}\")\n}\n\nconst N: u8 = 1;\n";
+        let with_bytes = OutlineOptions {
+            byte_ranges: true,
+            ..OutlineOptions::default()
+        };
+        let out = outline_with(code, Lang::Rust, usize::MAX, &with_bytes);
+        assert!(out.contains("[4-6 b27-90] pub fn greet"), "{out}");
+        assert!(out.contains("[8 b92-108]  const N"), "{out}");
+        assert!(!outline(code, Lang::Rust, usize::MAX).contains(" b27-90"));
+
+        let entries = get_outline_entries(code, Lang::Rust);
+        let greet = entries.iter().find(|e| e.name == "greet").unwrap();
+        assert_eq!(greet.byte_range, 27..90);
+        assert!(code[greet.byte_range.clone()].starts_with("pub fn greet"));
+        assert!(code[greet.byte_range.clone()].ends_with('}'));
+
+        // Offsets index the file on disk, BOM included.
+        let with_bom = format!("\u{feff}{code}");
+        let out = outline_with(&with_bom, Lang::Rust, usize::MAX, &with_bytes);
+        assert!(out.contains("[4-6 b30-93] pub fn greet"), "{out}");
+        assert!(with_bom[30..93].starts_with("pub fn greet"));
+        let out = super::super::generate_with(
+            std::path::Path::new("greet.rs"),
+            crate::types::FileType::Code(Lang::Rust),
+            &with_bom,
+            with_bom.as_bytes(),
+            false,
+            &with_bytes,
+        );
+        assert!(out.contains("[4-6 b30-93] pub fn greet"), "{out}");
+    }
+
+    #[test]
+    fn format_version_1_is_pinned() {
+        let code = r"use std::fmt;
+use std::io::{self, Read};
+
+/// A parsed token.
+pub struct Token {
+    pub text: String,
+}
+
+impl Token {
+    #[deprecated]
+    pub fn new(text: &str) -> Self {
+        Self { text: text.into() }
+    }
+}
+
+pub const LIMIT: usize = 8;
+";
+        let v1 = OutlineOptions {
+            format_version: FormatVersion::V1,
+            byte_ranges: true,
+            unused_imports: true,
+            ..OutlineOptions::default()
+        };
+        // Scripts pinned to version 1 parse exactly this layout; a format
+        // change must land as a new version, never as an edit here.
+        let expected = "\
+[1-]   imports: std::fmt, std::io
+       unused (heuristic): fmt, io, Read
+[5-7 b62-104] struct Token  // A parsed token.
+[9-14 b106-216] mod impl Token
+  [11-13 b141-214] fn new [deprecated]
+             pub fn new(text: &str) -> Self
+[16 b218-245] const LIMIT";
+        assert_eq!(outline_with(code, Lang::Rust, usize::MAX, &v1), expected);
+
+        // Declarations outlined since version 1 stay out of it: Go types,
+        // interface methods and receiver groups, Rust trait methods and
+        // macros, C `#define`s and `#include`s.
+        let v1 = OutlineOptions {
+            format_version: FormatVersion::V1,
+            ..OutlineOptions::default()
+        };
+        let go = "package server
+
+type Server struct {
+\taddr string
+}
+
+type Store interface {
+\tGet(id string) error
+}
+
+func (s *Server) Start() error {
+\treturn nil
+}
+
+func New() *Server {
+\treturn nil
+}
+";
+        let expected = "\
+[11-13]      fn Start
+           func (s *Server) Start() error
+[15-17]      fn New
+           func New() *Server";
+        assert_eq!(outline_with(go, Lang::Go, usize::MAX, &v1), expected);
+        let rust = r"pub trait Shape {
+    fn area(&self) -> f64;
+    fn name(&self) -> String {
+        String::new()
+    }
+}
+
+macro_rules! square {
+    ($x:expr) => {
+        $x * $x
+    };
+}
+";
+        let expected = "\
+[1-6]        interface Shape
+  [3-5]        fn name
+             fn name(&self) -> String";
+        assert_eq!(outline_with(rust, Lang::Rust, usize::MAX, &v1), expected);
+        let c = "#include <stdio.h>
+
+#define MAX 100
+#define SQ(x) ((x) * (x))
+
+int twice(int x) {
+    return SQ(x) / 2;
+}
+";
+        let expected = "\
+[6-8]        fn <anonymous>
+           int twice(int x)";
+        assert_eq!(outline_with(c, Lang::C, usize::MAX, &v1), expected);
+
+        assert_eq!(FormatVersion::LATEST, FormatVersion::default());
+        assert_eq!("1".parse(), Ok(FormatVersion::V1));
+        assert_eq!("2".parse(), Ok(FormatVersion::V2));
+        assert_eq!("3".parse(), Ok(FormatVersion::V3));
+        assert_eq!("4".parse(), Ok(FormatVersion::V4));
+        assert!("5".parse::<FormatVersion>().is_err());
+    }
+
+    #[test]
+    fn earlier_versions_leave_out_later_additions() {
+        let code = "pub enum Mode {
+    Fast,
+    Slow,
+}
+
+pub async fn fetch<T: Clone>(
+    url: &str,
+) -> T {
+    todo!()
+}
+";
+        let at = |format_version| {
+            let opts = OutlineOptions {
+                format_version,
+                ..OutlineOptions::default()
+            };
+            outline_with(code, Lang::Rust, usize::MAX, &opts)
+        };
+        let v1 = "\
+[1-4]        enum Mode
+[6-10]       fn fetch
+           pub async fn fetch<T: Clone>(";
+        assert_eq!(at(FormatVersion::V1), v1);
+        let v2 = "\
+[1-4]        pub enum Mode
+  [2]          const Fast
+  [3]          const Slow
+[6-10]       pub fn fetch
+           pub async fn fetch<T: Clone>(";
+        assert_eq!(at(FormatVersion::V2), v2);
+        let v3 = "\
+[1-4]        pub enum Mode
+  [2]          const Fast
+  [3]          const Slow
+[6-10]       pub fn fetch<T: Clone> [async]
+           pub async fn fetch<T: Clone>(url: &str) -> T";
+        assert_eq!(at(FormatVersion::V3), v3);
+
+        let py = "@app.get(\"/\")\ndef index():\n    pass\n";
+        let opts = OutlineOptions {
+            format_version: FormatVersion::V2,
+            ..OutlineOptions::default()
+        };
+        assert_eq!(
+            outline_with(py, Lang::Python, usize::MAX, &opts),
+            "[1-3]        fn index\n           def index()"
+        );
+    }
+
+    #[test]
+    fn version_3_leaves_out_version_4_declarations() {
+        let at = |code, lang, format_version| {
+            let opts = OutlineOptions {
+                format_version,
+                ..OutlineOptions::default()
+            };
+            outline_with(code, lang, usize::MAX, &opts)
+        };
+        let go = "package server
+
+type Store interface {
+\tGet(id string) error
+}
+
+func (s *Server) Start() error {
+\treturn nil
+}
+";
+        assert_eq!(
+            at(go, Lang::Go, FormatVersion::V3),
+            "\
+[3-5]        interface Store
+[7-9]        fn Start
+           func (s *Server) Start() error"
+        );
+        assert_eq!(
+            at(go, Lang::Go, FormatVersion::V4),
+            "\
+[3-5]        interface Store
+  [4]          fn Get
+             Get(id string) error
+[7-9]        mod Server
+  [7-9]        fn Start
+             func (s *Server) Start() error"
+        );
+
+        let rust = "pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+macro_rules! square {
+    ($x:expr) => {
+        $x * $x
+    };
+}
+";
+        assert_eq!(
+            at(rust, Lang::Rust, FormatVersion::V3),
+            "[1-3]        pub interface Shape"
+        );
+        assert_eq!(
+            at(rust, Lang::Rust, FormatVersion::V4),
+            "\
+[1-3]        pub interface Shape
+  [2]          fn area
+             fn area(&self) -> f64
+[5-9]        macro square!"
+        );
+
+        let java = "interface Task {\n    void run();\n}\n";
+        assert_eq!(
+            at(java, Lang::Java, FormatVersion::V3),
+            "[1-3]        interface Task\n  [2]          fn run\n             void run();"
+        );
+        assert_eq!(
+            at(java, Lang::Java, FormatVersion::V4),
+            "[1-3]        interface Task\n  [2]          fn run\n             void run()"
+        );
+
+        let cpp = "namespace geo {\nclass Point {};\n}\n";
+        assert_eq!(
+            at(cpp, Lang::Cpp, FormatVersion::V3),
+            "[1-3]        mod geo"
+        );
+        assert_eq!(
+            at(cpp, Lang::Cpp, FormatVersion::V4),
+            "[1-3]        namespace geo\n  [2]          class Point"
+        );
+    }
+
+    #[test]
+    fn overloads_are_grouped() {
+        let ts_code = r"export function parse(x: number): Date;
+export function parse(x: string): Date;
+export function parse(x: number | string): Date {
+  return new Date(x);
+}
+
+class Parser {
+  read(x: number): void;
+  read(x: string): void;
+  read(x: unknown): void {}
+  close(): void {}
+}
+";
+        let expected = "\
+[1-5]        export parse (3 overloads)
+           export function parse(x: number): Date
+           export function parse(x: string): Date
+           export function parse(x: number | string): Date
+[7-12]       class Parser
+  [8-10]       fn read (3 overloads)
+             read(x: number): void
+             read(x: string): void
+             read(x: unknown): void
+  [11]         fn close
+             close(): void";
+        assert_eq!(outline(ts_code, Lang::TypeScript, usize::MAX), expected);
+    }
+
+    #[test]
+    fn accessor_pairs_collapse() {
+        let java = r"public class User {
+    private String name;
+
+    /** The display name. */
+    public String getName() {
+        return name;
+    }
+
+    public void setName(String name) {
+        this.name = name;
+    }
+
+    public void setActive(boolean active) {
+        this.active = active;
+    }
+
+    public boolean isActive() {
+        return active;
+    }
+
+    public int getAge() {
+        return age;
+    }
+
+    public void save() {}
+}
+";
+        let collapse = OutlineOptions {
+            collapse_accessors: true,
+            ..OutlineOptions::default()
+        };
+        let expected = "\
+[1-26]       pub class User
+  [5-11]       pub prop Name { get; set }
+             public String Name  // The display name.
+  [13-19]      pub prop Active { get; set }
+             public boolean Active
+  [21-23]      pub fn getAge
+             public int getAge()
+  [25]         pub fn save
+             public void save()";
+        assert_eq!(
+            outline_with(java, Lang::Java, usize::MAX, &collapse),
+            expected
+        );
+        assert!(outline(java, Lang::Java, usize::MAX).contains("fn setName"));
+
+        let ts = "class Box {\n  get size(): number { return 1; }\n  set size(v: number) {}\n}\n";
+        assert!(outline_with(ts, Lang::TypeScript, usize::MAX, &collapse)
+            .contains("  [2-3]        prop size { get; set }\n             size: number"));
+    }
+
+    #[test]
+    fn grouped_buckets_by_kind() {
+        let code = r"use std::fmt;
+use std::io;
+
+pub const LIMIT: usize = 8;
+
+pub fn parse(s: &str) -> Token {
+    Token::Word(s.into())
+}
+
+pub enum Token {
+    Word(String),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Ok(())
+    }
+}
+
+pub struct Lexer;
+
+fn helper() {}
+";
+        let expected = "\
+Imports:
+[1-]   imports: std::fmt, std::io
+
+Types:
+[10-12]      pub enum Token
+  [11]         const Word
+             Word(String)
+[20]         pub struct Lexer
+
+Impls & modules:
+[14-18]      mod impl Token
+  [15-17]      fn fmt
+             fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+
+Functions:
+[6-8]        pub fn parse
+           pub fn parse(s: &str) -> Token
+[22]         fn helper
+           fn helper()
+
+Constants:
+[4]          pub const LIMIT";
+        assert_eq!(
+            grouped(code, Lang::Rust, usize::MAX, &OutlineOptions::default()),
+            expected
+        );
+    }
+
+    #[test]
+    fn visibility_markers_show_from_version_2() {
+        let code = "pub mod api {
+    pub struct Client;
+
+    pub(crate) fn connect() {}
+
+    fn retry() {}
+}
+
+pub(super) const MAX: u8 = 3;
+
+struct Internal {
+    pub(in crate::api) id: u32,
+}
+";
+        let expected = "\
+[1-7]        pub mod api
+  [2]          pub struct Client
+  [4]          pub(crate) fn connect
+             pub(crate) fn connect()
+  [6]          fn retry
+             fn retry()
+[9]          pub(super) const MAX
+[11-13]      struct Internal
+  [12]         pub(in crate::api) prop id
+             pub(in crate::api) id: u32";
+        assert_eq!(outline(code, Lang::Rust, usize::MAX), expected);
+
+        let v1 = OutlineOptions {
+            format_version: FormatVersion::V1,
+            ..OutlineOptions::default()
+        };
+        let pinned = outline_with(code, Lang::Rust, usize::MAX, &v1);
+        assert!(
+            pinned.starts_with("[1-7]        mod api\n  [2]          struct Client"),
+            "{pinned}"
+        );
+
+        let ts = "class Account {\n  private check(): void {}\n  protected audit(): void {}\n  public deposit(n: number): void {}\n}\n";
+        let expected = "\
+[1-5]        class Account
+  [2]          priv fn check
+             private check(): void
+  [3]          protected fn audit
+             protected audit(): void
+  [4]          pub fn deposit
+             public deposit(n: number): void";
+        assert_eq!(outline(ts, Lang::TypeScript, usize::MAX), expected);
+    }
+
+    #[test]
+    fn struct_fields_are_children() {
+        let rust_code = "/// A user.
+pub struct User {
+    /// Primary key.
+    pub id: u64,
+    name: String,
+}
+";
+        let expected = "\
+[2-6]        pub struct User  // A user.
+  [4]          pub prop id
+             pub id: u64  // Primary key.
+  [5]          prop name
+             name: String";
+        assert_eq!(outline(rust_code, Lang::Rust, usize::MAX), expected);
+
+        let go_code = "package geo
+
+// Point is a labelled point.
+type Point struct {
+\tX, Y  int
+\tLabel string `json:\"label\"`
+\tio.Reader
+}
+
+type (
+\tID    int
+\tShape interface{ Area() float64 }
+)
+";
+        let expected = "\
+[4-8]        struct Point  // Point is a labelled point.
+  [5]          prop X
+             X int
+  [5]          prop Y
+             Y int
+  [6]          prop Label
+             Label string
+  [7]          prop io.Reader
+[11]         type ID
+[12]         interface Shape
+  [12]         fn Area
+             Area() float64";
+        assert_eq!(outline(go_code, Lang::Go, usize::MAX), expected);
+
+        let v1 = OutlineOptions {
+            format_version: FormatVersion::V1,
+            ..OutlineOptions::default()
+        };
+        assert_eq!(
+            outline_with(rust_code, Lang::Rust, usize::MAX, &v1),
+            "[2-6]        struct User  // A user."
+        );
+    }
+
+    #[test]
+    fn go_interface_methods_are_children() {
+        let go_code = "package store
+
+// Store persists records.
+type Store interface {
+\tio.Closer
+\t// Get loads one record.
+\tGet(ctx context.Context, id string) (*Record, error)
+\tput(r *Record) error
+}
+";
+        let expected = "\
+[4-9]        interface Store  // Store persists records.
+  [5]          interface io.Closer
+  [7]          fn Get
+             Get(ctx context.Context, id string) (*Record, error)  // Get loads one record.
+  [8]          fn put
+             put(r *Record) error";
+        assert_eq!(outline(go_code, Lang::Go, usize::MAX), expected);
+    }
+
+    #[test]
+    fn go_methods_group_under_their_receiver() {
+        let go_code = "package server
+
+type Server struct {
+\taddr string
+}
+
+// Start listens on addr.
+func (s *Server) Start() error {
+\treturn nil
+}
+
+func New(addr string) *Server {
+\treturn &Server{addr: addr}
+}
+
+func (s Server) Addr() string {
+\treturn s.addr
+}
+";
+        let expected = "\
+[3-5]        struct Server
+  [4]          prop addr
+             addr string
+[8-18]       mod Server
+  [8-10]       fn Start
+             func (s *Server) Start() error  // Start listens on addr.
+  [16-18]      fn Addr
+             func (s Server) Addr() string
+[12-14]      fn New
+           func New(addr string) *Server";
+        assert_eq!(outline(go_code, Lang::Go, usize::MAX), expected);
+    }
+
+    #[test]
+    fn enum_variants_are_children() {
+        let rust_code = "/// Colours.
+pub enum Color {
+    /// Warm.
+    Red,
+    Green = 2,
+    Custom(u8, u8, u8),
+    Named { name: String },
+}
+";
+        let expected = "\
+[2-8]        pub enum Color  // Colours.
+  [4]          const Red  // Warm.
+  [5]          const Green
+             Green = 2
+  [6]          const Custom
+             Custom(u8, u8, u8)
+  [7]          const Named
+             Named { name: String }";
+        assert_eq!(outline(rust_code, Lang::Rust, usize::MAX), expected);
+
+        let ts = outline("enum Dir { Up = 1, Down }\n", Lang::TypeScript, usize::MAX);
+        assert_eq!(
+            ts,
+            "[1]          enum Dir\n  [1]          const Up\n             Up = 1\n  [1]          const Down"
+        );
+
+        let swift = outline(
+            "enum Shape {\n  case circle(Double), square\n}\n",
+            Lang::Swift,
+            usize::MAX,
+        );
+        assert!(
+            swift.contains("const circle\n  [2]          const square"),
+            "{swift}"
+        );
+    }
+
+    #[test]
+    fn java_nested_types_show_members() {
+        let java_code = r"package store;
+
+/** LRU cache. */
+public class Cache<K, V> {
+    private final int capacity;
+
+    public Cache(int capacity) {
+        this.capacity = capacity;
+    }
+
+    public V get(K key) {
+        return null;
+    }
+
+    /** Eviction strategies. */
+    public enum Policy {
+        LRU,
+        LFU;
+
+        public boolean isRecent() {
+            return this == LRU;
+        }
+    }
+
+    public static class Builder {
+        private int capacity;
+
+        public Builder capacity(int n) {
+            this.capacity = n;
+            return this;
+        }
+
+        public Cache<String, String> build() {
+            return new Cache<>(capacity);
+        }
+    }
+
+    private class Node {
+        K key;
+        V value;
+    }
+}
+";
+        let expected = "\
+[4-42]       pub class Cache<K, V>  // LRU cache.
+  [7-9]        pub fn Cache
+             public Cache(int capacity)
+  [11-13]      pub fn get
+             public V get(K key)
+  [16-23]      pub enum Policy  // Eviction strategies.
+    [17]         const LRU
+    [18]         const LFU
+    [20-22]      pub fn isRecent
+               public boolean isRecent()
+  [25-36]      pub class Builder (static)
+    [28-31]      pub fn capacity
+               public Builder capacity(int n)
+    [33-35]      pub fn build
+               public Cache<String, String> build()
+  [38-41]      priv class Node";
+        assert_eq!(outline(java_code, Lang::Java, usize::MAX), expected);
+    }
+
+    #[test]
+    fn deprecated_symbols_are_flagged() {
+        fn deprecated(code: &str, lang: Lang) -> Vec<String> {
+            fn walk(entries: &[OutlineEntry], out: &mut Vec<String>) {
+                for e in entries {
+                    if e.deprecated {
+                        out.push(e.name.clone());
+                    }
+                    walk(&e.children, out);
+                }
+            }
+            let mut out = Vec::new();
+            walk(&get_outline_entries(code, lang), &mut out);
+            out
+        }
+
+        let rust = "#[deprecated(note = \"use new_helper\")]\npub fn old_helper() {}\npub fn new_helper() {}\n";
+        assert_eq!(deprecated(rust, Lang::Rust), ["old_helper"]);
+        assert_eq!(
+            outline(rust, Lang::Rust, usize::MAX).lines().next(),
+            Some("[2]          pub fn old_helper [deprecated]")
+        );
+
+        let java = "class A {\n    @Deprecated\n    void oldMethod() {}\n    /** @deprecated use newMethod */\n    void older() {}\n    void newMethod() {}\n}\n";
+        assert_eq!(deprecated(java, Lang::Java), ["oldMethod", "older"]);
+
+        let ts = "/**\n * @deprecated Use parseDate.\n */\nfunction parse(s: string) {}\nfunction parseDate(s: string) {}\n";
+        assert_eq!(deprecated(ts, Lang::TypeScript), ["parse"]);
+
+        let cs = "class A {\n    [Obsolete(\"use New\")]\n    public void Old() {}\n    public void New() {}\n}\n";
+        assert_eq!(deprecated(cs, Lang::CSharp), ["Old"]);
+
+        let py = "@deprecated(\"use new\")\ndef old():\n    pass\n\ndef new():\n    pass\n";
+        assert_eq!(deprecated(py, Lang::Python), ["old"]);
+    }
+
+    #[test]
+    fn scala_outline_constructs() {
+        let scala_code = r#"
+package example
+
+import scala.util.Try
+
+trait DataSource {
+  def load(): String
+}
+
+class Database {
+  val connectionString = "jdbc:..."
+  var connected = false
+  
+  def connect(): Unit = {}
+}
+
+object Database {
+  def create(): Database = new Database()
+}
+
+enum Color {
+  case Red, Green, Blue
+}
+
+type UserId = String
+"#;
+
+        let outline = outline(scala_code, Lang::Scala, 1000);
+
+        assert!(outline.contains("trait DataSource"));
+        assert!(outline.contains("class Database"));
+        assert!(outline.contains("object Database"));
+        assert!(outline.contains("enum Color"));
+        assert!(outline.contains("type UserId"));
+        assert!(outline.contains("val connectionString"));
+        assert!(outline.contains("var connected"));
+        assert!(outline.contains("def load"));
+        assert!(outline.contains("def connect"));
+        assert!(outline.contains("def create"));
+    }
+
+    #[test]
+    fn php_outline_constructs() {
+        let php_code = r"<?php
+namespace App\Services;
+
+use App\Support\Client;
+
+trait LogsQueries {
+    public function log(string $query): void {}
+}
+
+class UserService {
+    use LogsQueries;
+
+    public function __construct(private Client $client) {}
+
+    public function findUser(int $id): array {
+        return $this->client->loadUser($id);
+    }
+}
+";
 
         let outline = outline(php_code, Lang::Php, 1000);
 
@@ -1,16 +1,181 @@
+pub mod build_script;
 pub mod code;
 pub mod fallback;
 pub mod markdown;
 pub mod structured;
+pub mod stub;
 pub mod tabular;
 pub mod test_file;
 
 use std::path::Path;
+use std::str::FromStr;
 
-use crate::types::FileType;
+use crate::types::{FileType, Lang};
 
 const OUTLINE_CAP: usize = 100; // max outline lines for huge files
 
+/// How code outlines are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutlineStyle {
+    /// `[start-end] kind name` entries with signatures and docs.
+    #[default]
+    Standard,
+    /// Top-level symbol names only, one per line — the cheapest index.
+    Names,
+    /// Public declarations without bodies, like a `.d.ts` / `.pyi`.
+    Stub,
+    /// Standard entries bucketed by kind under section headers.
+    Grouped,
+    /// The entry tree as JSON, for tools that want structure, not text.
+    Json,
+}
+
+impl FromStr for OutlineStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "standard" => Ok(Self::Standard),
+            "names" => Ok(Self::Names),
+            "stub" => Ok(Self::Stub),
+            "grouped" => Ok(Self::Grouped),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "unknown outline style \"{s}\" (expected: standard, names, stub, grouped, json)"
+            )),
+        }
+    }
+}
+
+/// Version of the standard and grouped outline text. The human format keeps
+/// evolving; scripts that parse it pin a version and keep getting that layout
+/// after upgrades. `Default` is the latest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum FormatVersion {
+    /// `[start-end]  kind name` entries, signatures and `// doc` underneath,
+    /// children indented two spaces, imports summarized as `imports: a(2), b`.
+    V1,
+    /// Version 1 with a visibility marker before the kind where the source
+    /// declares one: `[3-9]  pub struct Config`, and enum variants and struct
+    /// fields listed as `const` and `prop` children.
+    V2,
+    /// Version 2 with signatures joined across lines, `[async]`-style modifier
+    /// tags and type parameters after names (`fn fetch<T>`), and decorators
+    /// and annotations on their own lines above the entry.
+    V3,
+    /// Version 3 with more declarations outlined: Rust trait methods,
+    /// associated types and `macro_rules!`, C/C++ `#define`s and `#include`s,
+    /// Go interface methods and methods grouped under their receiver type,
+    /// C++ classes, structs and templates inside `namespace` entries, Ruby
+    /// modules, classes and methods, and TypeScript exported enum members
+    /// with `[const]` on const enums. Signatures drop a trailing `;`.
+    #[default]
+    V4,
+}
+
+impl FormatVersion {
+    pub const LATEST: Self = Self::V4;
+}
+
+impl FromStr for FormatVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "1" => Ok(Self::V1),
+            "2" => Ok(Self::V2),
+            "3" => Ok(Self::V3),
+            "4" => Ok(Self::V4),
+            _ => Err(format!(
+                "unknown format version \"{s}\" (supported: 1, 2, 3, 4)"
+            )),
+        }
+    }
+}
+
+/// Caller-selected outline rendering. `Default` is what the smart view produces.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutlineOptions {
+    pub style: OutlineStyle,
+    /// Lines of each function body shown under its entry in the standard style.
+    /// 0 shows signatures only.
+    pub body_lines: usize,
+    /// Cut symbol names longer than this many bytes in the standard and grouped
+    /// styles, ending them with `...`. Caps below 3 count as 3. Signatures have
+    /// their own cap.
+    pub max_name_len: Option<usize>,
+    /// Add byte offsets to entry ranges in the standard and grouped styles:
+    /// `[10-20 b340-890]`, end exclusive, so scripts can slice the file.
+    pub byte_ranges: bool,
+    /// Keep only entries overlapping these lines in the standard and grouped
+    /// styles — the symbols a change touched. Parents of a touched child stay,
+    /// with their untouched children dropped.
+    pub changed_lines: Option<LineRanges>,
+    /// Collapse a getter next to its setter (`getX`/`setX`, TS `get x`/`set x`)
+    /// into one `X { get; set }` property in the standard and grouped styles.
+    pub collapse_accessors: bool,
+    /// List imported names the file never mentions, `unused (heuristic): a, b`,
+    /// under the import summary in the standard and grouped styles. A textual
+    /// guess: a Rust trait imported only for its methods looks unused.
+    pub unused_imports: bool,
+    /// Layout of the standard and grouped outline text.
+    pub format_version: FormatVersion,
+    /// Most outline lines to show. `None` keeps the default: 100 for files too
+    /// large to show whole, no limit otherwise.
+    pub max_lines: Option<usize>,
+    /// Cut signatures longer than this many bytes in the standard and grouped
+    /// styles, ending them with `...`. Signatures are already capped at 120
+    /// bytes when extracted, so only a smaller value has an effect.
+    pub max_sig_len: Option<usize>,
+    /// Spaces per nesting level in the standard and grouped styles. `None`
+    /// keeps the default of 2.
+    pub indent: Option<usize>,
+}
+
+/// Inclusive 1-based line ranges, e.g. the new-side lines of a diff.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineRanges(pub Vec<(u32, u32)>);
+
+impl LineRanges {
+    /// True when `start..=end` shares a line with any range.
+    #[must_use]
+    pub fn overlaps(&self, start: u32, end: u32) -> bool {
+        self.0.iter().any(|&(lo, hi)| start <= hi && lo <= end)
+    }
+}
+
+impl FromStr for LineRanges {
+    type Err = String;
+
+    /// Comma-separated lines or `start-end` ranges: `12-30,88`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid line ranges \"{s}\" (expected e.g. \"12-30,88\")");
+        let mut ranges = Vec::new();
+        for item in s.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+            let (lo, hi) = item.split_once('-').unwrap_or((item, item));
+            let lo: u32 = lo.trim().parse().map_err(|_| invalid())?;
+            let hi: u32 = hi.trim().parse().map_err(|_| invalid())?;
+            if lo == 0 || hi < lo {
+                return Err(invalid());
+            }
+            ranges.push((lo, hi));
+        }
+        if ranges.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self(ranges))
+    }
+}
+
+impl OutlineOptions {
+    /// True when the caller asked for something other than the smart-view outline.
+    /// Non-default outlines are never cached — the cache holds standard outlines only.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Generate a smart view based on file type.
 pub fn generate(
     path: &Path,
@@ -19,7 +184,57 @@ pub fn generate(
     buf: &[u8],
     capped: bool,
 ) -> String {
-    let max_lines = if capped { OUTLINE_CAP } else { usize::MAX };
+    generate_with(
+        path,
+        file_type,
+        content,
+        buf,
+        capped,
+        &OutlineOptions::default(),
+    )
+}
+
+/// Outline an in-memory buffer as if it were the file at `path`. The path only
+/// drives language and test-file detection — it need not exist — so an editor's
+/// unsaved buffer outlines the same as the saved file would.
+#[must_use]
+pub fn outline_named(content: &str, path: &Path) -> String {
+    let file_type = crate::lang::detect_file_type(path);
+    generate(path, file_type, content, content.as_bytes(), false)
+}
+
+/// Generate a view with explicit outline options. Options only affect code files,
+/// except `max_lines`; other file types always get their standard view.
+pub fn generate_with(
+    path: &Path,
+    file_type: FileType,
+    content: &str,
+    buf: &[u8],
+    capped: bool,
+    opts: &OutlineOptions,
+) -> String {
+    let max_lines = match opts.max_lines {
+        Some(max) => max,
+        None if capped => OUTLINE_CAP,
+        None => usize::MAX,
+    };
+    // The standard and grouped styles see the BOM so byte offsets count it.
+    let raw = content;
+    let content = crate::lang::strip_bom(content);
+    let buf = buf.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(buf);
+
+    if let FileType::Code(lang) = file_type {
+        match opts.style {
+            OutlineStyle::Standard if !opts.is_default() => {
+                return code::outline_with(raw, lang, max_lines, opts);
+            }
+            OutlineStyle::Standard => {}
+            OutlineStyle::Names => return code::names(content, lang, max_lines),
+            OutlineStyle::Stub => return stub::stub(content, lang, max_lines),
+            OutlineStyle::Grouped => return code::grouped(raw, lang, max_lines, opts),
+            OutlineStyle::Json => return code::json(content, lang),
+        }
+    }
 
     // Test files get special treatment regardless of language
     if crate::types::is_test_file(path) {
@@ -31,6 +246,18 @@ pub fn generate(
     }
 
     match file_type {
+        FileType::Code(Lang::Rust) if build_script::is_build_script(path) => {
+            let mut out = code::outline(content, Lang::Rust, max_lines);
+            let directives = build_script::directives(content, max_lines);
+            if !directives.is_empty() {
+                out.push_str("\n\ncargo directives:");
+                for d in directives {
+                    out.push_str("\n  ");
+                    out.push_str(&d);
+                }
+            }
+            out
+        }
         FileType::Code(lang) => code::outline(content, lang, max_lines),
         FileType::Markdown => markdown::outline(buf, max_lines),
         FileType::StructuredData => structured::outline(path, content, max_lines),
@@ -39,3 +266,46 @@ pub fn generate(
         FileType::Other => fallback::head_tail(content),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outline_named_uses_path_for_detection_only() {
+        let content = "pub fn render() {}\n";
+        let path = Path::new("/nonexistent/unsaved/button.rs");
+        assert_eq!(
+            outline_named(content, path),
+            "[1]          pub fn render\n           pub fn render()"
+        );
+        // Same bytes under a name with no language: plain head view, not an outline.
+        assert_eq!(
+            outline_named(content, Path::new("button.txt")),
+            fallback::head_tail(content)
+        );
+    }
+
+    #[test]
+    fn max_lines_overrides_the_huge_file_cap() {
+        let content = (0..150)
+            .map(|i| format!("fn f{i}() {{}}\n"))
+            .collect::<Vec<_>>()
+            .concat();
+        let path = Path::new("big.rs");
+        let entries = |max_lines| {
+            let opts = OutlineOptions {
+                max_lines,
+                ..OutlineOptions::default()
+            };
+            let file_type = FileType::Code(Lang::Rust);
+            generate_with(path, file_type, &content, content.as_bytes(), true, &opts)
+                .lines()
+                .filter(|l| l.starts_with('['))
+                .count()
+        };
+        assert_eq!(entries(None), OUTLINE_CAP);
+        assert_eq!(entries(Some(10)), 10);
+        assert_eq!(entries(Some(1000)), 150);
+    }
+}
@@ -1,11 +1,9 @@
 use std::path::Path;
 
-/// Depth-limited outline for JSON, YAML, TOML.
+/// Depth-limited outline for JSON.
 pub fn outline(path: &Path, content: &str, max_lines: usize) -> String {
     match path.extension().and_then(|e| e.to_str()) {
         Some("json") => json_outline(content, max_lines),
-        Some("yaml" | "yml") => yaml_outline(content, max_lines),
-        Some("toml") => toml_outline(content, max_lines),
         _ => key_value_outline(content, max_lines),
     }
 }
@@ -120,101 +118,6 @@ fn truncate_json_value(v: &serde_json::Value, max: usize) -> String {
     }
 }
 
-/// YAML outline via line scan — no parser needed.
-/// Detect keys by: optional whitespace, then a word, then `: ` or `:`+EOL.
-/// Indentation level = nesting depth (2-space standard).
-fn yaml_outline(content: &str, max_lines: usize) -> String {
-    let mut entries = Vec::new();
-    for (i, line) in content.lines().enumerate() {
-        if entries.len() >= max_lines {
-            break;
-        }
-        let trimmed = line.trim_start();
-        // Skip comments, blank lines, and list items
-        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('-') {
-            continue;
-        }
-        // Look for key: value or key: (block)
-        if let Some(colon) = trimmed.find(':') {
-            let key = &trimmed[..colon];
-            // Keys shouldn't contain spaces (that would be a value line)
-            if key.contains(' ') {
-                continue;
-            }
-            let indent = line.len() - trimmed.len();
-            let depth = indent / 2;
-            if depth <= 2 {
-                let prefix = "  ".repeat(depth);
-                let after_colon = trimmed[colon + 1..].trim();
-                if after_colon.is_empty() {
-                    // Block mapping — just show key
-                    entries.push(format!("[{}] {prefix}{key}:", i + 1));
-                } else {
-                    let val = if after_colon.len() > 40 {
-                        format!("{}...", crate::types::truncate_str(after_colon, 37))
-                    } else {
-                        after_colon.to_string()
-                    };
-                    entries.push(format!("[{}] {prefix}{key}: {val}", i + 1));
-                }
-            }
-        }
-    }
-    entries.join("\n")
-}
-
-fn toml_outline(content: &str, max_lines: usize) -> String {
-    let value: toml::Value = match content.parse() {
-        Ok(v) => v,
-        Err(e) => return format!("[parse error: {e}]"),
-    };
-    let mut lines = Vec::new();
-    walk_toml(&value, 0, 2, max_lines, &mut lines);
-    lines.join("\n")
-}
-
-fn walk_toml(
-    value: &toml::Value,
-    depth: usize,
-    max_depth: usize,
-    max_lines: usize,
-    lines: &mut Vec<String>,
-) {
-    if lines.len() >= max_lines {
-        return;
-    }
-    let indent = "  ".repeat(depth);
-
-    if let toml::Value::Table(table) = value {
-        for (key, val) in table {
-            if lines.len() >= max_lines {
-                return;
-            }
-            match val {
-                toml::Value::Table(inner) if depth < max_depth => {
-                    lines.push(format!("{indent}[{key}]"));
-                    walk_toml(val, depth + 1, max_depth, max_lines, lines);
-                }
-                toml::Value::Table(inner) => {
-                    lines.push(format!("{indent}{key}: {{{} keys}}", inner.len()));
-                }
-                toml::Value::Array(arr) => {
-                    lines.push(format!("{indent}{key}: [{} items]", arr.len()));
-                }
-                _ => {
-                    let val_str = val.to_string();
-                    let truncated = if val_str.len() > 40 {
-                        format!("{}...", crate::types::truncate_str(&val_str, 37))
-                    } else {
-                        val_str
-                    };
-                    lines.push(format!("{indent}{key}: {truncated}"));
-                }
-            }
-        }
-    }
-}
-
 fn key_value_outline(content: &str, max_lines: usize) -> String {
     content
         .lines()
@@ -0,0 +1,526 @@
+//! Public API stub: declarations without bodies, roughly a `.d.ts` / `.pyi`.
+//! Built from outline entries — signatures come from `extract_signature`,
+//! type declarations are copied verbatim since their bodies *are* the interface.
+
+use crate::lang::outline::get_outline_entries;
+use crate::types::{Lang, OutlineEntry, OutlineKind};
+
+/// Emit a stub of the file's public surface. Private items are dropped;
+/// function bodies and initializer values are replaced by a placeholder.
+/// A Python module's `__all__`, when present, decides which top-level names
+/// are public instead of the underscore convention.
+pub fn stub(content: &str, lang: Lang, max_lines: usize) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let exports = if lang == Lang::Python {
+        python_all(&lines)
+    } else {
+        None
+    };
+    let mut out = Vec::new();
+    // Go methods are grouped by receiver type; in source they stand alone.
+    let mut entries: Vec<OutlineEntry> = get_outline_entries(content, lang)
+        .into_iter()
+        .flat_map(|e| {
+            if lang == Lang::Go && e.kind == OutlineKind::Module {
+                e.children
+            } else {
+                vec![e]
+            }
+        })
+        .collect();
+    entries.sort_by_key(|e| e.start_line);
+    for entry in entries {
+        let public = match &exports {
+            Some(names) => entry.kind != OutlineKind::Import && names.contains(&entry.name),
+            None => is_public(&entry, &lines, lang),
+        };
+        if public {
+            render(&entry, &lines, lang, 0, &mut out);
+        }
+    }
+    out.truncate(max_lines);
+    out.join("\n")
+}
+
+/// Visibility filter: would another module be able to use this item?
+/// Keyword-based where the language has modifiers, naming conventions otherwise.
+fn is_public(entry: &OutlineEntry, lines: &[&str], lang: Lang) -> bool {
+    let text = first_line(entry, lines);
+    let has_prefix = |prefixes: &[&str]| prefixes.iter().any(|p| text.starts_with(p));
+    match entry.kind {
+        OutlineKind::Import => false,
+        // Impl blocks carry no modifier — kept when any member survives.
+        OutlineKind::Module if text.starts_with("impl") => true,
+        _ => match lang {
+            Lang::Rust => rust_visibility(text) == Visibility::Public,
+            Lang::TypeScript | Lang::Tsx | Lang::JavaScript => entry.kind == OutlineKind::Export,
+            Lang::Python => {
+                !entry.name.starts_with('_')
+                    || (entry.name.starts_with("__") && entry.name.ends_with("__"))
+            }
+            Lang::Go => entry.name.starts_with(|c: char| c.is_uppercase()),
+            // Dot-prefixed names are hidden from `ls()` and package exports by convention.
+            Lang::R => !entry.name.starts_with('.'),
+            Lang::Perl => !entry.name.starts_with('_'),
+            // Container fields are always visible; declarations need `pub`.
+            Lang::Zig => entry.kind == OutlineKind::Property || has_prefix(&["pub ", "export "]),
+            Lang::Java | Lang::CSharp => text.split_whitespace().any(|w| w == "public"),
+            Lang::C | Lang::Cpp => !text.starts_with("static "),
+            Lang::Php | Lang::Kotlin | Lang::Scala | Lang::Swift => {
+                !has_prefix(&["private ", "protected ", "internal ", "fileprivate "])
+            }
+            Lang::Ruby
+            | Lang::Dockerfile
+            | Lang::Make
+            | Lang::Nix
+            | Lang::Lua
+            | Lang::OCaml
+            | Lang::Toml
+            | Lang::Yaml
+            | Lang::Vue
+            | Lang::Registered(_) => true,
+        },
+    }
+}
+
+/// Names listed by top-level `__all__ = [...]` (or `(...)`) assignments, plus
+/// any `__all__ += [...]`. String literals only; `None` without an assignment.
+fn python_all(lines: &[&str]) -> Option<Vec<String>> {
+    let mut names: Option<Vec<String>> = None;
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        i += 1;
+        let Some(rest) = line.strip_prefix("__all__") else {
+            continue;
+        };
+        // `__all__ = [`, `__all__: list[str] = [`, `__all__ += [`.
+        let rest = rest.trim_start();
+        let value = match rest.strip_prefix(':') {
+            Some(annotated) => annotated.split_once('=').map(|(_, v)| v),
+            None => rest.strip_prefix("+=").or_else(|| rest.strip_prefix('=')),
+        };
+        let Some(value) = value.filter(|v| !v.starts_with('=')) else {
+            continue;
+        };
+        let mut text = value.to_string();
+        let depth = |s: &str| {
+            s.matches(['[', '(']).count() as isize - s.matches([']', ')']).count() as isize
+        };
+        while depth(&text) > 0 && i < lines.len() {
+            text.push('\n');
+            text.push_str(lines[i]);
+            i += 1;
+        }
+        names
+            .get_or_insert_with(Vec::new)
+            .extend(string_literals(&text));
+    }
+    names
+}
+
+/// Contents of the `'...'` / `"..."` literals in `text`, comments skipped.
+fn string_literals(text: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '#' => {
+                chars.by_ref().find(|&c| c == '\n');
+            }
+            '\'' | '"' => out.push(chars.by_ref().take_while(|&q| q != c).collect()),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Rust visibility as written by the grammar's `visibility_modifier`. Only
+/// plain `pub` is public API; restricted forms never leave the crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Visibility {
+    Public,
+    /// `pub(crate)`
+    Crate,
+    /// `pub(super)`
+    Super,
+    /// `pub(in some::path)`
+    InPath,
+    /// No modifier, or `pub(self)`.
+    Private,
+}
+
+fn rust_visibility(text: &str) -> Visibility {
+    let Some(rest) = text.strip_prefix("pub") else {
+        return Visibility::Private;
+    };
+    let Some((restriction, _)) = rest
+        .trim_start()
+        .strip_prefix('(')
+        .and_then(|r| r.split_once(')'))
+    else {
+        return Visibility::Public;
+    };
+    match restriction.trim() {
+        "crate" => Visibility::Crate,
+        "super" => Visibility::Super,
+        r if r.starts_with("in ") => Visibility::InPath,
+        _ => Visibility::Private,
+    }
+}
+
+fn render(entry: &OutlineEntry, lines: &[&str], lang: Lang, indent: usize, out: &mut Vec<String>) {
+    let text = first_line(entry, lines);
+    let pad = " ".repeat(indent);
+
+    match entry.kind {
+        OutlineKind::Function => {
+            let sig = full_signature(entry, lines, lang);
+            out.push(format!("{pad}{sig}{}", body_placeholder(lang)));
+        }
+        OutlineKind::Constant
+        | OutlineKind::Variable
+        | OutlineKind::ImmutableVariable
+        | OutlineKind::Property => out.push(format!("{pad}{}", strip_value(text, lang))),
+        OutlineKind::Export => out.push(format!("{pad}{}", export_stub(entry, text, lines))),
+        _ if entry
+            .children
+            .iter()
+            .any(|c| c.kind == OutlineKind::Function) =>
+        {
+            render_container(entry, text, lines, lang, indent, out);
+        }
+        _ => {
+            for line in source_lines(entry, lines) {
+                out.push(format!("{pad}{line}"));
+            }
+        }
+    }
+}
+
+/// Classes, traits and impls with methods: header, public members, closer.
+fn render_container(
+    entry: &OutlineEntry,
+    text: &str,
+    lines: &[&str],
+    lang: Lang,
+    indent: usize,
+    out: &mut Vec<String>,
+) {
+    // Trait impls expose every member; inherent members need their own modifier.
+    let all_public = lang == Lang::Rust
+        && (text.starts_with("impl") && text.contains(" for ") || text.contains("trait "));
+
+    let mut members = Vec::new();
+    for child in &entry.children {
+        if all_public || is_public(child, lines, lang) {
+            render(child, lines, lang, indent + 4, &mut members);
+        }
+    }
+    if members.is_empty() && text.starts_with("impl") {
+        return;
+    }
+
+    let pad = " ".repeat(indent);
+    let header = text.split('{').next().unwrap_or(text).trim_end();
+    match lang {
+        Lang::Python => {
+            out.push(format!("{pad}{header}"));
+            if members.is_empty() {
+                members.push(format!("{pad}    ..."));
+            }
+            out.extend(members);
+        }
+        Lang::Ruby => {
+            out.push(format!("{pad}{header}"));
+            out.extend(members);
+            out.push(format!("{pad}end"));
+        }
+        _ => {
+            out.push(format!("{pad}{header} {{"));
+            out.extend(members);
+            out.push(format!("{pad}}}"));
+        }
+    }
+}
+
+/// The outline signature is the first line only; a stub needs the whole
+/// parameter list. Joins continuation lines until the parentheses balance.
+pub(crate) fn full_signature(entry: &OutlineEntry, lines: &[&str], lang: Lang) -> String {
+    let first = entry
+        .signature
+        .clone()
+        .unwrap_or_else(|| first_line(entry, lines).to_string());
+    let depth = |s: &str| s.matches('(').count() as isize - s.matches(')').count() as isize;
+    if depth(&first) <= 0 {
+        return first
+            .split('{')
+            .next()
+            .unwrap_or(&first)
+            .trim_end()
+            .to_string();
+    }
+
+    let mut joined = String::new();
+    for line in source_lines(entry, lines) {
+        let line = line.trim();
+        if !joined.is_empty() && !joined.ends_with('(') && !line.starts_with(')') {
+            joined.push(' ');
+        }
+        joined.push_str(line);
+        if depth(&joined) <= 0 && (line.contains('{') || line.ends_with(':') || line.ends_with(';'))
+        {
+            break;
+        }
+    }
+    let sig = joined.split('{').next().unwrap_or(&joined).trim_end();
+    let sig = if lang == Lang::Python {
+        sig.strip_suffix(':').unwrap_or(sig)
+    } else {
+        sig
+    };
+    sig.replace(",)", ")")
+}
+
+/// What replaces a function body in this language's stub syntax.
+fn body_placeholder(lang: Lang) -> &'static str {
+    match lang {
+        Lang::Python => ": ...",
+        Lang::Ruby => "; end",
+        Lang::Nix => " ...;",
+        Lang::Lua => " end",
+        Lang::Go | Lang::Kotlin | Lang::Scala | Lang::Swift | Lang::R | Lang::OCaml => "",
+        _ => ";",
+    }
+}
+
+/// `pub const MAX: usize = 10;` → `pub const MAX: usize;`
+fn strip_value(text: &str, lang: Lang) -> String {
+    let Some((lhs, _)) = text.split_once(" = ") else {
+        return text.to_string();
+    };
+    match lang {
+        Lang::Python | Lang::Ruby => format!("{lhs} = ..."),
+        Lang::Go | Lang::Kotlin | Lang::Scala | Lang::Swift | Lang::R => lhs.to_string(),
+        _ => format!("{lhs};"),
+    }
+}
+
+/// JS/TS exports: functions and values lose their bodies, classes collapse,
+/// type-only declarations (interfaces, enums, aliases) are kept whole.
+fn export_stub(entry: &OutlineEntry, text: &str, lines: &[&str]) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let declares = |kw: &str| words.contains(&kw);
+    if declares("interface") || declares("enum") || declares("type") {
+        return source_lines(entry, lines).join("\n");
+    }
+    if declares("class") {
+        let header = text.split('{').next().unwrap_or(text).trim_end();
+        return format!("{header} {{ ... }}");
+    }
+    if declares("function") || declares("async") {
+        let header = text.split('{').next().unwrap_or(text).trim_end();
+        return format!("{header};");
+    }
+    strip_value(text, Lang::TypeScript)
+}
+
+fn first_line<'a>(entry: &OutlineEntry, lines: &[&'a str]) -> &'a str {
+    lines
+        .get(entry.start_line as usize - 1)
+        .map_or("", |l| l.trim())
+}
+
+fn source_lines<'a>(entry: &OutlineEntry, lines: &[&'a str]) -> Vec<&'a str> {
+    let start = entry.start_line as usize - 1;
+    let end = (entry.end_line as usize).min(lines.len());
+    let base = lines
+        .get(start)
+        .map_or(0, |l| l.len() - l.trim_start().len());
+    lines[start..end]
+        .iter()
+        .map(|l| l.get(base..).unwrap_or_else(|| l.trim_start()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rust_stub_keeps_public_surface() {
+        let main_rs = r#"
+use std::fmt;
+
+/// Runtime settings.
+pub struct Config {
+    pub verbose: bool,
+}
+
+pub enum Status {
+    Ready,
+    Failed(String),
+}
+
+pub const MAX_RETRIES: u32 = 3;
+
+pub fn initialize() -> Config {
+    Config { verbose: false }
+}
+
+fn helper() {}
+
+impl Config {
+    pub fn reset(&mut self) {
+        self.verbose = false;
+    }
+
+    pub fn merge(
+        &mut self,
+        other: &Config,
+    ) -> bool {
+        true
+    }
+
+    fn private_reset(&mut self) {}
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "status")
+    }
+}
+"#;
+        let out = stub(main_rs, Lang::Rust, usize::MAX);
+        let expected = "\
+pub struct Config {
+    pub verbose: bool,
+}
+pub enum Status {
+    Ready,
+    Failed(String),
+}
+pub const MAX_RETRIES: u32;
+pub fn initialize() -> Config;
+impl Config {
+    pub fn reset(&mut self);
+    pub fn merge(&mut self, other: &Config) -> bool;
+}
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}";
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn rust_restricted_visibility_is_not_public_api() {
+        let lib_rs = "\
+pub struct Api;
+pub(crate) struct Shared;
+pub(super) fn parent_only() {}
+pub(in crate::net) fn net_only() {}
+pub(self) const LOCAL: u8 = 0;
+fn private() {}
+pub ( crate ) fn spaced() {}
+";
+        let visibility: Vec<Visibility> = lib_rs.lines().map(rust_visibility).collect();
+        assert_eq!(
+            visibility,
+            [
+                Visibility::Public,
+                Visibility::Crate,
+                Visibility::Super,
+                Visibility::InPath,
+                Visibility::Private,
+                Visibility::Private,
+                Visibility::Crate,
+            ]
+        );
+        assert_eq!(stub(lib_rs, Lang::Rust, usize::MAX), "pub struct Api;");
+    }
+
+    #[test]
+    fn typescript_stub_drops_bodies_and_private_items() {
+        let code = r#"
+import { x } from "./x";
+export function add(a: number, b: number): number {
+  return a + b;
+}
+export const PI = 3.14;
+export interface Shape {
+  area(): number;
+}
+function local() {}
+"#;
+        let out = stub(code, Lang::TypeScript, usize::MAX);
+        assert_eq!(
+            out,
+            "export function add(a: number, b: number): number;\n\
+             export const PI;\n\
+             export interface Shape {\n  area(): number;\n}"
+        );
+    }
+
+    #[test]
+    fn python_stub_hides_underscored_names() {
+        let code = "\
+class Client:
+    def __init__(self, url):
+        self.url = url
+
+    def _retry(self):
+        pass
+
+    def fetch(self, path):
+        return path
+
+def _private():
+    pass
+
+def connect(url):
+    return Client(url)
+";
+        let out = stub(code, Lang::Python, usize::MAX);
+        assert_eq!(
+            out,
+            "class Client:\n    def __init__(self, url): ...\n    def fetch(self, path): ...\ndef connect(url): ..."
+        );
+    }
+
+    #[test]
+    fn python_all_decides_public_names() {
+        let code = r#"""Storage backends."""
+import os
+
+__all__ = [
+    "Config",  # settings
+    '_load',
+]
+__all__ += ["helper"]
+
+class Config:
+    def get(self, key):
+        pass
+
+    def _cache(self):
+        pass
+
+class Hidden:
+    pass
+
+def _load(path):
+    return path
+
+def helper(x):
+    return x
+
+def unlisted():
+    pass
+"#;
+        let out = stub(code, Lang::Python, usize::MAX);
+        assert_eq!(
+            out,
+            "class Config:\n    def get(self, key): ...\ndef _load(path): ...\ndef helper(x): ..."
+        );
+    }
+}
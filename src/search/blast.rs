@@ -193,27 +193,11 @@ mod tests {
     }
 
     fn make_fn(name: &str, start: u32, end: u32) -> OutlineEntry {
-        OutlineEntry {
-            kind: OutlineKind::Function,
-            name: name.to_string(),
-            start_line: start,
-            end_line: end,
-            signature: None,
-            children: Vec::new(),
-            doc: None,
-        }
+        OutlineEntry::new(OutlineKind::Function, name, start, end)
     }
 
     fn make_entry(kind: OutlineKind, name: &str, start: u32, end: u32) -> OutlineEntry {
-        OutlineEntry {
-            kind,
-            name: name.to_string(),
-            start_line: start,
-            end_line: end,
-            signature: None,
-            children: Vec::new(),
-            doc: None,
-        }
+        OutlineEntry::new(kind, name, start, end)
     }
 
     #[test]
@@ -51,7 +51,7 @@ pub fn find_callers(
     let found_count = AtomicUsize::new(0);
     let needle = target.as_bytes();
 
-    let walker = super::walker(scope, glob)?;
+    let walker = super::walker(scope, glob, &[])?;
 
     walker.run(|| {
         let matches = &matches;
@@ -231,7 +231,7 @@ pub(crate) fn find_callers_batch(
     let matches: Mutex<Vec<(String, CallerMatch)>> = Mutex::new(Vec::new());
     let found_count = AtomicUsize::new(0);
 
-    let walker = super::walker(scope, glob)?;
+    let walker = super::walker(scope, glob, &[])?;
 
     walker.run(|| {
         let matches = &matches;
@@ -1,27 +1,26 @@
 use std::path::Path;
-use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Mutex;
 
-use super::file_metadata;
+use super::{file_metadata, SearchOptions};
 
 use crate::error::TilthError;
+use crate::lang::detect_file_type;
+use crate::lang::detection::{is_minified, MinifiedThreshold};
 use crate::search::rank;
-use crate::types::{Match, SearchResult};
+use crate::types::{FileType, Match, SearchResult};
 use grep_regex::RegexMatcher;
 use grep_searcher::sinks::UTF8;
 use grep_searcher::Searcher;
 
-const MAX_MATCHES: usize = 10;
-const EARLY_QUIT_THRESHOLD: usize = MAX_MATCHES * 3;
-const MAX_SEARCH_FILE_SIZE: u64 = 500_000;
-
 /// Content search using ripgrep crates. Literal by default, regex if `is_regex`.
+/// Walks the whole scope: every page is cut from the same complete, ranked set.
 pub fn search(
     pattern: &str,
     scope: &Path,
     is_regex: bool,
     context: Option<&Path>,
     glob: Option<&str>,
+    opts: &SearchOptions,
 ) -> Result<SearchResult, TilthError> {
     let matcher = if is_regex {
         RegexMatcher::new(pattern)
@@ -34,95 +33,108 @@ pub fn search(
     })?;
 
     let matches: Mutex<Vec<Match>> = Mutex::new(Vec::new());
-    // Relaxed is correct: walker.run() joins all threads before we read the final value.
-    // Early-quit checks are approximate by design — one extra iteration is harmless.
-    let total_found = AtomicUsize::new(0);
-
-    let walker = super::walker(scope, glob)?;
-
-    walker.run(|| {
-        let matcher = &matcher;
-        let matches = &matches;
-        let total_found = &total_found;
-
-        Box::new(move |entry| {
-            if total_found.load(Ordering::Relaxed) >= EARLY_QUIT_THRESHOLD {
-                return ignore::WalkState::Quit;
-            }
-
-            let Ok(entry) = entry else {
-                return ignore::WalkState::Continue;
-            };
-
-            if !entry.file_type().is_some_and(|ft| ft.is_file()) {
-                return ignore::WalkState::Continue;
-            }
-
-            let path = entry.path();
-
-            // Skip oversized files — tree-sitter and ripgrep shouldn't spend time on minified bundles
-            if let Ok(meta) = std::fs::metadata(path) {
-                if meta.len() > MAX_SEARCH_FILE_SIZE {
-                    return ignore::WalkState::Continue;
-                }
-            }
-
-            let (file_lines, mtime) = file_metadata(path);
-
-            let mut file_matches = Vec::new();
-            let mut searcher = Searcher::new();
-
-            let _ = searcher.search_path(
-                matcher,
-                path,
-                UTF8(|line_num, line| {
-                    file_matches.push(Match {
-                        path: path.to_path_buf(),
-                        line: line_num as u32,
-                        text: line.trim_end().to_string(),
-                        is_definition: false,
-                        exact: false,
-                        file_lines,
-                        mtime,
-                        def_range: None,
-                        def_name: None,
-                        def_weight: 0,
-                        impl_target: None,
-                    });
-                    Ok(true)
-                }),
-            );
-
-            if !file_matches.is_empty() {
-                total_found.fetch_add(file_matches.len(), Ordering::Relaxed);
-                let mut all = matches
-                    .lock()
-                    .unwrap_or_else(std::sync::PoisonError::into_inner);
-                all.extend(file_matches);
-            }
-
-            if total_found.load(Ordering::Relaxed) >= EARLY_QUIT_THRESHOLD {
-                ignore::WalkState::Quit
-            } else {
-                ignore::WalkState::Continue
-            }
-        })
-    });
-
-    let total = total_found.load(Ordering::Relaxed);
+    let minified_threshold = MinifiedThreshold::from_env();
+
+    super::visit_files(scope, glob, opts, |path, bytes| {
+        // Minified bundles match everything on one giant line — useless results
+        if matches!(detect_file_type(path), FileType::Code(_))
+            && is_minified(bytes, minified_threshold)
+        {
+            return ignore::WalkState::Continue;
+        }
+
+        let (file_lines, mtime) = file_metadata(path);
+
+        let mut file_matches = Vec::new();
+        let mut searcher = Searcher::new();
+
+        let _ = searcher.search_slice(
+            &matcher,
+            bytes,
+            UTF8(|line_num, line| {
+                file_matches.push(Match {
+                    path: path.to_path_buf(),
+                    line: line_num as u32,
+                    text: line.trim_end().to_string(),
+                    is_definition: false,
+                    exact: false,
+                    file_lines,
+                    mtime,
+                    def_range: None,
+                    def_name: None,
+                    def_weight: 0,
+                    impl_target: None,
+                });
+                Ok(true)
+            }),
+        );
+
+        if !file_matches.is_empty() {
+            let mut all = matches
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            all.extend(file_matches);
+        }
+
+        ignore::WalkState::Continue
+    })?;
+
     let mut all_matches = matches
         .into_inner()
         .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let total = all_matches.len();
 
     rank::sort(&mut all_matches, pattern, scope, context);
-    all_matches.truncate(MAX_MATCHES);
+    opts.paginate(&mut all_matches);
 
     Ok(SearchResult {
         query: pattern.to_string(),
         scope: scope.to_path_buf(),
         matches: all_matches,
+        offset: opts.offset,
         total_found: total,
         definitions: 0,
         usages: total,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minified_code_is_skipped_but_data_is_searched() {
+        let dir = tempfile::tempdir().unwrap();
+        let line = format!("var needle={};", "x".repeat(6000));
+        std::fs::write(dir.path().join("bundle.min.js"), &line).unwrap();
+        std::fs::write(
+            dir.path().join("data.json"),
+            format!("{{\"needle\":\"{line}\"}}"),
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("app.js"), "const needle = 1;\n").unwrap();
+
+        let result = search(
+            "needle",
+            dir.path(),
+            false,
+            None,
+            None,
+            &SearchOptions::default(),
+        )
+        .unwrap();
+        let mut paths: Vec<_> = result
+            .matches
+            .iter()
+            .map(|m| {
+                m.path
+                    .strip_prefix(dir.path())
+                    .unwrap()
+                    .display()
+                    .to_string()
+            })
+            .collect();
+        paths.sort();
+        assert_eq!(paths, ["app.js", "data.json"]);
+    }
+}
@@ -60,10 +60,9 @@ pub fn analyze_deps(
     bloom: &crate::index::bloom::BloomFilterCache,
 ) -> Result<DepsResult, TilthError> {
     // Canonicalize for reliable path comparison (callers return absolute paths).
-    let path = &path.canonicalize().map_err(|e| TilthError::IoError {
-        path: path.to_path_buf(),
-        source: e,
-    })?;
+    let path = &path
+        .canonicalize()
+        .map_err(|e| TilthError::from_io(path, e))?;
 
     let content = fs::read_to_string(path).map_err(|e| TilthError::IoError {
         path: path.clone(),
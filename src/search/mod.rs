@@ -11,10 +11,12 @@ pub mod strip;
 pub mod symbol;
 pub mod truncate;
 
+use std::borrow::Cow;
 use std::collections::HashSet;
 use std::fmt::Write;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::time::SystemTime;
 
 use ignore::WalkBuilder;
@@ -24,7 +26,7 @@ use crate::error::TilthError;
 use crate::format;
 use crate::read;
 use crate::session::Session;
-use crate::types::{estimate_tokens, FileType, Match, SearchResult};
+use crate::types::{estimate_tokens, FileType, Match, OutlineKind, SearchResult};
 
 use crate::format::rel;
 
@@ -67,10 +69,207 @@ pub(crate) const SKIP_DIRS: &[&str] = &[
 
 const EXPAND_FULL_FILE_THRESHOLD: u64 = 800;
 
+/// Matches per page when the caller doesn't ask for a specific limit.
+pub const DEFAULT_PAGE_SIZE: usize = 10;
+
+/// Per-call search settings. `Default` reproduces the standard first page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Ranked matches to skip before the returned page. Paging (a non-zero
+    /// offset or a non-default limit) searches the whole scope, so pages never
+    /// overlap or skip matches.
+    pub offset: usize,
+    /// Maximum matches returned.
+    pub limit: usize,
+    /// Skip test files (see [`crate::lang::detection::is_test_path`]).
+    pub exclude_tests: bool,
+    /// Definition kinds a symbol search considers.
+    pub kinds: KindFilter,
+    /// Which classified symbol-search matches are returned.
+    pub role: MatchRole,
+    /// Glob patterns, relative to the scope, of files never searched.
+    pub exclude: Vec<String>,
+    /// Match symbols across naming conventions: `user_name`, `userName` and
+    /// `user-name` all match `username` (see [`fold_ident`]).
+    pub ignore_case_style: bool,
+    /// Match symbols regardless of letter case: `color` finds `Color`. Output
+    /// keeps each match's original spelling.
+    pub ignore_case: bool,
+    /// Match symbols anywhere inside an identifier: `set` also finds
+    /// `settings` and `reset`. Off by default — the query must be a whole
+    /// identifier.
+    pub substring: bool,
+    /// Take a symbol query as a regex over definition names (`handle.*Request`,
+    /// `^parse`). Usages are the lines naming any definition it matched.
+    pub regex: bool,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            limit: DEFAULT_PAGE_SIZE,
+            exclude_tests: false,
+            kinds: KindFilter::default(),
+            role: MatchRole::All,
+            exclude: Vec::new(),
+            ignore_case_style: false,
+            ignore_case: false,
+            substring: false,
+            regex: false,
+        }
+    }
+}
+
+/// Which definition kinds a symbol search keeps. A definition of a filtered-out
+/// kind is dropped entirely — it is not demoted to a usage. Definitions whose
+/// kind can't be told (files without a grammar) are always kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KindFilter {
+    /// Keep only these kinds; empty keeps every kind.
+    pub only: Vec<OutlineKind>,
+    /// Drop these kinds.
+    pub except: Vec<OutlineKind>,
+}
+
+impl KindFilter {
+    #[must_use]
+    pub fn allows(&self, kind: OutlineKind) -> bool {
+        (self.only.is_empty() || self.only.contains(&kind)) && !self.except.contains(&kind)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.only.is_empty() && self.except.is_empty()
+    }
+}
+
+impl FromStr for KindFilter {
+    type Err = String;
+
+    /// Comma-separated kind names; a `!` prefix excludes (`type,fn`, `!var`).
+    /// `type` covers class, struct, interface, alias and enum.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Self::default();
+        for item in s.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+            let (name, list) = match item.strip_prefix('!') {
+                Some(name) => (name, &mut filter.except),
+                None => (item, &mut filter.only),
+            };
+            let kinds: &[OutlineKind] = match name {
+                "fn" | "function" => &[OutlineKind::Function],
+                "class" => &[OutlineKind::Class],
+                "struct" => &[OutlineKind::Struct],
+                "interface" | "trait" => &[OutlineKind::Interface],
+                "alias" => &[OutlineKind::TypeAlias],
+                "enum" => &[OutlineKind::Enum],
+                "type" => &[
+                    OutlineKind::Class,
+                    OutlineKind::Struct,
+                    OutlineKind::Interface,
+                    OutlineKind::TypeAlias,
+                    OutlineKind::Enum,
+                ],
+                "const" => &[OutlineKind::Constant],
+                "var" | "variable" => &[OutlineKind::Variable, OutlineKind::ImmutableVariable],
+                "prop" | "property" => &[OutlineKind::Property],
+                "mod" | "module" => &[OutlineKind::Module],
+                "macro" => &[OutlineKind::Macro],
+                "import" => &[OutlineKind::Import],
+                "export" => &[OutlineKind::Export],
+                _ => {
+                    return Err(format!(
+                        "unknown kind \"{name}\" (expected: fn, class, struct, interface, alias, \
+                         enum, type, const, var, prop, mod, macro, import, export)"
+                    ))
+                }
+            };
+            list.extend_from_slice(kinds);
+        }
+        Ok(filter)
+    }
+}
+
+/// Definitions, usages, or both. Applied after classification, so the
+/// counts a search reports cover only the kept role.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MatchRole {
+    #[default]
+    All,
+    Definitions,
+    Usages,
+}
+
+impl MatchRole {
+    #[must_use]
+    pub fn keeps(self, m: &Match) -> bool {
+        match self {
+            Self::All => true,
+            Self::Definitions => m.is_definition,
+            Self::Usages => !m.is_definition,
+        }
+    }
+}
+
+impl FromStr for MatchRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "all" => Ok(Self::All),
+            "definitions" | "defs" => Ok(Self::Definitions),
+            "usages" => Ok(Self::Usages),
+            _ => Err(format!(
+                "unknown match role \"{s}\" (expected: all, definitions, usages)"
+            )),
+        }
+    }
+}
+
+impl SearchOptions {
+    /// Whether the walker should skip `path` before reading it.
+    pub(crate) fn skips(&self, path: &Path, scope: &Path) -> bool {
+        self.exclude_tests
+            && crate::lang::detection::is_test_path(path.strip_prefix(scope).unwrap_or(path))
+    }
+
+    /// `s` as symbol names are compared: [`fold_ident`] under
+    /// `ignore_case_style`, lowercased under `ignore_case`, else unchanged.
+    pub(crate) fn fold<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        if self.ignore_case_style {
+            Cow::Owned(fold_ident(s))
+        } else if self.ignore_case {
+            Cow::Owned(s.to_lowercase())
+        } else {
+            Cow::Borrowed(s)
+        }
+    }
+
+    /// Cut ranked matches down to the requested page.
+    pub(crate) fn paginate(&self, matches: &mut Vec<Match>) {
+        matches.drain(..self.offset.min(matches.len()));
+        matches.truncate(self.limit);
+    }
+}
+
+/// An identifier with `_`/`-` separators removed and lowercased, so snake,
+/// camel, pascal and kebab spellings of a name compare equal.
+pub(crate) fn fold_ident(s: &str) -> String {
+    s.chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
 /// Build a parallel directory walker that searches ALL files except known junk directories.
 /// Does NOT respect .gitignore — ensures gitignored but locally-relevant files are found.
 /// When `glob` is Some, applies a file-pattern override (whitelist or negation).
-pub(crate) fn walker(scope: &Path, glob: Option<&str>) -> Result<ignore::WalkParallel, TilthError> {
+/// Files matching any `exclude` pattern are skipped on top of that.
+pub(crate) fn walker(
+    scope: &Path,
+    glob: Option<&str>,
+    exclude: &[String],
+) -> Result<ignore::WalkParallel, TilthError> {
     let threads = std::env::var("TILTH_THREADS")
         .ok()
         .and_then(|v| v.parse::<usize>().ok())
@@ -97,25 +296,127 @@ pub(crate) fn walker(scope: &Path, glob: Option<&str>) -> Result<ignore::WalkPar
             true
         });
 
-    if let Some(pattern) = glob {
-        if !pattern.is_empty() {
-            let mut overrides = ignore::overrides::OverrideBuilder::new(scope);
-            overrides
-                .add(pattern)
-                .map_err(|e| TilthError::InvalidQuery {
-                    query: pattern.to_string(),
-                    reason: format!("invalid glob: {e}"),
-                })?;
-            builder.overrides(overrides.build().map_err(|e| TilthError::InvalidQuery {
-                query: pattern.to_string(),
-                reason: format!("invalid glob: {e}"),
-            })?);
-        }
+    if let Some(overrides) = overrides(scope, glob, exclude)? {
+        builder.overrides(overrides);
     }
 
     Ok(builder.build_parallel())
 }
 
+/// Files larger than this are never read by a search.
+pub(crate) const MAX_SEARCH_FILE_SIZE: u64 = 500_000;
+
+/// Call `visit` with the path and contents of every file a search reads under
+/// `scope`, in parallel, until it returns [`ignore::WalkState::Quit`]. Skips
+/// what [`walker`] and [`SearchOptions::skips`] filter out, plus files over
+/// [`MAX_SEARCH_FILE_SIZE`].
+///
+/// With the `archive` feature, an archive `scope` is searched entry by entry in
+/// memory, one at a time; entry paths are joined onto the archive path.
+pub(crate) fn visit_files<F>(
+    scope: &Path,
+    glob: Option<&str>,
+    opts: &SearchOptions,
+    visit: F,
+) -> Result<(), TilthError>
+where
+    F: Fn(&Path, &[u8]) -> ignore::WalkState + Sync,
+{
+    #[cfg(feature = "archive")]
+    if crate::archive::is_archive(scope) {
+        return visit_archive(scope, glob, opts, &visit);
+    }
+
+    let walker = walker(scope, glob, &opts.exclude)?;
+    walker.run(|| {
+        let visit = &visit;
+        Box::new(move |entry| {
+            let Ok(entry) = entry else {
+                return ignore::WalkState::Continue;
+            };
+            if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+                return ignore::WalkState::Continue;
+            }
+            let path = entry.path();
+            if opts.skips(path, scope)
+                || fs::metadata(path).is_ok_and(|m| m.len() > MAX_SEARCH_FILE_SIZE)
+            {
+                return ignore::WalkState::Continue;
+            }
+            let Ok(bytes) = fs::read(path) else {
+                return ignore::WalkState::Continue;
+            };
+            visit(path, &bytes)
+        })
+    });
+    Ok(())
+}
+
+/// [`visit_files`] over the entries of a source archive, with the same
+/// directory, glob, exclude and size filters as a directory walk.
+#[cfg(feature = "archive")]
+fn visit_archive<F>(
+    archive: &Path,
+    glob: Option<&str>,
+    opts: &SearchOptions,
+    visit: &F,
+) -> Result<(), TilthError>
+where
+    F: Fn(&Path, &[u8]) -> ignore::WalkState,
+{
+    let overrides = overrides(archive, glob, &opts.exclude)?;
+    for entry in crate::archive::read_entries(archive)? {
+        let skipped_dir = entry.path.parent().is_some_and(|p| {
+            p.iter()
+                .any(|c| c.to_str().is_some_and(|c| SKIP_DIRS.contains(&c)))
+        });
+        let path = archive.join(&entry.path);
+        if skipped_dir
+            || entry.size > MAX_SEARCH_FILE_SIZE
+            || opts.skips(&path, archive)
+            || overrides
+                .as_ref()
+                .is_some_and(|o| o.matched(&path, false).is_ignore())
+        {
+            continue;
+        }
+        if matches!(visit(&path, &entry.data), ignore::WalkState::Quit) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// The walk override for a `glob` filter (whitelist or negation) plus `exclude`
+/// patterns, all relative to `scope`. `None` when there is nothing to filter.
+pub(crate) fn overrides(
+    scope: &Path,
+    glob: Option<&str>,
+    exclude: &[String],
+) -> Result<Option<ignore::overrides::Override>, TilthError> {
+    let glob = glob.filter(|p| !p.is_empty());
+    if glob.is_none() && exclude.is_empty() {
+        return Ok(None);
+    }
+    let invalid = |pattern: &str, e: ignore::Error| TilthError::InvalidQuery {
+        query: pattern.to_string(),
+        reason: format!("invalid glob: {e}"),
+    };
+    let mut overrides = ignore::overrides::OverrideBuilder::new(scope);
+    if let Some(pattern) = glob {
+        overrides.add(pattern).map_err(|e| invalid(pattern, e))?;
+    }
+    for pattern in exclude {
+        overrides
+            .add(&format!("!{pattern}"))
+            .map_err(|e| invalid(pattern, e))?;
+    }
+    overrides
+        .build()
+        .map(Some)
+        .map_err(|e| invalid(glob.unwrap_or_default(), e))
+}
+
 /// Parse `/pattern/` regex syntax. Returns (pattern, `is_regex`).
 fn parse_pattern(query: &str) -> (&str, bool) {
     if query.starts_with('/') && query.ends_with('/') && query.len() > 2 {
@@ -143,8 +444,9 @@ pub fn search_symbol(
     scope: &Path,
     cache: &OutlineCache,
     glob: Option<&str>,
+    opts: &SearchOptions,
 ) -> Result<String, TilthError> {
-    let result = symbol::search(query, scope, None, glob)?;
+    let result = symbol::search(query, scope, None, glob, opts)?;
     let bloom = crate::index::bloom::BloomFilterCache::new();
     format_search_result(&result, cache, None, &bloom, 0)
 }
@@ -159,12 +461,13 @@ pub fn search_symbol_expanded(
     expand: usize,
     context: Option<&Path>,
     glob: Option<&str>,
+    opts: &SearchOptions,
 ) -> Result<String, TilthError> {
     // Index is available but not yet used for search fast-path.
     // Build will be triggered when the lookup path is wired in.
     let _ = index;
 
-    let result = symbol::search(query, scope, context, glob)?;
+    let result = symbol::search(query, scope, context, glob, opts)?;
     format_search_result(&result, cache, Some(session), bloom, expand)
 }
 
@@ -178,6 +481,7 @@ pub fn search_multi_symbol_expanded(
     expand: usize,
     context: Option<&Path>,
     glob: Option<&str>,
+    opts: &SearchOptions,
 ) -> Result<String, TilthError> {
     let _ = index; // Available but not yet used for search fast-path
 
@@ -192,7 +496,7 @@ pub fn search_multi_symbol_expanded(
     let mut sections = Vec::with_capacity(queries.len());
 
     for query in queries {
-        let result = symbol::search(query, scope, context, glob)?;
+        let result = symbol::search(query, scope, context, glob, opts)?;
         let mut out = format::search_header(
             &result.query,
             &result.scope,
@@ -210,13 +514,7 @@ pub fn search_multi_symbol_expanded(
             &mut expanded_files,
             &mut out,
         );
-        if result.total_found > result.matches.len() {
-            let omitted = result.total_found - result.matches.len();
-            let _ = write!(
-                out,
-                "\n\n... and {omitted} more matches. Narrow with scope."
-            );
-        }
+        write_page_footer(&result, &mut out);
         sections.push(out);
     }
 
@@ -228,9 +526,10 @@ pub fn search_content(
     scope: &Path,
     cache: &OutlineCache,
     glob: Option<&str>,
+    opts: &SearchOptions,
 ) -> Result<String, TilthError> {
     let (pattern, is_regex) = parse_pattern(query);
-    let result = content::search(pattern, scope, is_regex, None, glob)?;
+    let result = content::search(pattern, scope, is_regex, None, glob, opts)?;
     let bloom = crate::index::bloom::BloomFilterCache::new();
     format_search_result(&result, cache, None, &bloom, 0)
 }
@@ -240,8 +539,9 @@ pub fn search_regex(
     scope: &Path,
     cache: &OutlineCache,
     glob: Option<&str>,
+    opts: &SearchOptions,
 ) -> Result<String, TilthError> {
-    let result = content::search(pattern, scope, true, None, glob)?;
+    let result = content::search(pattern, scope, true, None, glob, opts)?;
     let bloom = crate::index::bloom::BloomFilterCache::new();
     format_search_result(&result, cache, None, &bloom, 0)
 }
@@ -254,9 +554,10 @@ pub fn search_content_expanded(
     expand: usize,
     context: Option<&Path>,
     glob: Option<&str>,
+    opts: &SearchOptions,
 ) -> Result<String, TilthError> {
     let (pattern, is_regex) = parse_pattern(query);
-    let result = content::search(pattern, scope, is_regex, context, glob)?;
+    let result = content::search(pattern, scope, is_regex, context, glob, opts)?;
     let bloom = crate::index::bloom::BloomFilterCache::new();
     format_search_result(&result, cache, Some(session), &bloom, expand)
 }
@@ -270,8 +571,9 @@ pub fn search_regex_expanded(
     expand: usize,
     context: Option<&Path>,
     glob: Option<&str>,
+    opts: &SearchOptions,
 ) -> Result<String, TilthError> {
-    let result = content::search(pattern, scope, true, context, glob)?;
+    let result = content::search(pattern, scope, true, context, glob, opts)?;
     let bloom = crate::index::bloom::BloomFilterCache::new();
     format_search_result(&result, cache, Some(session), &bloom, expand)
 }
@@ -281,8 +583,9 @@ pub fn search_symbol_raw(
     query: &str,
     scope: &Path,
     glob: Option<&str>,
+    opts: &SearchOptions,
 ) -> Result<SearchResult, TilthError> {
-    symbol::search(query, scope, None, glob)
+    symbol::search(query, scope, None, glob, opts)
 }
 
 /// Raw content search — returns structured result for programmatic inspection.
@@ -290,9 +593,10 @@ pub fn search_content_raw(
     query: &str,
     scope: &Path,
     glob: Option<&str>,
+    opts: &SearchOptions,
 ) -> Result<SearchResult, TilthError> {
     let (pattern, is_regex) = parse_pattern(query);
-    content::search(pattern, scope, is_regex, None, glob)
+    content::search(pattern, scope, is_regex, None, glob, opts)
 }
 
 /// Raw regex search — returns structured result for programmatic inspection.
@@ -300,8 +604,9 @@ pub fn search_regex_raw(
     pattern: &str,
     scope: &Path,
     glob: Option<&str>,
+    opts: &SearchOptions,
 ) -> Result<SearchResult, TilthError> {
-    content::search(pattern, scope, true, None, glob)
+    content::search(pattern, scope, true, None, glob, opts)
 }
 
 /// Format a raw search result (symbol or content — both use the same pipeline).
@@ -968,13 +1273,7 @@ fn format_search_result(
         );
     }
 
-    if result.total_found > result.matches.len() {
-        let omitted = result.total_found - result.matches.len();
-        let _ = write!(
-            out,
-            "\n\n... and {omitted} more matches. Narrow with scope."
-        );
-    }
+    write_page_footer(result, &mut out);
 
     let tokens = estimate_tokens(out.len() as u64);
     let token_str = if tokens >= 1000 {
@@ -987,6 +1286,29 @@ fn format_search_result(
     Ok(out)
 }
 
+/// Tell the reader what lies beyond the shown matches. First pages keep the
+/// "narrow with scope" nudge; later pages report their position instead.
+fn write_page_footer(result: &SearchResult, out: &mut String) {
+    let shown_end = result.offset + result.matches.len();
+    if result.offset > 0 {
+        let _ = write!(
+            out,
+            "\n\nresults {}–{shown_end} of {}",
+            (result.offset + 1).min(shown_end),
+            result.total_found
+        );
+        if result.total_found > shown_end {
+            let _ = write!(out, ". Next page: offset={shown_end}.");
+        }
+    } else if result.total_found > shown_end {
+        let omitted = result.total_found - shown_end;
+        let _ = write!(
+            out,
+            "\n\n... and {omitted} more matches. Narrow with scope."
+        );
+    }
+}
+
 /// Inline the actual code for a match. Returns `(formatted_block, raw_content)`.
 /// The raw content is returned so the caller can reuse it (e.g. for related-file hints)
 /// without a redundant file read.
@@ -1229,7 +1551,7 @@ mod tests {
 
     /// Collect all file paths from a walker into a sorted Vec.
     fn walk_paths(scope: &Path, glob: Option<&str>) -> Vec<PathBuf> {
-        let w = walker(scope, glob).expect("walker failed");
+        let w = walker(scope, glob, &[]).expect("walker failed");
         let paths: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
         w.run(|| {
             let paths = &paths;
@@ -1306,7 +1628,7 @@ mod tests {
     #[test]
     fn walker_invalid_glob_returns_error() {
         let scope = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
-        let result = walker(&scope, Some("[unclosed"));
+        let result = walker(&scope, Some("[unclosed"), &[]);
         match result {
             Err(TilthError::InvalidQuery { query, reason }) => {
                 assert_eq!(query, "[unclosed");
@@ -1323,7 +1645,7 @@ mod tests {
     #[test]
     fn walker_brace_expansion_matches_multiple_extensions() {
         let scope = Path::new(env!("CARGO_MANIFEST_DIR"));
-        let filtered = walk_paths(&scope, Some("*.{rs,toml}"));
+        let filtered = walk_paths(scope, Some("*.{rs,toml}"));
         let exts = extensions(&filtered);
         assert!(
             exts.contains("rs"),
@@ -1346,8 +1668,8 @@ mod tests {
         // Use project root (not src/) — project root has .toml, .md, .lock etc.
         // alongside .rs files, so *.rs is guaranteed to be a strict subset.
         let scope = Path::new(env!("CARGO_MANIFEST_DIR"));
-        let all = walk_paths(&scope, None);
-        let rs_only = walk_paths(&scope, Some("*.rs"));
+        let all = walk_paths(scope, None);
+        let rs_only = walk_paths(scope, Some("*.rs"));
         assert!(
             rs_only.len() < all.len(),
             "whitelist ({}) should find fewer files than unfiltered ({})",
@@ -1359,7 +1681,7 @@ mod tests {
     #[test]
     fn walker_path_pattern_restricts_directory() {
         let scope = Path::new(env!("CARGO_MANIFEST_DIR"));
-        let filtered = walk_paths(&scope, Some("src/**/*.rs"));
+        let filtered = walk_paths(scope, Some("src/**/*.rs"));
         assert!(!filtered.is_empty(), "path pattern should find files");
         let src_dir = scope.join("src");
         for p in &filtered {
@@ -1376,11 +1698,33 @@ mod tests {
     #[test]
     fn content_search_glob_restricts_results() {
         let scope = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
-        let all = content::search("TilthError", &scope, false, None, None).expect("search failed");
-        let rs_only = content::search("TilthError", &scope, false, None, Some("*.rs"))
-            .expect("search with glob failed");
-        let toml_only = content::search("TilthError", &scope, false, None, Some("*.toml"))
-            .expect("search with toml glob failed");
+        let all = content::search(
+            "TilthError",
+            &scope,
+            false,
+            None,
+            None,
+            &SearchOptions::default(),
+        )
+        .expect("search failed");
+        let rs_only = content::search(
+            "TilthError",
+            &scope,
+            false,
+            None,
+            Some("*.rs"),
+            &SearchOptions::default(),
+        )
+        .expect("search with glob failed");
+        let toml_only = content::search(
+            "TilthError",
+            &scope,
+            false,
+            None,
+            Some("*.toml"),
+            &SearchOptions::default(),
+        )
+        .expect("search with toml glob failed");
 
         assert!(all.total_found > 0, "unfiltered should find TilthError");
         assert!(rs_only.total_found > 0, "*.rs should find TilthError");
@@ -1401,10 +1745,22 @@ mod tests {
     #[test]
     fn symbol_search_glob_restricts_results() {
         let scope = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
-        let rs_result =
-            symbol::search("walker", &scope, None, Some("*.rs")).expect("symbol search failed");
-        let toml_result = symbol::search("walker", &scope, None, Some("*.toml"))
-            .expect("symbol search with toml failed");
+        let rs_result = symbol::search(
+            "walker",
+            &scope,
+            None,
+            Some("*.rs"),
+            &SearchOptions::default(),
+        )
+        .expect("symbol search failed");
+        let toml_result = symbol::search(
+            "walker",
+            &scope,
+            None,
+            Some("*.toml"),
+            &SearchOptions::default(),
+        )
+        .expect("symbol search with toml failed");
 
         assert!(rs_result.total_found > 0, "*.rs should find 'walker'");
         assert_eq!(
@@ -1540,8 +1896,15 @@ mod tests {
         #[cfg(windows)]
         std::os::windows::fs::symlink_dir(&real_dir, tmp.path().join("linked")).unwrap();
 
-        let result =
-            content::search("unique_symlink_test_symbol", tmp.path(), false, None, None).unwrap();
+        let result = content::search(
+            "unique_symlink_test_symbol",
+            tmp.path(),
+            false,
+            None,
+            None,
+            &SearchOptions::default(),
+        )
+        .unwrap();
         // Should find the symbol in both real/api.rs and linked/api.rs
         assert!(
             result.total_found >= 2,
@@ -1549,4 +1912,414 @@ mod tests {
             result.total_found
         );
     }
+
+    #[test]
+    fn pages_do_not_overlap_and_match_unpaginated_order() {
+        let tmp = tempfile::tempdir().unwrap();
+        // Well over a default page, from many files walked in parallel.
+        for i in 0..12 {
+            let body = (0..4)
+                .map(|j| format!("let paged_needle_{i}_{j} = 1;"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            std::fs::write(tmp.path().join(format!("f{i}.rs")), body).unwrap();
+        }
+        let search = |offset, limit| {
+            let opts = SearchOptions {
+                offset,
+                limit,
+                ..SearchOptions::default()
+            };
+            content::search("paged_needle", tmp.path(), false, None, None, &opts).unwrap()
+        };
+        let key = |m: &Match| (m.path.clone(), m.line);
+
+        let all = search(0, 48);
+        let first = search(0, 24);
+        let second = search(24, 24);
+        assert_eq!(all.total_found, 48);
+        assert_eq!(second.total_found, 48);
+        assert_eq!(second.offset, 24);
+
+        let first: Vec<_> = first.matches.iter().map(key).collect();
+        let second: Vec<_> = second.matches.iter().map(key).collect();
+        assert!(first.iter().all(|k| !second.contains(k)));
+        let paged: Vec<_> = first.into_iter().chain(second).collect();
+        let unpaged: Vec<_> = all.matches.iter().map(key).collect();
+        assert_eq!(paged, unpaged);
+
+        let mut out = String::new();
+        write_page_footer(&search(5, 5), &mut out);
+        assert_eq!(out, "\n\nresults 6–10 of 48. Next page: offset=10.");
+
+        // The default first page ranks the same complete set the pages do.
+        let default = content::search(
+            "paged_needle",
+            tmp.path(),
+            false,
+            None,
+            None,
+            &SearchOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(default.total_found, 48);
+        let default: Vec<_> = default.matches.iter().map(key).collect();
+        let fives: Vec<_> = search(0, 5)
+            .matches
+            .iter()
+            .chain(&search(5, 5).matches)
+            .map(key)
+            .collect();
+        assert_eq!(fives, default);
+        assert_eq!(fives, unpaged[..DEFAULT_PAGE_SIZE]);
+    }
+
+    #[test]
+    fn kind_filter_keeps_only_requested_definitions() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join("types.rs"),
+            "pub struct Config {\n    pub debug: bool,\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("values.rs"),
+            "#[allow(non_upper_case_globals)]\npub static Config: u8 = 0;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("app.rs"),
+            "fn load() -> Config {\n    todo!()\n}\n",
+        )
+        .unwrap();
+        let search = |kinds: &str| {
+            let opts = SearchOptions {
+                kinds: kinds.parse().unwrap(),
+                ..SearchOptions::default()
+            };
+            let result = symbol::search("Config", tmp.path(), None, None, &opts).unwrap();
+            let mut defs: Vec<String> = result
+                .matches
+                .iter()
+                .filter(|m| m.is_definition)
+                .map(|m| {
+                    format!(
+                        "{}:{}",
+                        m.path.file_name().unwrap().to_string_lossy(),
+                        m.line
+                    )
+                })
+                .collect();
+            defs.sort();
+            (defs, result.matches)
+        };
+
+        assert_eq!(search("").0, ["types.rs:1", "values.rs:2"]);
+        let (defs, matches) = search("type");
+        assert_eq!(defs, ["types.rs:1"]);
+        // The dropped static isn't demoted to a usage; real usages remain.
+        assert!(!matches.iter().any(|m| m.path.ends_with("values.rs")));
+        assert!(matches.iter().any(|m| m.path.ends_with("app.rs")));
+        assert_eq!(search("!type").0, ["values.rs:2"]);
+        assert!("typo".parse::<KindFilter>().is_err());
+    }
+
+    #[test]
+    fn whole_word_by_default_substring_on_request() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join("store.rs"),
+            "pub fn set() {}\n\npub fn settings() {}\n\nfn reset() {\n    set();\n}\n",
+        )
+        .unwrap();
+        // No grammar: definitions come from the keyword heuristic.
+        std::fs::write(
+            tmp.path().join("notes.txt"),
+            "function settings() {}\nfunction set() {}\n",
+        )
+        .unwrap();
+
+        let search = |substring: bool| {
+            let opts = SearchOptions {
+                substring,
+                limit: 20,
+                ..SearchOptions::default()
+            };
+            let result = symbol::search("set", tmp.path(), None, None, &opts).unwrap();
+            let mut found: Vec<String> = result
+                .matches
+                .iter()
+                .map(|m| {
+                    let file = m.path.file_name().unwrap().to_string_lossy();
+                    format!("{file}:{}:{}", m.line, m.is_definition)
+                })
+                .collect();
+            found.sort();
+            found
+        };
+
+        assert_eq!(
+            search(false),
+            ["notes.txt:2:true", "store.rs:1:true", "store.rs:6:false"]
+        );
+        assert_eq!(
+            search(true),
+            [
+                "notes.txt:1:true",
+                "notes.txt:2:true",
+                "store.rs:1:true",
+                "store.rs:3:true",
+                "store.rs:5:true",
+                "store.rs:6:false",
+            ]
+        );
+    }
+
+    #[test]
+    fn role_keeps_only_definitions_or_usages() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join("lexer.rs"),
+            "pub fn tokenize(s: &str) -> Vec<&str> { if s.is_empty() { vec![] } else { tokenize(&s[1..]) } }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("main.rs"),
+            "fn main() {\n    lexer::tokenize(\"a b\");\n}\n",
+        )
+        .unwrap();
+
+        let search = |role: &str, query: &str| {
+            let opts = SearchOptions {
+                role: role.parse().unwrap(),
+                ..SearchOptions::default()
+            };
+            let result = symbol::search(query, tmp.path(), None, None, &opts).unwrap();
+            let found: Vec<String> = result
+                .matches
+                .iter()
+                .map(|m| {
+                    let file = m.path.file_name().unwrap().to_string_lossy();
+                    format!("{file}:{}:{}", m.line, m.is_definition)
+                })
+                .collect();
+            (found, result.definitions, result.usages)
+        };
+
+        assert_eq!(
+            search("all", "tokenize"),
+            (
+                vec!["lexer.rs:1:true".into(), "main.rs:2:false".into()],
+                1,
+                1
+            )
+        );
+        assert_eq!(
+            search("definitions", "tokenize"),
+            (vec!["lexer.rs:1:true".into()], 1, 0)
+        );
+        assert_eq!(
+            search("usages", "tokenize"),
+            (vec!["main.rs:2:false".into()], 0, 1)
+        );
+        std::fs::remove_file(tmp.path().join("main.rs")).unwrap();
+        // The recursive call shares the definition's line, so it is no usage.
+        assert_eq!(search("usages", "tokenize"), (vec![], 0, 0));
+        assert!("calls".parse::<MatchRole>().is_err());
+    }
+
+    #[test]
+    fn ignore_case_style_matches_every_naming_convention() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join("users.py"),
+            "def user_name(u):\n    return u\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("users.ts"),
+            "function userName(u: string) {\n  return u;\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("users.css"),
+            ".user-name {\n  color: red;\n}\n",
+        )
+        .unwrap();
+
+        assert_eq!(fold_ident("user_name"), "username");
+        assert_eq!(fold_ident("UserName"), "username");
+        assert_eq!(fold_ident("user-name"), "username");
+
+        let search = |ignore_case_style: bool| {
+            let opts = SearchOptions {
+                ignore_case_style,
+                ..SearchOptions::default()
+            };
+            let result = symbol::search("username", tmp.path(), None, None, &opts).unwrap();
+            let mut found: Vec<String> = result
+                .matches
+                .iter()
+                .map(|m| {
+                    let file = m.path.file_name().unwrap().to_string_lossy();
+                    format!("{file}:{}:{}", m.line, m.is_definition)
+                })
+                .collect();
+            found.sort();
+            found
+        };
+
+        assert!(search(false).is_empty());
+        assert_eq!(
+            search(true),
+            ["users.css:1:false", "users.py:1:true", "users.ts:1:true"]
+        );
+    }
+
+    #[test]
+    fn regex_matches_symbol_names_across_languages() {
+        let polyglot = Path::new(env!("CARGO_MANIFEST_DIR")).join("benches/fixtures/polyglot");
+        let search = |pattern: &str, role: MatchRole| {
+            let opts = SearchOptions {
+                regex: true,
+                role,
+                limit: 50,
+                ..SearchOptions::default()
+            };
+            let result = symbol::search(pattern, &polyglot, None, None, &opts)?;
+            let mut found: Vec<String> = result
+                .matches
+                .iter()
+                .map(|m| {
+                    let file = m.path.strip_prefix(&polyglot).unwrap().display();
+                    format!("{file}:{}:{}", m.line, m.is_definition)
+                })
+                .collect();
+            found.sort();
+            Ok::<_, TilthError>(found)
+        };
+
+        // Anchored: `low_stock_threshold` and `lowStock` start with "low" too.
+        assert_eq!(
+            search("^low", MatchRole::All).unwrap(),
+            [
+                "scripts/report.py:60:false",
+                "src/lib.rs:106:false",
+                "src/lib.rs:30:true",
+                "src/lib.rs:37:false",
+                "src/lib.rs:89:true",
+                "src/lib.rs:94:false",
+                "web/client.ts:62:true",
+            ]
+        );
+        assert_eq!(
+            search("(?i)^reserve$", MatchRole::Definitions).unwrap(),
+            [
+                "server/Reservations.java:30:true",
+                "server/main.go:45:true",
+                "src/lib.rs:72:true",
+                "web/client.ts:38:true",
+            ]
+        );
+        // Wildcard: exported TS types count, and a decorated Python class is
+        // defined on its `class` line, not its decorator's.
+        assert_eq!(
+            search("^[A-Z].*Level$", MatchRole::Definitions).unwrap(),
+            [
+                "scripts/report.py:20:true",
+                "server/main.go:19:true",
+                "web/client.ts:12:true",
+            ]
+        );
+
+        let err = search("handle(", MatchRole::All).unwrap_err();
+        assert!(matches!(err, TilthError::InvalidQuery { .. }), "{err}");
+    }
+
+    #[test]
+    fn ignore_case_keeps_original_spelling() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join("theme.rs"),
+            "pub enum Color {\n    Red,\n}\n\nfn paint(c: Color) {}\n",
+        )
+        .unwrap();
+
+        let search = |ignore_case: bool| {
+            let opts = SearchOptions {
+                ignore_case,
+                ..SearchOptions::default()
+            };
+            let result = symbol::search("color", tmp.path(), None, None, &opts).unwrap();
+            result
+                .matches
+                .iter()
+                .map(|m| format!("{}:{}:{}", m.line, m.is_definition, m.text.trim()))
+                .collect::<Vec<_>>()
+        };
+
+        assert!(search(false).is_empty());
+        assert_eq!(
+            search(true),
+            ["1:true:pub enum Color {", "5:false:fn paint(c: Color) {}"]
+        );
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn searches_archive_entries_without_extracting() {
+        use std::io::Write as _;
+
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (path, body) in [
+            (
+                "pkg-1.0/src/lib.rs",
+                "pub fn parse() -> Token {\n    Token\n}\npub struct Token;\n",
+            ),
+            ("pkg-1.0/util.py", "def helper():\n    return parse()\n"),
+            ("pkg-1.0/node_modules/dep/index.js", "function parse() {}\n"),
+        ] {
+            writer
+                .start_file(path, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(body.as_bytes()).unwrap();
+        }
+        let bytes = writer.finish().unwrap().into_inner();
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("pkg-1.0.zip");
+        std::fs::write(&archive, bytes).unwrap();
+
+        let sites = |result: SearchResult| {
+            let mut sites: Vec<String> = result
+                .matches
+                .iter()
+                .map(|m| {
+                    let rel = m.path.strip_prefix(&archive).unwrap();
+                    format!("{}:{}:{}", rel.display(), m.line, m.is_definition)
+                })
+                .collect();
+            sites.sort();
+            sites
+        };
+        let opts = SearchOptions::default();
+
+        let parse = symbol::search("parse", &archive, None, None, &opts).unwrap();
+        assert_eq!(
+            sites(parse),
+            ["pkg-1.0/src/lib.rs:1:true", "pkg-1.0/util.py:2:false"]
+        );
+        let py_only = symbol::search("parse", &archive, None, Some("*.py"), &opts).unwrap();
+        assert_eq!(sites(py_only), ["pkg-1.0/util.py:2:false"]);
+
+        let token = content::search("Token", &archive, false, None, None, &opts).unwrap();
+        assert_eq!(
+            sites(token),
+            [
+                "pkg-1.0/src/lib.rs:1:false",
+                "pkg-1.0/src/lib.rs:2:false",
+                "pkg-1.0/src/lib.rs:4:false",
+            ]
+        );
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
 }
@@ -1,29 +1,26 @@
-use std::fs;
-use std::path::Path;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::time::SystemTime;
 
-use super::file_metadata;
+use super::{file_metadata, fold_ident, SearchOptions};
 use crate::lang::treesitter::{
-    definition_weight, extract_definition_name, extract_impl_trait, extract_impl_type,
-    extract_implemented_interfaces, DEFINITION_KINDS,
+    definition_kind, definition_weight, extract_definition_name, extract_impl_trait,
+    extract_impl_type, extract_implemented_interfaces, DEFINITION_KINDS,
 };
 
 use crate::error::TilthError;
 use crate::lang::detect_file_type;
+use crate::lang::detection::{is_minified, MinifiedThreshold};
 use crate::lang::outline::outline_language;
 use crate::search::rank;
-use crate::types::{FileType, Match, SearchResult};
+use crate::types::{FileType, Match, OutlineEntry, OutlineKind, SearchResult};
 use grep_regex::RegexMatcher;
 use grep_searcher::sinks::UTF8;
 use grep_searcher::Searcher;
 
-const MAX_MATCHES: usize = 10;
-/// Stop walking once we have this many raw definition matches.
-const EARLY_QUIT_THRESHOLD_DEFINITIONS: usize = 50;
-/// Stop walking once we have this many raw usage matches.
-const EARLY_QUIT_THRESHOLD_USAGES: usize = MAX_MATCHES * 3;
+/// A file and 1-based line.
+type Site = (PathBuf, u32);
 
 /// Symbol search: find definitions via tree-sitter, usages via ripgrep, concurrently.
 /// Merge results, deduplicate, definitions first.
@@ -32,50 +29,220 @@ pub fn search(
     scope: &Path,
     context: Option<&Path>,
     glob: Option<&str>,
+    opts: &SearchOptions,
 ) -> Result<SearchResult, TilthError> {
+    if opts.regex {
+        return search_pattern(query, scope, context, glob, opts);
+    }
+
     // Compile regex once, share across both arms
-    let word_pattern = format!(r"\b{}\b", regex_syntax::escape(query));
+    let edge = if opts.substring { "" } else { r"\b" };
+    let word_pattern = if opts.ignore_case_style {
+        // Any spelling that folds to the query: separators allowed between letters.
+        let letters: Vec<String> = fold_ident(query)
+            .chars()
+            .map(|c| regex_syntax::escape(c.encode_utf8(&mut [0; 4])))
+            .collect();
+        format!(r"(?i){edge}{}{edge}", letters.join("[_-]?"))
+    } else if opts.ignore_case {
+        format!(r"(?i){edge}{}{edge}", regex_syntax::escape(query))
+    } else {
+        format!(r"{edge}{}{edge}", regex_syntax::escape(query))
+    };
     let matcher = RegexMatcher::new(&word_pattern).map_err(|e| TilthError::InvalidQuery {
         query: query.to_string(),
         reason: e.to_string(),
     })?;
 
     let (defs, usages) = rayon::join(
-        || find_definitions(query, scope, glob),
-        || find_usages(query, &matcher, scope, glob),
+        || find_definitions(query, scope, glob, opts),
+        || find_usages(query, &matcher, scope, glob, opts),
     );
 
-    let defs = defs?;
+    let (defs, dropped) = defs?;
     let usages = usages?;
+    Ok(merge(query, scope, context, opts, defs, &dropped, usages))
+}
 
+/// Definitions first, then the usages not on a definition's (or a dropped
+/// definition's) line; filtered by [`SearchOptions::role`], ranked and cut to
+/// the requested page.
+fn merge(
+    query: &str,
+    scope: &Path,
+    context: Option<&Path>,
+    opts: &SearchOptions,
+    defs: Vec<Match>,
+    dropped: &[Site],
+    usages: Vec<Match>,
+) -> SearchResult {
     // Deduplicate: remove usage matches that overlap with definition matches.
-    // Linear scan — max ~30 defs from EARLY_QUIT_THRESHOLD, no allocation needed.
+    let taken: HashSet<(&Path, u32)> = defs
+        .iter()
+        .map(|d| (d.path.as_path(), d.line))
+        .chain(dropped.iter().map(|(p, l)| (p.as_path(), *l)))
+        .collect();
+    let usages: Vec<Match> = usages
+        .into_iter()
+        .filter(|m| !taken.contains(&(m.path.as_path(), m.line)))
+        .collect();
     let mut merged: Vec<Match> = defs;
-    let def_count = merged.len();
-
-    for m in usages {
-        let dominated = merged[..def_count]
-            .iter()
-            .any(|d| d.path == m.path && d.line == m.line);
-        if !dominated {
-            merged.push(m);
-        }
-    }
+    merged.extend(usages);
 
+    merged.retain(|m| opts.role.keeps(m));
     let total = merged.len();
+    let def_count = merged.iter().filter(|m| m.is_definition).count();
     let usage_count = total - def_count;
 
     rank::sort(&mut merged, query, scope, context);
-    merged.truncate(MAX_MATCHES);
+    opts.paginate(&mut merged);
 
-    Ok(SearchResult {
+    SearchResult {
         query: query.to_string(),
         scope: scope.to_path_buf(),
         matches: merged,
+        offset: opts.offset,
         total_found: total,
         definitions: def_count,
         usages: usage_count,
-    })
+    }
+}
+
+/// Regex symbol search ([`SearchOptions::regex`]): every outline entry whose
+/// name `pattern` matches is a definition; usages are word-boundary matches of
+/// those names, found in a second walk once the names are known.
+fn search_pattern(
+    pattern: &str,
+    scope: &Path,
+    context: Option<&Path>,
+    glob: Option<&str>,
+    opts: &SearchOptions,
+) -> Result<SearchResult, TilthError> {
+    let invalid = |reason: String| TilthError::InvalidQuery {
+        query: pattern.to_string(),
+        reason,
+    };
+    let re = regex::RegexBuilder::new(pattern)
+        .case_insensitive(opts.ignore_case)
+        .build()
+        .map_err(|e| invalid(e.to_string()))?;
+
+    let defs: Mutex<Vec<Match>> = Mutex::new(Vec::new());
+    let dropped: Mutex<Vec<Site>> = Mutex::new(Vec::new());
+    let minified_threshold = MinifiedThreshold::from_env();
+    super::visit_files(scope, glob, opts, |path, bytes| {
+        let FileType::Code(lang) = detect_file_type(path) else {
+            return ignore::WalkState::Continue;
+        };
+        let Ok(content) = std::str::from_utf8(bytes) else {
+            return ignore::WalkState::Continue;
+        };
+        if is_minified(bytes, minified_threshold) {
+            return ignore::WalkState::Continue;
+        }
+
+        let (file_lines, mtime) = file_metadata(path);
+        let lines: Vec<&str> = content.lines().collect();
+        let mut file_defs = Vec::new();
+        let mut file_dropped = Vec::new();
+        let mut stack: Vec<&OutlineEntry> = Vec::new();
+        let entries = crate::lang::outline::get_outline_entries(content, lang);
+        stack.extend(entries.iter().rev());
+        while let Some(e) = stack.pop() {
+            stack.extend(e.children.iter().rev());
+            // Import and export entries are named by their statement text.
+            let name = match e.kind {
+                OutlineKind::Import => continue,
+                OutlineKind::Export => {
+                    let Some(name) = crate::read::outline::code::export_name(&e.name) else {
+                        continue;
+                    };
+                    name
+                }
+                _ => e.name.clone(),
+            };
+            if !re.is_match(&name) {
+                continue;
+            }
+            // Decorators and annotations open some entries; the definition
+            // is the first line naming the symbol.
+            let line = (e.start_line..=e.end_line)
+                .find(|&n| {
+                    lines
+                        .get(n as usize - 1)
+                        .is_some_and(|l| contains_word(l, &name))
+                })
+                .unwrap_or(e.start_line);
+            if !opts.kinds.allows(e.kind) {
+                file_dropped.push((path.to_path_buf(), line));
+                continue;
+            }
+            file_defs.push(Match {
+                path: path.to_path_buf(),
+                line,
+                text: lines
+                    .get(line as usize - 1)
+                    .unwrap_or(&"")
+                    .trim_end()
+                    .to_string(),
+                is_definition: true,
+                exact: true,
+                file_lines,
+                mtime,
+                def_range: Some((e.start_line, e.end_line)),
+                def_name: Some(name),
+                def_weight: 80,
+                impl_target: None,
+            });
+        }
+        if !file_defs.is_empty() {
+            defs.lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .extend(file_defs);
+        }
+        if !file_dropped.is_empty() {
+            dropped
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .extend(file_dropped);
+        }
+        ignore::WalkState::Continue
+    })?;
+
+    let defs = defs
+        .into_inner()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let dropped = dropped
+        .into_inner()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+    let mut names: Vec<&str> = defs.iter().filter_map(|d| d.def_name.as_deref()).collect();
+    names.sort_unstable();
+    names.dedup();
+    let usages = usages_of(pattern, &names, scope, glob, opts)?;
+
+    Ok(merge(pattern, scope, context, opts, defs, &dropped, usages))
+}
+
+/// Word-boundary usages of any of `names` under `scope`, walked like a symbol
+/// search. `query` labels the matches and any error.
+pub(crate) fn usages_of(
+    query: &str,
+    names: &[&str],
+    scope: &Path,
+    glob: Option<&str>,
+    opts: &SearchOptions,
+) -> Result<Vec<Match>, TilthError> {
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+    let alternation: Vec<String> = names.iter().map(|n| regex_syntax::escape(n)).collect();
+    let word_pattern = format!(r"\b(?:{})\b", alternation.join("|"));
+    let matcher = RegexMatcher::new(&word_pattern).map_err(|e| TilthError::InvalidQuery {
+        query: query.to_string(),
+        reason: e.to_string(),
+    })?;
+    find_usages(query, &matcher, scope, glob, opts)
 }
 
 /// Find definitions using tree-sitter structural detection.
@@ -85,99 +252,90 @@ pub fn search(
 ///
 /// Single-read design: reads each file once, checks for symbol via
 /// `memchr::memmem` (SIMD), then reuses the buffer for tree-sitter parsing.
-/// Early termination: quits the parallel walker once enough defs are found.
+/// Walks the whole scope, so every page is cut from the same ranked set.
+///
+/// Also returns the sites of definitions dropped by [`SearchOptions::kinds`], so
+/// they aren't reported as usages instead.
 fn find_definitions(
     query: &str,
     scope: &Path,
     glob: Option<&str>,
-) -> Result<Vec<Match>, TilthError> {
+    opts: &SearchOptions,
+) -> Result<(Vec<Match>, Vec<Site>), TilthError> {
     let matches: Mutex<Vec<Match>> = Mutex::new(Vec::new());
-    // Relaxed is correct: walker.run() joins all threads before we read the final value.
-    // Early-quit checks are approximate by design — one extra iteration is harmless.
-    let found_count = AtomicUsize::new(0);
-    let needle = query.as_bytes();
-
-    let walker = super::walker(scope, glob)?;
-
-    walker.run(|| {
-        let matches = &matches;
-        let found_count = &found_count;
-
-        Box::new(move |entry| {
-            // Early termination: enough definitions found
-            if found_count.load(Ordering::Relaxed) >= EARLY_QUIT_THRESHOLD_DEFINITIONS {
-                return ignore::WalkState::Quit;
-            }
-
-            let Ok(entry) = entry else {
-                return ignore::WalkState::Continue;
-            };
-
-            if !entry.file_type().is_some_and(|ft| ft.is_file()) {
-                return ignore::WalkState::Continue;
-            }
-
-            let path = entry.path();
-
-            // Skip oversized files — avoid tree-sitter parsing multi-MB minified bundles
-            if let Ok(meta) = std::fs::metadata(path) {
-                if meta.len() > 500_000 {
-                    return ignore::WalkState::Continue;
-                }
-            }
-
-            // Single read: read file once, use buffer for both check and parse
-            let Ok(content) = fs::read_to_string(path) else {
-                return ignore::WalkState::Continue;
-            };
-
-            // Fast byte check via memchr::memmem (SIMD) — skip files without the symbol
-            if memchr::memmem::find(content.as_bytes(), needle).is_none() {
-                return ignore::WalkState::Continue;
-            }
-
-            // Get file metadata once per file
-            let (file_lines, mtime) = file_metadata(path);
-
-            // Try tree-sitter structural detection
-            let file_type = detect_file_type(path);
-            let lang = match file_type {
-                FileType::Code(l) => Some(l),
-                _ => None,
-            };
+    let dropped: Mutex<Vec<Site>> = Mutex::new(Vec::new());
+    let minified_threshold = MinifiedThreshold::from_env();
+    let folded = opts.fold(query);
+    let needle = folded.as_bytes();
+
+    super::visit_files(scope, glob, opts, |path, bytes| {
+        // Single read: the same buffer serves both the check and the parse
+        let Ok(content) = std::str::from_utf8(bytes) else {
+            return ignore::WalkState::Continue;
+        };
+
+        // Fast byte check via memchr::memmem (SIMD) — skip files without the symbol
+        let haystack = opts.fold(content);
+        if memchr::memmem::find(haystack.as_bytes(), needle).is_none() {
+            return ignore::WalkState::Continue;
+        }
 
-            let ts_language = lang.and_then(outline_language);
+        if is_minified(bytes, minified_threshold) {
+            return ignore::WalkState::Continue;
+        }
 
-            let mut file_defs = if let Some(ref ts_lang) = ts_language {
-                find_defs_treesitter(path, query, ts_lang, &content, file_lines, mtime)
-            } else {
-                Vec::new()
-            };
+        // Get file metadata once per file
+        let (file_lines, mtime) = file_metadata(path);
+
+        // Try tree-sitter structural detection
+        let file_type = detect_file_type(path);
+        let lang = match file_type {
+            FileType::Code(l) => Some(l),
+            _ => None,
+        };
+
+        let ts_language = lang.and_then(outline_language);
+
+        let (mut file_defs, file_dropped) = if let Some(ref ts_lang) = ts_language {
+            find_defs_treesitter(path, query, ts_lang, content, file_lines, mtime, opts)
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        if !file_dropped.is_empty() {
+            dropped
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .extend(file_dropped.into_iter().map(|l| (path.to_path_buf(), l)));
+        }
 
-            // Fallback: keyword heuristic for files without grammars
-            if file_defs.is_empty() && ts_language.is_none() {
-                file_defs = find_defs_heuristic_buf(path, query, &content, file_lines, mtime);
-            }
+        // Fallback: keyword heuristic for files without grammars
+        if file_defs.is_empty() && ts_language.is_none() {
+            file_defs = find_defs_heuristic_buf(path, query, content, file_lines, mtime, opts);
+        }
 
-            if !file_defs.is_empty() {
-                found_count.fetch_add(file_defs.len(), Ordering::Relaxed);
-                let mut all = matches
-                    .lock()
-                    .unwrap_or_else(std::sync::PoisonError::into_inner);
-                all.extend(file_defs);
-            }
+        if !file_defs.is_empty() {
+            let mut all = matches
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            all.extend(file_defs);
+        }
 
-            ignore::WalkState::Continue
-        })
-    });
+        ignore::WalkState::Continue
+    })?;
 
-    Ok(matches
-        .into_inner()
-        .unwrap_or_else(std::sync::PoisonError::into_inner))
+    Ok((
+        matches
+            .into_inner()
+            .unwrap_or_else(std::sync::PoisonError::into_inner),
+        dropped
+            .into_inner()
+            .unwrap_or_else(std::sync::PoisonError::into_inner),
+    ))
 }
 
 /// Tree-sitter structural definition detection.
 /// Accepts pre-read content — no redundant file read.
+/// Returns the definitions [`SearchOptions::kinds`] allows and the lines of those it doesn't.
 fn find_defs_treesitter(
     path: &Path,
     query: &str,
@@ -185,23 +343,36 @@ fn find_defs_treesitter(
     content: &str,
     file_lines: u32,
     mtime: SystemTime,
-) -> Vec<Match> {
+    opts: &SearchOptions,
+) -> (Vec<Match>, Vec<u32>) {
     let mut parser = tree_sitter::Parser::new();
     if parser.set_language(ts_lang).is_err() {
-        return Vec::new();
+        return (Vec::new(), Vec::new());
     }
 
     let Some(tree) = parser.parse(content, None) else {
-        return Vec::new();
+        return (Vec::new(), Vec::new());
     };
 
     let lines: Vec<&str> = content.lines().collect();
     let root = tree.root_node();
     let mut defs = Vec::new();
+    let mut dropped = Vec::new();
+
+    walk_for_definitions(
+        root,
+        query,
+        path,
+        &lines,
+        file_lines,
+        mtime,
+        opts,
+        &mut defs,
+        &mut dropped,
+        0,
+    );
 
-    walk_for_definitions(root, query, path, &lines, file_lines, mtime, &mut defs, 0);
-
-    defs
+    (defs, dropped)
 }
 
 /// Recursively walk AST nodes looking for definitions of the queried symbol.
@@ -212,7 +383,9 @@ fn walk_for_definitions(
     lines: &[&str],
     file_lines: u32,
     mtime: SystemTime,
+    opts: &SearchOptions,
     defs: &mut Vec<Match>,
+    dropped: &mut Vec<u32>,
     depth: usize,
 ) {
     if depth > 3 {
@@ -224,7 +397,12 @@ fn walk_for_definitions(
     if DEFINITION_KINDS.contains(&kind) {
         // Check if this node defines the queried symbol
         if let Some(name) = extract_definition_name(node, lines) {
-            if name == query {
+            let kinds = &opts.kinds;
+            let allowed = kinds.is_empty() || definition_kind(node).is_none_or(|k| kinds.allows(k));
+            let named = same_symbol(&name, query, opts);
+            if named && !allowed {
+                dropped.push(node.start_position().row as u32 + 1);
+            } else if named {
                 let line_num = node.start_position().row as u32 + 1;
                 let line_text = lines
                     .get(node.start_position().row)
@@ -242,7 +420,7 @@ fn walk_for_definitions(
                         node.start_position().row as u32 + 1,
                         node.end_position().row as u32 + 1,
                     )),
-                    def_name: Some(query.to_string()),
+                    def_name: Some(name),
                     def_weight: definition_weight(node.kind()),
                     impl_target: None,
                 });
@@ -253,7 +431,7 @@ fn walk_for_definitions(
         // `class X implements Interface` blocks when searching for the trait/interface.
         if kind == "impl_item" {
             if let Some(trait_name) = extract_impl_trait(node, lines) {
-                if trait_name == query {
+                if same_symbol(&trait_name, query, opts) {
                     let impl_type =
                         extract_impl_type(node, lines).unwrap_or_else(|| "<unknown>".to_string());
                     let line_num = node.start_position().row as u32 + 1;
@@ -273,15 +451,15 @@ fn walk_for_definitions(
                             node.start_position().row as u32 + 1,
                             node.end_position().row as u32 + 1,
                         )),
-                        def_name: Some(format!("impl {query} for {impl_type}")),
+                        def_name: Some(format!("impl {trait_name} for {impl_type}")),
                         def_weight: 80,
-                        impl_target: Some(query.to_string()),
+                        impl_target: Some(trait_name),
                     });
                 }
             }
         } else if kind == "class_declaration" || kind == "class_definition" {
             let interfaces = extract_implemented_interfaces(node, lines);
-            if interfaces.iter().any(|i| i == query) {
+            if let Some(interface) = interfaces.into_iter().find(|i| same_symbol(i, query, opts)) {
                 let class_name = extract_definition_name(node, lines)
                     .unwrap_or_else(|| "<anonymous>".to_string());
                 let line_num = node.start_position().row as u32 + 1;
@@ -301,9 +479,9 @@ fn walk_for_definitions(
                         node.start_position().row as u32 + 1,
                         node.end_position().row as u32 + 1,
                     )),
-                    def_name: Some(format!("{class_name} implements {query}")),
+                    def_name: Some(format!("{class_name} implements {interface}")),
                     def_weight: 80,
-                    impl_target: Some(query.to_string()),
+                    impl_target: Some(interface),
                 });
             }
         }
@@ -319,12 +497,34 @@ fn walk_for_definitions(
             lines,
             file_lines,
             mtime,
+            opts,
             defs,
+            dropped,
             depth + 1,
         );
     }
 }
 
+/// Whether a definition named `name` is the queried symbol: exact, or equal
+/// after [`SearchOptions::fold`]. Under [`SearchOptions::substring`] the
+/// query need only occur in the name.
+fn same_symbol(name: &str, query: &str, opts: &SearchOptions) -> bool {
+    if opts.substring {
+        return opts.fold(name).contains(opts.fold(query).as_ref());
+    }
+    name == query || opts.fold(name) == opts.fold(query)
+}
+
+/// Whether `word` occurs in `line` as a whole identifier, with no letter,
+/// digit or `_` right before or after it.
+fn contains_word(line: &str, word: &str) -> bool {
+    let ident = |c: char| c.is_alphanumeric() || c == '_';
+    line.match_indices(word).any(|(i, _)| {
+        !line[..i].chars().next_back().is_some_and(ident)
+            && !line[i + word.len()..].chars().next().is_some_and(ident)
+    })
+}
+
 /// Keyword heuristic fallback for files without tree-sitter grammars.
 /// Operates on pre-read buffer — no redundant file read.
 fn find_defs_heuristic_buf(
@@ -333,11 +533,19 @@ fn find_defs_heuristic_buf(
     content: &str,
     file_lines: u32,
     mtime: SystemTime,
+    opts: &SearchOptions,
 ) -> Vec<Match> {
     let mut defs = Vec::new();
+    let folded = opts.fold(query);
 
     for (i, line) in content.lines().enumerate() {
-        if line.contains(query) && is_definition_line(line) {
+        let line_folded = opts.fold(line);
+        let named = if opts.substring {
+            line_folded.contains(folded.as_ref())
+        } else {
+            contains_word(&line_folded, &folded)
+        };
+        if named && is_definition_line(line) {
             defs.push(Match {
                 path: path.to_path_buf(),
                 line: (i + 1) as u32,
@@ -359,83 +567,51 @@ fn find_defs_heuristic_buf(
 
 /// Find all usages via ripgrep (word-boundary matching).
 /// Collects per-file, locks once per file (not per line).
-/// Early termination once enough usages found.
 fn find_usages(
     query: &str,
     matcher: &RegexMatcher,
     scope: &Path,
     glob: Option<&str>,
+    opts: &SearchOptions,
 ) -> Result<Vec<Match>, TilthError> {
     let matches: Mutex<Vec<Match>> = Mutex::new(Vec::new());
-    // Relaxed: same reasoning as find_definitions — approximate early-quit, joined before read
-    let found_count = AtomicUsize::new(0);
-
-    let walker = super::walker(scope, glob)?;
-
-    walker.run(|| {
-        let matches = &matches;
-        let found_count = &found_count;
-
-        Box::new(move |entry| {
-            // Early termination: enough usages found
-            if found_count.load(Ordering::Relaxed) >= EARLY_QUIT_THRESHOLD_USAGES {
-                return ignore::WalkState::Quit;
-            }
-
-            let Ok(entry) = entry else {
-                return ignore::WalkState::Continue;
-            };
-
-            if !entry.file_type().is_some_and(|ft| ft.is_file()) {
-                return ignore::WalkState::Continue;
-            }
 
-            let path = entry.path();
+    super::visit_files(scope, glob, opts, |path, bytes| {
+        let (file_lines, mtime) = file_metadata(path);
 
-            // Skip oversized files
-            if let Ok(meta) = std::fs::metadata(path) {
-                if meta.len() > 500_000 {
-                    return ignore::WalkState::Continue;
-                }
-            }
+        let mut file_matches = Vec::new();
+        let mut searcher = Searcher::new();
 
-            let (file_lines, mtime) = file_metadata(path);
-
-            let mut file_matches = Vec::new();
-            let mut searcher = Searcher::new();
+        let _ = searcher.search_slice(
+            matcher,
+            bytes,
+            UTF8(|line_num, line| {
+                file_matches.push(Match {
+                    path: path.to_path_buf(),
+                    line: line_num as u32,
+                    text: line.trim_end().to_string(),
+                    is_definition: false,
+                    exact: line.contains(query),
+                    file_lines,
+                    mtime,
+                    def_range: None,
+                    def_name: None,
+                    def_weight: 0,
+                    impl_target: None,
+                });
+                Ok(true)
+            }),
+        );
 
-            let _ = searcher.search_path(
-                matcher,
-                path,
-                UTF8(|line_num, line| {
-                    file_matches.push(Match {
-                        path: path.to_path_buf(),
-                        line: line_num as u32,
-                        text: line.trim_end().to_string(),
-                        is_definition: false,
-                        exact: line.contains(query),
-                        file_lines,
-                        mtime,
-                        def_range: None,
-                        def_name: None,
-                        def_weight: 0,
-                        impl_target: None,
-                    });
-                    Ok(true)
-                }),
-            );
-
-            if !file_matches.is_empty() {
-                found_count.fetch_add(file_matches.len(), Ordering::Relaxed);
-                let mut all = matches
-                    .lock()
-                    .unwrap_or_else(std::sync::PoisonError::into_inner);
-                all.extend(file_matches);
-            }
+        if !file_matches.is_empty() {
+            let mut all = matches
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            all.extend(file_matches);
+        }
 
-            ignore::WalkState::Continue
-        })
-    });
+        ignore::WalkState::Continue
+    })?;
 
     Ok(matches
         .into_inner()
@@ -503,36 +679,95 @@ pub(crate) fn dispatch_tool(tool: &str) -> Result<String, String> {
 "#;
         let ts_lang = crate::lang::outline::outline_language(crate::types::Lang::Rust).unwrap();
 
-        let defs = find_defs_treesitter(
+        let (defs, _) = find_defs_treesitter(
             std::path::Path::new("test.rs"),
             "hello",
             &ts_lang,
             code,
             15,
             SystemTime::now(),
+            &SearchOptions::default(),
         );
         assert!(!defs.is_empty(), "should find 'hello' definition");
         assert!(defs[0].is_definition);
         assert!(defs[0].def_range.is_some());
 
-        let defs = find_defs_treesitter(
+        let (defs, _) = find_defs_treesitter(
             std::path::Path::new("test.rs"),
             "Foo",
             &ts_lang,
             code,
             15,
             SystemTime::now(),
+            &SearchOptions::default(),
         );
         assert!(!defs.is_empty(), "should find 'Foo' definition");
 
-        let defs = find_defs_treesitter(
+        let (defs, _) = find_defs_treesitter(
             std::path::Path::new("test.rs"),
             "dispatch_tool",
             &ts_lang,
             code,
             15,
             SystemTime::now(),
+            &SearchOptions::default(),
         );
         assert!(!defs.is_empty(), "should find 'dispatch_tool' definition");
     }
+
+    #[test]
+    fn rust_trait_methods_are_definitions() {
+        let code = "pub trait Shape {\n    fn area(&self) -> f64;\n}\n\nimpl Shape for Square {\n    fn area(&self) -> f64 {\n        1.0\n    }\n}\n";
+        let ts_lang = crate::lang::outline::outline_language(crate::types::Lang::Rust).unwrap();
+
+        let (defs, usages) = find_defs_treesitter(
+            std::path::Path::new("shape.rs"),
+            "area",
+            &ts_lang,
+            code,
+            15,
+            SystemTime::now(),
+            &SearchOptions::default(),
+        );
+        let lines: Vec<u32> = defs.iter().map(|d| d.line).collect();
+        assert_eq!(lines, [2, 6]);
+        assert!(usages.is_empty());
+    }
+
+    #[test]
+    fn rust_macro_rules_is_a_definition() {
+        let code = "macro_rules! square {\n    ($x:expr) => {\n        $x * $x\n    };\n}\n\nfn four() -> i32 {\n    square!(2)\n}\n";
+        let ts_lang = crate::lang::outline::outline_language(crate::types::Lang::Rust).unwrap();
+
+        let (defs, usages) = find_defs_treesitter(
+            std::path::Path::new("square.rs"),
+            "square",
+            &ts_lang,
+            code,
+            15,
+            SystemTime::now(),
+            &SearchOptions::default(),
+        );
+        let lines: Vec<u32> = defs.iter().map(|d| d.line).collect();
+        assert_eq!(lines, [1]);
+        assert!(usages.is_empty());
+    }
+
+    #[test]
+    fn c_function_like_macro_is_a_definition() {
+        let code = "#define MAX(a, b) ((a) > (b) ? (a) : (b))\n\nint top(int x, int y) {\n    return MAX(x, y);\n}\n";
+        let ts_lang = crate::lang::outline::outline_language(crate::types::Lang::C).unwrap();
+
+        let (defs, _) = find_defs_treesitter(
+            std::path::Path::new("max.h"),
+            "MAX",
+            &ts_lang,
+            code,
+            15,
+            SystemTime::now(),
+            &SearchOptions::default(),
+        );
+        let lines: Vec<u32> = defs.iter().map(|d| d.line).collect();
+        assert_eq!(lines, [1]);
+    }
 }
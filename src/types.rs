@@ -1,6 +1,9 @@
 use std::path::PathBuf;
 use std::time::SystemTime;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// What kind of query the user issued.
 #[derive(Debug)]
 pub enum QueryType {
@@ -21,6 +24,11 @@ pub enum QueryType {
 /// code never re-detects. Adding a language means adding an arm here
 /// and the compiler tells you everywhere else.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum Lang {
     Rust,
     TypeScript,
@@ -39,6 +47,53 @@ pub enum Lang {
     CSharp,
     Dockerfile,
     Make,
+    Nix,
+    R,
+    Lua,
+    Perl,
+    Zig,
+    OCaml,
+    Toml,
+    Yaml,
+    Vue,
+    /// A grammar added at runtime via `register_language`; index into the registry.
+    Registered(u16),
+}
+
+impl Lang {
+    /// The language's name in the JSON outputs, lowercase. A registered
+    /// grammar goes by the name it was registered under.
+    pub(crate) fn json_name(self) -> &'static str {
+        match self {
+            Self::Rust => "rust",
+            Self::TypeScript => "typescript",
+            Self::Tsx => "tsx",
+            Self::JavaScript => "javascript",
+            Self::Python => "python",
+            Self::Go => "go",
+            Self::Java => "java",
+            Self::Scala => "scala",
+            Self::C => "c",
+            Self::Cpp => "cpp",
+            Self::Ruby => "ruby",
+            Self::Php => "php",
+            Self::Swift => "swift",
+            Self::Kotlin => "kotlin",
+            Self::CSharp => "csharp",
+            Self::Dockerfile => "dockerfile",
+            Self::Make => "make",
+            Self::Nix => "nix",
+            Self::R => "r",
+            Self::Lua => "lua",
+            Self::Perl => "perl",
+            Self::Zig => "zig",
+            Self::OCaml => "ocaml",
+            Self::Toml => "toml",
+            Self::Yaml => "yaml",
+            Self::Vue => "vue",
+            Self::Registered(id) => crate::lang::registry::name(id),
+        }
+    }
 }
 
 /// File type as detected by extension. Determines outline strategy.
@@ -57,6 +112,12 @@ pub enum FileType {
 pub enum ViewMode {
     Full,
     Outline,
+    /// Names-only outline (`OutlineStyle::Names`).
+    Names,
+    /// Public API stub (`OutlineStyle::Stub`).
+    Stub,
+    /// Outline entries as JSON (`OutlineStyle::Json`).
+    Json,
     Keys,
     #[allow(dead_code)]
     HeadTail,
@@ -74,6 +135,9 @@ impl std::fmt::Display for ViewMode {
         match self {
             Self::Full => write!(f, "full"),
             Self::Outline => write!(f, "outline"),
+            Self::Names => write!(f, "names"),
+            Self::Stub => write!(f, "stub"),
+            Self::Json => write!(f, "json"),
             Self::Keys => write!(f, "keys"),
             Self::HeadTail => write!(f, "head+tail"),
             Self::Empty => write!(f, "empty"),
@@ -113,24 +177,72 @@ pub struct SearchResult {
     pub query: String,
     pub scope: PathBuf,
     pub matches: Vec<Match>,
+    /// Rank of the first match in `matches` — non-zero for later pages.
+    pub offset: usize,
     pub total_found: usize,
     pub definitions: usize,
     pub usages: usize,
 }
 
-/// A single entry in a code outline.
-#[derive(Debug)]
+/// A single entry in a code outline. With the `serde` feature it serializes
+/// field for field, children nested, as `--outline json` writes it.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OutlineEntry {
     pub kind: OutlineKind,
     pub name: String,
     pub start_line: u32,
     pub end_line: u32,
+    /// Byte offsets into the (BOM-stripped) source, as tree-sitter's
+    /// `node.byte_range()`; line-scanner languages record the span they read.
+    pub byte_range: std::ops::Range<usize>,
     pub signature: Option<String>,
     pub children: Vec<OutlineEntry>,
     pub doc: Option<String>,
+    /// Marked deprecated by attribute, annotation, decorator or `@deprecated` doc tag.
+    pub deprecated: bool,
+    /// Declared visibility as a marker: `pub`, `pub(crate)`, `priv`, `protected`.
+    /// `None` where the declaration carries no modifier.
+    pub visibility: Option<String>,
+    /// Modifiers worth flagging: `async`, `unsafe`, Ruby class-level `self`,
+    /// TypeScript `const` enums.
+    pub modifiers: Vec<String>,
+    /// Generic parameters shown after the name: `<T: Clone>`, Go's `[T any]`.
+    pub type_params: Option<String>,
+    /// Python decorators and Java annotations on the definition, one line
+    /// each: `@app.get("/")`, `@Override`.
+    pub decorators: Vec<String>,
+}
+
+impl OutlineEntry {
+    /// An entry with only its kind, name and lines set, everything else empty.
+    /// Scanners fill in the rest with `OutlineEntry { doc, ..OutlineEntry::new(..) }`.
+    #[must_use]
+    pub fn new(kind: OutlineKind, name: impl Into<String>, start_line: u32, end_line: u32) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+            start_line,
+            end_line,
+            byte_range: 0..0,
+            signature: None,
+            children: Vec::new(),
+            doc: None,
+            deprecated: false,
+            visibility: None,
+            modifiers: Vec::new(),
+            type_params: None,
+            decorators: Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum OutlineKind {
     Import,
     Function,
@@ -146,12 +258,39 @@ pub enum OutlineKind {
     #[allow(dead_code)]
     Property,
     Module,
+    /// Rust `macro_rules!` definitions and C/C++ function-like `#define`s.
+    /// Object-like `#define`s are [`Constant`](Self::Constant)s.
+    Macro,
     #[allow(dead_code)]
     TestSuite,
     #[allow(dead_code)]
     TestCase,
 }
 
+impl OutlineKind {
+    /// The kind's name in the JSON outputs, `snake_case`.
+    pub(crate) fn json_name(self) -> &'static str {
+        match self {
+            Self::Import => "import",
+            Self::Function => "function",
+            Self::Class => "class",
+            Self::Struct => "struct",
+            Self::Interface => "interface",
+            Self::TypeAlias => "type_alias",
+            Self::Enum => "enum",
+            Self::Constant => "constant",
+            Self::Variable => "variable",
+            Self::ImmutableVariable => "immutable_variable",
+            Self::Export => "export",
+            Self::Property => "property",
+            Self::Module => "module",
+            Self::Macro => "macro",
+            Self::TestSuite => "test_suite",
+            Self::TestCase => "test_case",
+        }
+    }
+}
+
 /// Detect test files by path patterns.
 pub(crate) fn is_test_file(path: &std::path::Path) -> bool {
     let s = path.to_string_lossy();